@@ -1,4 +1,5 @@
 use crate::languages::{Language, LanguageConsts};
+use std::process::Command;
 use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -31,10 +32,155 @@ impl FromStr for BuildSystem {
 	}
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Libc {
+	#[default]
+	Glibc,
+	Musl,
+}
+
+impl Libc {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Libc::Glibc => "glibc",
+			Libc::Musl => "musl",
+		}
+	}
+}
+
+impl std::fmt::Display for Libc {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.as_str())
+	}
+}
+
+impl FromStr for Libc {
+	type Err = anyhow::Error;
+
+	fn from_str(input: &str) -> Result<Libc, Self::Err> {
+		match input.to_lowercase().as_str() {
+			"glibc" | "gnu" => Ok(Libc::Glibc),
+			"musl" => Ok(Libc::Musl),
+			_ => anyhow::bail!("Unsupported libc: {}. Use 'glibc' or 'musl'", input),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BuildProfile {
+	pub libc: Libc,
+	pub static_link: bool,
+}
+
+impl BuildProfile {
+	pub fn new(libc: Libc, static_link: bool) -> Self {
+		BuildProfile { libc, static_link }
+	}
+
+	pub fn is_static(&self) -> bool {
+		self.static_link || self.libc == Libc::Musl
+	}
+
+	pub fn compiler(&self, language: Language) -> Compiler {
+		match self.libc {
+			Libc::Glibc => Compiler::new(language.cc()),
+			Libc::Musl => musl_compiler(language),
+		}
+	}
+
+	pub fn check_toolchain(&self, language: Language) -> anyhow::Result<()> {
+		if self.libc != Libc::Musl || !matches!(language, Language::Fortran | Language::CFortran) {
+			return Ok(());
+		}
+		let compiler = musl_compiler(language);
+		if !tool_available(&compiler.program) {
+			anyhow::bail!(
+				"No musl Fortran toolchain found: install {} or build with glibc",
+				compiler.program
+			);
+		}
+		Ok(())
+	}
+
+	pub fn ldflags(&self) -> &'static str {
+		if self.is_static() {
+			"-static"
+		} else {
+			""
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Compiler {
+	pub program: String,
+	pub args: Vec<String>,
+}
+
+impl Compiler {
+	fn new(program: &str) -> Self {
+		Compiler {
+			program: program.to_string(),
+			args: Vec::new(),
+		}
+	}
+
+	pub fn command_line(&self) -> String {
+		std::iter::once(self.program.as_str())
+			.chain(self.args.iter().map(String::as_str))
+			.collect::<Vec<_>>()
+			.join(" ")
+	}
+}
+
+fn musl_compiler(language: Language) -> Compiler {
+	let target = format!("{}-linux-musl", std::env::consts::ARCH);
+	let zig_driver = match language {
+		Language::C | Language::ObjC => "cc",
+		Language::Cpp | Language::Mixed | Language::ObjCpp => "c++",
+		Language::Fortran | Language::CFortran => {
+			return Compiler::new(&format!("{}-gfortran", target))
+		}
+	};
+
+	if matches!(language, Language::C | Language::ObjC) && tool_available("musl-gcc") {
+		return Compiler::new("musl-gcc");
+	}
+
+	if tool_available("zig") {
+		return Compiler {
+			program: "zig".to_string(),
+			args: vec![zig_driver.to_string(), "-target".to_string(), target],
+		};
+	}
+
+	match language {
+		Language::C | Language::ObjC => Compiler::new("musl-gcc"),
+		_ => Compiler::new(&format!("{}-g++", target)),
+	}
+}
+
+fn tool_available(program: &str) -> bool {
+	Command::new(program)
+		.arg("--version")
+		.output()
+		.map(|o| o.status.success())
+		.unwrap_or(false)
+}
+
 pub trait BuildSystemGenerator {
 	fn name(&self) -> &'static str;
-	fn generate_build_file(&self, language: Language, project_name: &str) -> String;
+	fn generate_build_file_with_profile(
+		&self,
+		language: Language,
+		project_name: &str,
+		profile: &BuildProfile,
+	) -> String;
 	fn extension(&self) -> &'static str;
+
+	fn generate_build_file(&self, language: Language, project_name: &str) -> String {
+		self.generate_build_file_with_profile(language, project_name, &BuildProfile::default())
+	}
 }
 
 pub struct MakefileGenerator;
@@ -44,8 +190,13 @@ impl BuildSystemGenerator for MakefileGenerator {
 		"Makefile"
 	}
 
-	fn generate_build_file(&self, language: Language, project_name: &str) -> String {
-		language.generate_makefile_content(project_name)
+	fn generate_build_file_with_profile(
+		&self,
+		language: Language,
+		project_name: &str,
+		profile: &BuildProfile,
+	) -> String {
+		language.generate_makefile_content_with_profile(project_name, profile)
 	}
 
 	fn extension(&self) -> &'static str {
@@ -55,50 +206,140 @@ impl BuildSystemGenerator for MakefileGenerator {
 
 pub struct CMakeGenerator;
 
+type CMakeLanguage = (Language, &'static str, Option<&'static str>);
+
 impl BuildSystemGenerator for CMakeGenerator {
 	fn name(&self) -> &'static str {
 		"CMakeLists.txt"
 	}
 
-	fn generate_build_file(&self, language: Language, project_name: &str) -> String {
-		match language {
-			Language::C => format!(
-				"cmake_minimum_required(VERSION 3.15)\n\
-				project({} C)\n\
-				\n\
-				set(CMAKE_C_STANDARD 11)\n\
-				set(CMAKE_C_STANDARD_REQUIRED ON)\n\
-				set(CMAKE_C_FLAGS \"${{CMAKE_C_FLAGS}} -Wall -Wextra -Werror\")\n\
-				set(CMAKE_RUNTIME_OUTPUT_DIRECTORY ${{CMAKE_SOURCE_DIR}}/bin)\n\
-				\n\
-				file(GLOB_RECURSE SOURCES \"src/*.c\")\n\
-				\n\
-				add_executable(${{PROJECT_NAME}} ${{SOURCES}})\n\
-				target_include_directories(${{PROJECT_NAME}} PRIVATE \"${{CMAKE_CURRENT_SOURCE_DIR}}/include\")\n\
-				\n\
-				# Optional: Installation\n\
-				install(TARGETS ${{PROJECT_NAME}} DESTINATION bin)\n",
-				project_name
+	fn generate_build_file_with_profile(
+		&self,
+		language: Language,
+		project_name: &str,
+		profile: &BuildProfile,
+	) -> String {
+		let (languages, globs): (&[CMakeLanguage], &[&str]) = match language {
+			Language::C => (&[(Language::C, "C", Some("11"))], &["src/*.c"]),
+			Language::Cpp => (
+				&[(Language::Cpp, "CXX", Some("17"))],
+				&["src/*.cpp", "src/*.cc", "src/*.cxx", "src/*.C"],
+			),
+			Language::Mixed => (
+				&[
+					(Language::C, "C", Some("11")),
+					(Language::Cpp, "CXX", Some("17")),
+				],
+				&[
+					"src/*.c",
+					"src/*.cpp",
+					"src/*.cc",
+					"src/*.cxx",
+					"src/*.C",
+					"src/*.S",
+					"src/*.s",
+				],
 			),
-			Language::Cpp => format!(
-				"cmake_minimum_required(VERSION 3.15)\n\
-				project({} CXX)\n\
-				\n\
-				set(CMAKE_CXX_STANDARD 17)\n\
-				set(CMAKE_CXX_STANDARD_REQUIRED ON)\n\
-				set(CMAKE_CXX_FLAGS \"${{CMAKE_CXX_FLAGS}} -Wall -Wextra -Werror\")\n\
-				set(CMAKE_RUNTIME_OUTPUT_DIRECTORY ${{CMAKE_SOURCE_DIR}}/bin)\n\
-				\n\
-				file(GLOB_RECURSE SOURCES \"src/*.cpp\")\n\
-				\n\
-				add_executable(${{PROJECT_NAME}} ${{SOURCES}})\n\
-				target_include_directories(${{PROJECT_NAME}} PRIVATE \"${{CMAKE_CURRENT_SOURCE_DIR}}/include\")\n\
-				\n\
-				# Optional: Installation\n\
-				install(TARGETS ${{PROJECT_NAME}} DESTINATION bin)\n",
-				project_name
+			Language::ObjC => (&[(Language::ObjC, "OBJC", Some("11"))], &["src/*.m"]),
+			Language::ObjCpp => (&[(Language::ObjCpp, "OBJCXX", Some("17"))], &["src/*.mm"]),
+			Language::Fortran => (&[(Language::Fortran, "Fortran", None)], &["src/*.f90"]),
+			Language::CFortran => (
+				&[
+					(Language::C, "C", Some("11")),
+					(Language::Fortran, "Fortran", None),
+				],
+				&["src/*.c", "src/*.f90"],
 			),
+		};
+		let gnustep = matches!(language, Language::ObjC | Language::ObjCpp);
+
+		let mut project_languages: Vec<&str> = languages.iter().map(|l| l.1).collect();
+		if language == Language::Mixed {
+			project_languages.push("ASM");
+		}
+		let mut toolchain = String::new();
+		let mut settings = String::new();
+		let mut linking = String::new();
+		let mut warnings = "-Wall -Wextra -Werror";
+		if gnustep {
+			settings.push_str(
+				"execute_process(COMMAND gnustep-config --objc-flags OUTPUT_VARIABLE GNUSTEP_OBJC_FLAGS OUTPUT_STRIP_TRAILING_WHITESPACE)\n\
+				execute_process(COMMAND gnustep-config --base-libs OUTPUT_VARIABLE GNUSTEP_BASE_LIBS OUTPUT_STRIP_TRAILING_WHITESPACE)\n\
+				separate_arguments(GNUSTEP_BASE_LIBS UNIX_COMMAND \"${GNUSTEP_BASE_LIBS}\")\n",
+			);
+			warnings = "${GNUSTEP_OBJC_FLAGS} -Wall -Wextra";
 		}
+		for (language, lang, standard) in languages {
+			if profile.libc == Libc::Musl {
+				let compiler = profile.compiler(*language);
+				toolchain.push_str(&format!(
+					"set(CMAKE_{}_COMPILER {})\n",
+					lang, compiler.program
+				));
+				if !compiler.args.is_empty() {
+					toolchain.push_str(&format!(
+						"set(CMAKE_{}_COMPILER_ARG1 \"{}\")\n",
+						lang,
+						compiler.args.join(" ")
+					));
+				}
+			}
+			if let Some(standard) = standard {
+				settings.push_str(&format!(
+					"set(CMAKE_{lang}_STANDARD {standard})\n\
+					set(CMAKE_{lang}_STANDARD_REQUIRED ON)\n",
+					lang = lang,
+					standard = standard
+				));
+			}
+			settings.push_str(&format!(
+				"set(CMAKE_{lang}_FLAGS \"${{CMAKE_{lang}_FLAGS}} {warnings}\")\n",
+				lang = lang,
+				warnings = warnings
+			));
+		}
+		if matches!(language, Language::Fortran | Language::CFortran) {
+			settings.push_str("set(CMAKE_Fortran_MODULE_DIRECTORY ${CMAKE_BINARY_DIR}/modules)\n");
+		}
+		if profile.is_static() {
+			linking.push_str(
+				"set(CMAKE_EXE_LINKER_FLAGS \"${CMAKE_EXE_LINKER_FLAGS} -static\")\n\
+				set(CMAKE_FIND_LIBRARY_SUFFIXES \".a\")\n\
+				set(BUILD_SHARED_LIBS OFF)\n",
+			);
+		}
+
+		format!(
+			"cmake_minimum_required(VERSION {})\n\
+			{}project({} {})\n\
+			\n\
+			{}{}set(CMAKE_RUNTIME_OUTPUT_DIRECTORY ${{CMAKE_SOURCE_DIR}}/bin)\n\
+			\n\
+			file(GLOB_RECURSE SOURCES CONFIGURE_DEPENDS {})\n\
+			\n\
+			add_executable(${{PROJECT_NAME}} ${{SOURCES}})\n\
+			target_include_directories(${{PROJECT_NAME}} PRIVATE \"${{CMAKE_CURRENT_SOURCE_DIR}}/include\")\n\
+			{}\
+			\n\
+			# Optional: Installation\n\
+			install(TARGETS ${{PROJECT_NAME}} DESTINATION bin)\n",
+			if gnustep { "3.16" } else { "3.15" },
+			toolchain,
+			project_name,
+			project_languages.join(" "),
+			settings,
+			linking,
+			globs
+				.iter()
+				.map(|glob| format!("\"{}\"", glob))
+				.collect::<Vec<_>>()
+				.join(" "),
+			if gnustep {
+				"target_link_libraries(${PROJECT_NAME} PRIVATE ${GNUSTEP_BASE_LIBS})\n"
+			} else {
+				""
+			}
+		)
 	}
 
 	fn extension(&self) -> &'static str {
@@ -0,0 +1,132 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::config::{self, ProjectConfig};
+use crate::constants::project;
+use crate::features::detect_build_system;
+use crate::BuildSystem;
+
+const ELF_MAGIC: &[u8] = b"\x7fELF";
+const PT_INTERP: u32 = 3;
+
+pub fn build_project() -> Result<()> {
+	let build_system = detect_build_system()?
+		.context("No build system detected in current project. Cannot build.")?;
+	let profile = ProjectConfig::load()?
+		.map(|c| c.profile)
+		.unwrap_or_default();
+
+	match build_system {
+		BuildSystem::Makefile => run("make", &[])?,
+		BuildSystem::CMake => {
+			run(
+				"cmake",
+				&[
+					"-S",
+					".",
+					"-B",
+					project::BUILD_DIR,
+					"-DCMAKE_BUILD_TYPE=Release",
+				],
+			)?;
+			run("cmake", &["--build", project::BUILD_DIR])?;
+		}
+	}
+
+	let binary = output_binary();
+	println!("✓ Built {}", binary.display());
+
+	if profile.is_static() {
+		verify_static_binary(&binary)?;
+		println!("✓ Verified static binary: no dynamic interpreter present");
+	}
+
+	Ok(())
+}
+
+pub fn output_binary() -> PathBuf {
+	Path::new(project::BIN_DIR).join(config::project_name())
+}
+
+pub fn verify_static_binary(path: &Path) -> Result<()> {
+	if has_dynamic_interpreter(path)? {
+		anyhow::bail!(
+			"{} requests a dynamic interpreter (PT_INTERP); the binary is not fully static",
+			path.display()
+		);
+	}
+	Ok(())
+}
+
+pub fn has_dynamic_interpreter(path: &Path) -> Result<bool> {
+	let bytes =
+		fs::read(path).with_context(|| format!("Failed to read binary {}", path.display()))?;
+	elf_has_interpreter(&bytes).with_context(|| format!("Failed to inspect {}", path.display()))
+}
+
+pub fn elf_has_interpreter(bytes: &[u8]) -> Result<bool> {
+	if bytes.len() < 0x34 || &bytes[..4] != ELF_MAGIC {
+		anyhow::bail!("Not an ELF file");
+	}
+
+	let is_64 = match bytes[4] {
+		1 => false,
+		2 => true,
+		class => anyhow::bail!("Unknown ELF class {}", class),
+	};
+	let little_endian = match bytes[5] {
+		1 => true,
+		2 => false,
+		data => anyhow::bail!("Unknown ELF data encoding {}", data),
+	};
+
+	let read = |offset: usize, size: usize| -> Result<u64> {
+		let field = offset
+			.checked_add(size)
+			.and_then(|end| bytes.get(offset..end))
+			.context("Truncated ELF header")?;
+		let mut value = 0u64;
+		for i in 0..size {
+			let byte = if little_endian {
+				field[size - 1 - i]
+			} else {
+				field[i]
+			};
+			value = (value << 8) | u64::from(byte);
+		}
+		Ok(value)
+	};
+
+	let (phoff, phentsize, phnum) = if is_64 {
+		(read(0x20, 8)?, read(0x36, 2)?, read(0x38, 2)?)
+	} else {
+		(read(0x1c, 4)?, read(0x2a, 2)?, read(0x2c, 2)?)
+	};
+
+	for index in 0..phnum {
+		let offset = index
+			.checked_mul(phentsize)
+			.and_then(|relative| phoff.checked_add(relative))
+			.and_then(|offset| usize::try_from(offset).ok())
+			.context("Truncated ELF header")?;
+		if read(offset, 4)? as u32 == PT_INTERP {
+			return Ok(true);
+		}
+	}
+
+	Ok(false)
+}
+
+fn run(program: &str, args: &[&str]) -> Result<()> {
+	let status = Command::new(program)
+		.args(args)
+		.status()
+		.with_context(|| format!("Failed to run {}. Is it installed?", program))?;
+
+	if !status.success() {
+		anyhow::bail!("{} {} failed", program, args.join(" "));
+	}
+	Ok(())
+}
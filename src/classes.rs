@@ -0,0 +1,191 @@
+use anyhow::Result;
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::constants::project;
+use crate::languages::Language;
+use crate::sources::{
+	ensure_makefile_include_dir, ensure_recursive_makefile_sources, header_extension,
+	normalize_source_name, source_layout, write_file,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SpecialMembers {
+	#[default]
+	Default,
+	MoveOnly,
+	Immovable,
+	None,
+}
+
+impl FromStr for SpecialMembers {
+	type Err = anyhow::Error;
+
+	fn from_str(input: &str) -> Result<Self, Self::Err> {
+		match input.to_lowercase().as_str() {
+			"default" => Ok(SpecialMembers::Default),
+			"move-only" => Ok(SpecialMembers::MoveOnly),
+			"immovable" => Ok(SpecialMembers::Immovable),
+			"none" => Ok(SpecialMembers::None),
+			_ => anyhow::bail!(
+				"Unsupported rule-of-five option: {}. Use 'default', 'move-only', 'immovable' or 'none'",
+				input
+			),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ClassOptions {
+	pub name: String,
+	pub namespace: Option<String>,
+	pub dir: Option<String>,
+	pub special_members: SpecialMembers,
+	pub pragma_once: bool,
+	pub private: bool,
+}
+
+pub fn add_class(options: &ClassOptions) -> Result<()> {
+	if !Path::new(project::SRC_DIR).exists() {
+		anyhow::bail!(
+			"src directory not found. Cannot add a class.\n\
+			Maybe try creating a new project or initializing a new project in the current directory"
+		);
+	}
+	if !is_identifier(&options.name) {
+		anyhow::bail!("Invalid class name: {}", options.name);
+	}
+	let namespaces = namespace_segments(options.namespace.as_deref())?;
+
+	if let Some(language) =
+		Language::detect()?.filter(|l| !matches!(l, Language::Cpp | Language::Mixed))
+	{
+		anyhow::bail!(
+			"sticks class generates C++ classes, but this is a {} project",
+			language
+		);
+	}
+	let extension = "cpp";
+	let header_extension = header_extension("hpp")?;
+
+	let path = match options.dir.as_deref().map(|d| d.trim_matches('/')) {
+		Some(dir) if !dir.is_empty() => {
+			normalize_source_name(&format!("{}/{}", dir, options.name), &[])?
+		}
+		_ => options.name.clone(),
+	};
+	let layout = source_layout(&path, extension, &header_extension, options.private);
+	for existing in [&layout.source, &layout.header] {
+		if existing.exists() {
+			anyhow::bail!("{} already exists", existing.display());
+		}
+	}
+
+	let guard = (!options.pragma_once).then_some(layout.guard.as_str());
+	write_file(&layout.header, &class_header(options, &namespaces, guard))?;
+	write_file(
+		&layout.source,
+		&class_source(&options.name, &namespaces, &layout.include),
+	)?;
+	if !options.private {
+		ensure_makefile_include_dir()?;
+	}
+	if path.contains('/') {
+		ensure_recursive_makefile_sources(extension)?;
+	}
+
+	println!(
+		"✓ Added class {}{} ({}, {})",
+		namespaces
+			.iter()
+			.map(|n| format!("{}::", n))
+			.collect::<String>(),
+		options.name,
+		layout.header.display(),
+		layout.source.display()
+	);
+	Ok(())
+}
+
+pub fn class_header(options: &ClassOptions, namespaces: &[String], guard: Option<&str>) -> String {
+	let name = &options.name;
+	let mut header = match guard {
+		Some(guard) => format!("#ifndef {}\n#define {}\n\n", guard, guard),
+		None => "#pragma once\n\n".to_string(),
+	};
+	header.push_str(&open_namespace(namespaces));
+
+	header.push_str(&format!(
+		"class {name} {{\npublic:\n\t{name}();\n\t~{name}();\n",
+		name = name
+	));
+	let special = match options.special_members {
+		SpecialMembers::Default => Some(("default", "default")),
+		SpecialMembers::MoveOnly => Some(("delete", "default")),
+		SpecialMembers::Immovable => Some(("delete", "delete")),
+		SpecialMembers::None => None,
+	};
+	if let Some((copy, moves)) = special {
+		header.push_str(&format!(
+			"\n\t{name}(const {name}&) = {copy};\n\
+			\t{name}& operator=(const {name}&) = {copy};\n\
+			\t{name}({name}&&) noexcept = {moves};\n\
+			\t{name}& operator=({name}&&) noexcept = {moves};\n",
+			name = name,
+			copy = copy,
+			moves = moves
+		));
+	}
+	header.push_str("};\n");
+
+	header.push_str(&close_namespace(namespaces));
+	if let Some(guard) = guard {
+		header.push_str(&format!("\n#endif /* {} */\n", guard));
+	}
+	header
+}
+
+pub fn class_source(name: &str, namespaces: &[String], include: &str) -> String {
+	let mut source = format!("#include \"{}\"\n\n", include);
+	source.push_str(&open_namespace(namespaces));
+	source.push_str(&format!(
+		"{name}::{name}() {{}}\n\n{name}::~{name}() {{}}\n",
+		name = name
+	));
+	source.push_str(&close_namespace(namespaces));
+	source
+}
+
+fn open_namespace(namespaces: &[String]) -> String {
+	if namespaces.is_empty() {
+		String::new()
+	} else {
+		format!("namespace {} {{\n\n", namespaces.join("::"))
+	}
+}
+
+fn close_namespace(namespaces: &[String]) -> String {
+	if namespaces.is_empty() {
+		String::new()
+	} else {
+		format!("\n}} // namespace {}\n", namespaces.join("::"))
+	}
+}
+
+fn namespace_segments(namespace: Option<&str>) -> Result<Vec<String>> {
+	let namespace = match namespace.map(str::trim).filter(|n| !n.is_empty()) {
+		Some(namespace) => namespace,
+		None => return Ok(Vec::new()),
+	};
+	let segments: Vec<String> = namespace.split("::").map(String::from).collect();
+	if !segments.iter().all(|s| is_identifier(s)) {
+		anyhow::bail!("Invalid namespace: {}", namespace);
+	}
+	Ok(segments)
+}
+
+fn is_identifier(name: &str) -> bool {
+	let mut chars = name.chars();
+	matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+		&& chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
@@ -0,0 +1,420 @@
+use std::ops::Range;
+
+pub const REGION_BEGIN: &str = "# sticks:begin";
+pub const REGION_END: &str = "# sticks:end";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Placement<'a> {
+	BeforeCommand(&'a str),
+	AfterCommand(&'a str),
+	AfterTarget,
+	AfterLine(&'a str),
+	End,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArgumentKind {
+	Unquoted,
+	Quoted,
+	Bracket,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Argument {
+	pub value: String,
+	pub kind: ArgumentKind,
+	pub span: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Command {
+	pub name: String,
+	pub arguments: Vec<Argument>,
+	pub start: usize,
+	pub end: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region {
+	pub name: String,
+	pub start: usize,
+	pub end: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Listfile {
+	pub commands: Vec<Command>,
+	pub regions: Vec<Region>,
+	continuation: Vec<usize>,
+}
+
+impl Command {
+	pub fn is(&self, name: &str) -> bool {
+		self.name.eq_ignore_ascii_case(name)
+	}
+
+	pub fn values(&self) -> Vec<&str> {
+		self.arguments.iter().map(|a| a.value.as_str()).collect()
+	}
+}
+
+impl Listfile {
+	pub fn command(&self, name: &str) -> Option<&Command> {
+		self.commands.iter().find(|c| c.is(name))
+	}
+
+	pub fn region(&self, name: &str) -> Option<&Region> {
+		self.regions.iter().find(|r| r.name == name)
+	}
+
+	pub fn user_commands(&self) -> impl Iterator<Item = &Command> {
+		self.commands.iter().filter(|command| {
+			!self
+				.regions
+				.iter()
+				.any(|r| r.start < command.start && command.start < r.end)
+		})
+	}
+
+	fn starts_line(&self, line: usize) -> bool {
+		!self.continuation.contains(&line)
+	}
+}
+
+pub fn parse(content: &str) -> Listfile {
+	let mut lexer = Lexer {
+		chars: content.chars().collect(),
+		pos: 0,
+		offset: 0,
+		line: 0,
+	};
+	let mut listfile = Listfile::default();
+	let mut open: Vec<(String, usize)> = Vec::new();
+
+	while let Some(c) = lexer.peek(0) {
+		let start = lexer.line;
+		if c.is_whitespace() {
+			lexer.bump();
+		} else if c == '#' {
+			let own_line = lexer.at_line_start();
+			match lexer.comment() {
+				Some(text) if own_line => {
+					let text = text.trim();
+					if let Some(name) = marker_name(text, REGION_BEGIN) {
+						open.push((name.to_string(), start));
+					} else if let Some(name) = marker_name(text, REGION_END) {
+						if let Some(index) = open.iter().rposition(|(n, _)| n == name) {
+							let (name, begin) = open.remove(index);
+							listfile.regions.push(Region {
+								name,
+								start: begin,
+								end: start,
+							});
+						}
+					}
+				}
+				Some(_) => {}
+				None => listfile.continuation.extend(start + 1..=lexer.line),
+			}
+		} else if c.is_ascii_alphabetic() || c == '_' {
+			let name = lexer.identifier();
+			while matches!(lexer.peek(0), Some(' ') | Some('\t')) {
+				lexer.bump();
+			}
+			if lexer.peek(0) == Some('(') {
+				let arguments = lexer.arguments();
+				listfile.continuation.extend(start + 1..=lexer.line);
+				listfile.commands.push(Command {
+					name,
+					arguments,
+					start,
+					end: lexer.line,
+				});
+			}
+		} else {
+			lexer.bump();
+		}
+	}
+
+	listfile.regions.sort_by_key(|r| r.start);
+	listfile
+}
+
+pub fn region_names(content: &str) -> Vec<String> {
+	parse(content).regions.into_iter().map(|r| r.name).collect()
+}
+
+pub fn read_region(content: &str, name: &str) -> Option<String> {
+	let lines: Vec<&str> = content.lines().collect();
+	let region = parse(content).region(name)?.clone();
+	Some(lines[region.start + 1..region.end].join("\n"))
+}
+
+pub fn set_region(content: &str, name: &str, body: &str, placement: Placement) -> String {
+	let listfile = parse(content);
+	let mut lines: Vec<String> = content.lines().map(String::from).collect();
+	let mut region = vec![format!("{} {}", REGION_BEGIN, name)];
+	region.extend(body.lines().map(String::from));
+	region.push(format!("{} {}", REGION_END, name));
+
+	if let Some(existing) = listfile.region(name) {
+		lines.splice(existing.start..=existing.end, region);
+		return join(lines);
+	}
+
+	let insert_at = match placement {
+		Placement::BeforeCommand(command) => listfile.command(command).map(|c| c.start),
+		Placement::AfterCommand(command) => listfile.command(command).map(|c| c.end + 1),
+		Placement::AfterTarget => target_end(&listfile, &lines).map(|end| end + 1),
+		Placement::AfterLine(prefix) => lines
+			.iter()
+			.enumerate()
+			.position(|(index, l)| listfile.starts_line(index) && l.starts_with(prefix))
+			.map(|pos| skip_regions(&listfile, &lines, pos) + 1),
+		Placement::End => None,
+	};
+
+	match insert_at {
+		Some(index) => {
+			if !matches!(placement, Placement::BeforeCommand(_)) {
+				region.insert(0, String::new());
+			} else {
+				region.push(String::new());
+			}
+			lines.splice(index..index, region);
+		}
+		None => {
+			while lines.last().is_some_and(|l| l.trim().is_empty()) {
+				lines.pop();
+			}
+			lines.push(String::new());
+			lines.extend(region);
+		}
+	}
+
+	join(lines)
+}
+
+pub fn remove_region(content: &str, name: &str) -> String {
+	let mut lines: Vec<String> = content.lines().map(String::from).collect();
+	if let Some(region) = parse(content).region(name) {
+		let (start, end) = (region.start, region.end);
+		let blank_before = start > 0 && lines[start - 1].trim().is_empty();
+		let blank_after = lines.get(end + 1).is_some_and(|l| l.trim().is_empty());
+		let (start, end) = if blank_after {
+			(start, end + 1)
+		} else if blank_before {
+			(start - 1, end)
+		} else {
+			(start, end)
+		};
+		lines.drain(start..=end);
+	}
+	join(lines)
+}
+
+fn marker_name<'a>(text: &'a str, marker: &str) -> Option<&'a str> {
+	let name = text.strip_prefix(marker)?;
+	if !name.starts_with(char::is_whitespace) {
+		return None;
+	}
+	Some(name.trim()).filter(|name| !name.is_empty())
+}
+
+fn target_end(listfile: &Listfile, lines: &[String]) -> Option<usize> {
+	let target = listfile
+		.command("add_executable")
+		.or_else(|| listfile.command("add_library"))?;
+	let mut end = target.end;
+	for command in listfile.commands.iter().filter(|c| c.start > target.start) {
+		if command.start != end + 1 || !command.name.to_lowercase().starts_with("target_") {
+			break;
+		}
+		end = command.end;
+	}
+	Some(skip_regions(listfile, lines, end))
+}
+
+fn skip_regions(listfile: &Listfile, lines: &[String], mut end: usize) -> usize {
+	loop {
+		let next = match lines.get(end + 1) {
+			Some(line) if line.trim().is_empty() => end + 2,
+			_ => end + 1,
+		};
+		match listfile.regions.iter().find(|r| r.start == next) {
+			Some(region) => end = region.end,
+			None => break,
+		}
+	}
+	end
+}
+
+fn join(lines: Vec<String>) -> String {
+	let mut output = lines.join("\n");
+	output.push('\n');
+	output
+}
+
+struct Lexer {
+	chars: Vec<char>,
+	pos: usize,
+	offset: usize,
+	line: usize,
+}
+
+impl Lexer {
+	fn peek(&self, offset: usize) -> Option<char> {
+		self.chars.get(self.pos + offset).copied()
+	}
+
+	fn bump(&mut self) -> Option<char> {
+		let c = self.peek(0)?;
+		self.pos += 1;
+		self.offset += c.len_utf8();
+		if c == '\n' {
+			self.line += 1;
+		}
+		Some(c)
+	}
+
+	fn at_line_start(&self) -> bool {
+		self.chars[..self.pos]
+			.iter()
+			.rev()
+			.take_while(|c| **c != '\n')
+			.all(|c| c.is_whitespace())
+	}
+
+	fn identifier(&mut self) -> String {
+		let mut name = String::new();
+		while let Some(c) = self
+			.peek(0)
+			.filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+		{
+			name.push(c);
+			self.bump();
+		}
+		name
+	}
+
+	fn bracket_level(&self) -> Option<usize> {
+		if self.peek(0) != Some('[') {
+			return None;
+		}
+		let mut level = 0;
+		while self.peek(1 + level) == Some('=') {
+			level += 1;
+		}
+		(self.peek(1 + level) == Some('[')).then_some(level)
+	}
+
+	fn bracket(&mut self, level: usize) -> String {
+		for _ in 0..level + 2 {
+			self.bump();
+		}
+		if self.peek(0) == Some('\n') {
+			self.bump();
+		}
+		let close: String = format!("]{}]", "=".repeat(level));
+		let mut text = String::new();
+		while let Some(c) = self.bump() {
+			text.push(c);
+			if text.ends_with(&close) {
+				text.truncate(text.len() - close.len());
+				break;
+			}
+		}
+		text
+	}
+
+	fn comment(&mut self) -> Option<String> {
+		self.bump();
+		if let Some(level) = self.bracket_level() {
+			self.bracket(level);
+			return None;
+		}
+		let mut text = String::from("#");
+		while let Some(c) = self.peek(0).filter(|c| *c != '\n') {
+			text.push(c);
+			self.bump();
+		}
+		Some(text)
+	}
+
+	fn quoted(&mut self) -> String {
+		self.bump();
+		let mut value = String::new();
+		while let Some(c) = self.bump() {
+			match c {
+				'"' => break,
+				'\\' => match self.bump() {
+					Some('\n') => {}
+					Some('n') => value.push('\n'),
+					Some('t') => value.push('\t'),
+					Some('r') => value.push('\r'),
+					Some(';') => value.push_str("\\;"),
+					Some(other) => value.push(other),
+					None => {}
+				},
+				other => value.push(other),
+			}
+		}
+		value
+	}
+
+	fn unquoted(&mut self) -> String {
+		let mut value = String::new();
+		while let Some(c) = self.peek(0) {
+			if c.is_whitespace() || matches!(c, '(' | ')' | '#' | '"') {
+				break;
+			}
+			self.bump();
+			if c == '\\' {
+				if let Some(escaped) = self.bump() {
+					value.push(c);
+					value.push(escaped);
+				}
+			} else {
+				value.push(c);
+			}
+		}
+		value
+	}
+
+	fn arguments(&mut self) -> Vec<Argument> {
+		self.bump();
+		let mut arguments = Vec::new();
+		let mut depth = 1;
+		while let Some(c) = self.peek(0) {
+			if c.is_whitespace() {
+				self.bump();
+			} else if c == '(' {
+				depth += 1;
+				self.bump();
+			} else if c == ')' {
+				depth -= 1;
+				self.bump();
+				if depth == 0 {
+					break;
+				}
+			} else if c == '#' {
+				self.comment();
+			} else {
+				let start = self.offset;
+				let (value, kind) = if c == '"' {
+					(self.quoted(), ArgumentKind::Quoted)
+				} else if let Some(level) = self.bracket_level() {
+					(self.bracket(level), ArgumentKind::Bracket)
+				} else {
+					(self.unquoted(), ArgumentKind::Unquoted)
+				};
+				arguments.push(Argument {
+					value,
+					kind,
+					span: start..self.offset,
+				});
+			}
+		}
+		arguments
+	}
+}
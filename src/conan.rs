@@ -0,0 +1,171 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::cmake_editor::{self, Placement};
+use crate::config;
+use crate::constants::{cmake, conan, makefile};
+use crate::file_handler::{read, write};
+use crate::makefile_editor::{Item, Makefile, Rule};
+use crate::package_managers::{
+	get_package_manager_generator, ConanRecipeGenerator, PackageManager,
+};
+use crate::system_packages::cmake_link_block;
+use crate::BuildSystem;
+
+pub fn generators(build_system: BuildSystem) -> &'static [&'static str] {
+	match build_system {
+		BuildSystem::Makefile => &["PkgConfigDeps"],
+		BuildSystem::CMake => &["CMakeDeps", "CMakeToolchain"],
+	}
+}
+
+pub fn set_generators(manifest: &str, build_system: BuildSystem) -> String {
+	let mut lines: Vec<String> = manifest.lines().map(String::from).collect();
+	let wanted = generators(build_system).iter().map(|g| g.to_string());
+
+	match lines.iter().position(|l| l.trim() == "[generators]") {
+		Some(start) => {
+			let end = lines[start + 1..]
+				.iter()
+				.position(|l| l.trim().is_empty() || l.trim_start().starts_with('['))
+				.map(|offset| start + 1 + offset)
+				.unwrap_or(lines.len());
+			lines.splice(start + 1..end, wanted);
+		}
+		None => {
+			while lines.last().is_some_and(|l| l.trim().is_empty()) {
+				lines.pop();
+			}
+			lines.push(String::new());
+			lines.push("[generators]".to_string());
+			lines.extend(wanted);
+		}
+	}
+
+	let mut output = lines.join("\n");
+	output.push('\n');
+	output
+}
+
+pub fn integrate(pm: PackageManager, build_system: BuildSystem) -> Result<()> {
+	let generator = get_package_manager_generator(pm);
+	let manifest = read(generator.extension())?;
+	let manifest = match pm {
+		PackageManager::ConanRecipe => ConanRecipeGenerator.generate_recipe(
+			&config::project_name(),
+			build_system,
+			&generator.dependencies(&manifest)?,
+		),
+		_ => set_generators(&manifest, build_system),
+	};
+	write(generator.extension(), &manifest)?;
+
+	match build_system {
+		BuildSystem::Makefile => {
+			let content = read(makefile::FILENAME)?;
+			write(
+				makefile::FILENAME,
+				&integrate_makefile(&content, generator.install_command()),
+			)?;
+		}
+		BuildSystem::CMake => {
+			let content = read(cmake::FILENAME)?;
+			let content = cmake_editor::set_region(
+				&content,
+				conan::TOOLCHAIN_REGION,
+				&cmake_toolchain(),
+				Placement::BeforeCommand("project"),
+			);
+			write(cmake::FILENAME, &content)?;
+			sync_cmake_dependencies(pm)?;
+		}
+	}
+	Ok(())
+}
+
+pub fn remove_integration(build_system: BuildSystem) -> Result<()> {
+	match build_system {
+		BuildSystem::Makefile => {
+			let content = read(makefile::FILENAME)?;
+			write(makefile::FILENAME, &remove_makefile_integration(&content))
+		}
+		BuildSystem::CMake => {
+			let content = read(cmake::FILENAME)?;
+			let content = cmake_editor::remove_region(&content, conan::TOOLCHAIN_REGION);
+			let content = cmake_editor::remove_region(&content, conan::DEPENDENCIES_REGION);
+			write(cmake::FILENAME, &content)
+		}
+	}
+}
+
+pub fn sync_cmake_dependencies(pm: PackageManager) -> Result<()> {
+	let generator = get_package_manager_generator(pm);
+	if !Path::new(cmake::FILENAME).exists() || !Path::new(generator.extension()).exists() {
+		return Ok(());
+	}
+	let manifest = read(generator.extension())?;
+	let requirements = generator.dependencies(&manifest)?;
+	let content = read(cmake::FILENAME)?;
+
+	let names: Vec<String> = requirements.into_iter().map(|r| r.name).collect();
+	let updated = match cmake_link_block(&names) {
+		Some(block) => cmake_editor::set_region(
+			&content,
+			conan::DEPENDENCIES_REGION,
+			&block,
+			Placement::AfterTarget,
+		),
+		None => cmake_editor::remove_region(&content, conan::DEPENDENCIES_REGION),
+	};
+
+	write(cmake::FILENAME, &updated)
+}
+
+fn cmake_toolchain() -> String {
+	format!(
+		"if(NOT DEFINED CMAKE_TOOLCHAIN_FILE AND EXISTS \"${{CMAKE_SOURCE_DIR}}/{dir}/conan_toolchain.cmake\")\n\
+		\tset(CMAKE_TOOLCHAIN_FILE \"${{CMAKE_SOURCE_DIR}}/{dir}/conan_toolchain.cmake\")\n\
+		endif()\n",
+		dir = conan::OUTPUT_DIR
+	)
+}
+
+fn makefile_block() -> Vec<String> {
+	vec![
+		conan::MAKEFILE_COMMENT.to_string(),
+		format!("CONAN_DIR = {}", conan::OUTPUT_DIR),
+		"CONAN_PC = $(basename $(notdir $(wildcard $(CONAN_DIR)/*.pc)))".to_string(),
+		"CFLAGS += $(if $(CONAN_PC),$(shell PKG_CONFIG_PATH=$(CONAN_DIR) pkg-config --cflags $(CONAN_PC)))".to_string(),
+		"LDFLAGS += $(if $(CONAN_PC),$(shell PKG_CONFIG_PATH=$(CONAN_DIR) pkg-config --libs $(CONAN_PC)))".to_string(),
+	]
+}
+
+fn integrate_makefile(content: &str, install_command: &str) -> String {
+	let mut editor = Makefile::parse(&remove_makefile_integration(content));
+	let index = editor
+		.position(|item| matches!(item, Item::Variable(v) if v.name == "LDFLAGS"))
+		.map(|index| index + 1)
+		.unwrap_or(0);
+	editor.insert_block(index, &makefile_block());
+	editor.set_rule(
+		Rule::new(
+			conan::INSTALL_TARGET,
+			&[],
+			&[install_command.replace(conan::OUTPUT_DIR, "$(CONAN_DIR)")],
+		),
+		Some(conan::INSTALL_COMMENT),
+	);
+	editor.add_phony(conan::INSTALL_TARGET);
+	editor.render()
+}
+
+fn remove_makefile_integration(content: &str) -> String {
+	let block = makefile_block();
+	let mut editor = Makefile::parse(content);
+	editor.remove_rule(conan::INSTALL_TARGET);
+	editor.remove_block(|item| {
+		!matches!(item, Item::Blank(_)) && item.lines().iter().all(|line| block.contains(line))
+	});
+	editor.remove_phony(conan::INSTALL_TARGET);
+	editor.render()
+}
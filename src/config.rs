@@ -0,0 +1,310 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use crate::build_systems::{BuildProfile, Libc};
+use crate::constants::config;
+use crate::languages::Language;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigValue {
+	String(String),
+	Bool(bool),
+	Array(Vec<String>),
+}
+
+impl ConfigValue {
+	pub fn as_str(&self) -> Option<&str> {
+		match self {
+			ConfigValue::String(s) => Some(s),
+			_ => None,
+		}
+	}
+
+	pub fn as_bool(&self) -> Option<bool> {
+		match self {
+			ConfigValue::Bool(b) => Some(*b),
+			_ => None,
+		}
+	}
+
+	pub fn as_array(&self) -> Option<&[String]> {
+		match self {
+			ConfigValue::Array(items) => Some(items),
+			_ => None,
+		}
+	}
+
+	fn render(&self) -> String {
+		match self {
+			ConfigValue::String(s) => quote(s),
+			ConfigValue::Bool(b) => b.to_string(),
+			ConfigValue::Array(items) => format!(
+				"[{}]",
+				items
+					.iter()
+					.map(|i| quote(i))
+					.collect::<Vec<_>>()
+					.join(", ")
+			),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfigFile {
+	sections: Vec<(String, Vec<(String, ConfigValue)>)>,
+}
+
+impl ConfigFile {
+	pub fn parse(content: &str) -> Result<Self> {
+		let mut file = ConfigFile::default();
+		let mut section = String::new();
+
+		for (number, raw) in content.lines().enumerate() {
+			let line = strip_comment(raw).trim();
+			if line.is_empty() {
+				continue;
+			}
+
+			if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+				section = name.trim().to_string();
+				file.section_mut(&section);
+				continue;
+			}
+
+			let (key, value) = line
+				.split_once('=')
+				.with_context(|| format!("Invalid line {}: expected 'key = value'", number + 1))?;
+			let value = parse_value(value.trim())
+				.with_context(|| format!("Invalid value on line {}", number + 1))?;
+			file.set(&section, key.trim(), value);
+		}
+
+		Ok(file)
+	}
+
+	pub fn get(&self, section: &str, key: &str) -> Option<&ConfigValue> {
+		self.section(section)
+			.and_then(|entries| entries.iter().find(|(k, _)| k == key))
+			.map(|(_, v)| v)
+	}
+
+	pub fn set(&mut self, section: &str, key: &str, value: ConfigValue) {
+		let entries = self.section_mut(section);
+		match entries.iter_mut().find(|(k, _)| k == key) {
+			Some(entry) => entry.1 = value,
+			None => entries.push((key.to_string(), value)),
+		}
+	}
+
+	pub fn section(&self, section: &str) -> Option<&[(String, ConfigValue)]> {
+		self.sections
+			.iter()
+			.find(|(name, _)| name == section)
+			.map(|(_, entries)| entries.as_slice())
+	}
+
+	pub fn sections(&self) -> impl Iterator<Item = (&str, &[(String, ConfigValue)])> {
+		self.sections
+			.iter()
+			.map(|(name, entries)| (name.as_str(), entries.as_slice()))
+	}
+
+	fn section_mut(&mut self, section: &str) -> &mut Vec<(String, ConfigValue)> {
+		let index = match self.sections.iter().position(|(name, _)| name == section) {
+			Some(index) => index,
+			None => {
+				self.sections.push((section.to_string(), Vec::new()));
+				self.sections.len() - 1
+			}
+		};
+		&mut self.sections[index].1
+	}
+
+	pub fn render(&self) -> String {
+		let mut out = String::new();
+		for (name, entries) in &self.sections {
+			if entries.is_empty() {
+				continue;
+			}
+			if !out.is_empty() {
+				out.push('\n');
+			}
+			if !name.is_empty() {
+				out.push_str(&format!("[{}]\n", name));
+			}
+			for (key, value) in entries {
+				out.push_str(&format!("{} = {}\n", key, value.render()));
+			}
+		}
+		out
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct ProjectConfig {
+	pub name: String,
+	pub language: Option<Language>,
+	pub profile: BuildProfile,
+	pub header_extension: Option<String>,
+}
+
+impl ProjectConfig {
+	pub fn new(name: &str, language: Language, profile: BuildProfile) -> Self {
+		ProjectConfig {
+			name: name.to_string(),
+			language: Some(language),
+			profile,
+			header_extension: None,
+		}
+	}
+
+	pub fn load() -> Result<Option<Self>> {
+		if !Path::new(config::FILENAME).exists() {
+			return Ok(None);
+		}
+
+		let content = fs::read_to_string(config::FILENAME)
+			.with_context(|| format!("Failed to read {}", config::FILENAME))?;
+		let file = ConfigFile::parse(&content)
+			.with_context(|| format!("Failed to parse {}", config::FILENAME))?;
+
+		let name = file
+			.get("project", "name")
+			.and_then(ConfigValue::as_str)
+			.map(String::from)
+			.unwrap_or_else(current_dir_name);
+		let language = file
+			.get("project", "language")
+			.and_then(ConfigValue::as_str)
+			.map(str::parse::<Language>)
+			.transpose()?;
+		let header_extension = file
+			.get("project", "header_extension")
+			.and_then(ConfigValue::as_str)
+			.map(|ext| match ext.trim_start_matches('.') {
+				ext @ ("h" | "hpp" | "hh" | "hxx") => Ok(ext.to_string()),
+				other => Err(anyhow::anyhow!(
+					"Unsupported header_extension: {}. Use 'h', 'hpp', 'hh' or 'hxx'",
+					other
+				)),
+			})
+			.transpose()?;
+		let libc = file
+			.get("build", "libc")
+			.and_then(ConfigValue::as_str)
+			.map(str::parse::<Libc>)
+			.transpose()?
+			.unwrap_or_default();
+		let static_link = file
+			.get("build", "static")
+			.and_then(ConfigValue::as_bool)
+			.unwrap_or(false);
+
+		Ok(Some(ProjectConfig {
+			name,
+			language,
+			profile: BuildProfile { libc, static_link },
+			header_extension,
+		}))
+	}
+
+	pub fn save(&self) -> Result<()> {
+		let mut file = if Path::new(config::FILENAME).exists() {
+			let content = fs::read_to_string(config::FILENAME)
+				.with_context(|| format!("Failed to read {}", config::FILENAME))?;
+			ConfigFile::parse(&content)?
+		} else {
+			ConfigFile::default()
+		};
+
+		file.set("project", "name", ConfigValue::String(self.name.clone()));
+		if let Some(language) = self.language {
+			file.set(
+				"project",
+				"language",
+				ConfigValue::String(language.as_str().to_string()),
+			);
+		}
+		if let Some(extension) = &self.header_extension {
+			file.set(
+				"project",
+				"header_extension",
+				ConfigValue::String(extension.clone()),
+			);
+		}
+		file.set(
+			"build",
+			"libc",
+			ConfigValue::String(self.profile.libc.as_str().to_string()),
+		);
+		file.set(
+			"build",
+			"static",
+			ConfigValue::Bool(self.profile.static_link),
+		);
+
+		fs::write(config::FILENAME, file.render())
+			.with_context(|| format!("Failed to write {}", config::FILENAME))
+	}
+}
+
+pub fn project_name() -> String {
+	ProjectConfig::load()
+		.ok()
+		.flatten()
+		.map(|c| c.name)
+		.unwrap_or_else(current_dir_name)
+}
+
+fn current_dir_name() -> String {
+	std::env::current_dir()
+		.ok()
+		.and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+		.unwrap_or_else(|| "project".to_string())
+}
+
+fn strip_comment(line: &str) -> &str {
+	let mut in_string = false;
+	for (i, c) in line.char_indices() {
+		match c {
+			'"' => in_string = !in_string,
+			'#' if !in_string => return &line[..i],
+			_ => {}
+		}
+	}
+	line
+}
+
+fn parse_value(value: &str) -> Result<ConfigValue> {
+	match value {
+		"true" => return Ok(ConfigValue::Bool(true)),
+		"false" => return Ok(ConfigValue::Bool(false)),
+		_ => {}
+	}
+
+	if let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+		let items = inner
+			.split(',')
+			.map(str::trim)
+			.filter(|item| !item.is_empty())
+			.map(unquote)
+			.collect::<Result<Vec<_>>>()?;
+		return Ok(ConfigValue::Array(items));
+	}
+
+	Ok(ConfigValue::String(unquote(value)?))
+}
+
+fn unquote(value: &str) -> Result<String> {
+	let inner = value
+		.strip_prefix('"')
+		.and_then(|v| v.strip_suffix('"'))
+		.with_context(|| format!("Expected a quoted string, found: {}", value))?;
+	Ok(inner.replace("\\\"", "\"").replace("\\\\", "\\"))
+}
+
+fn quote(value: &str) -> String {
+	format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
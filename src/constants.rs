@@ -1,7 +1,107 @@
 pub mod makefile {
 	pub const FILENAME: &str = "Makefile";
 	pub const INSTALL_DEPS_PREFIX: &str = "sudo apt install -y";
-	pub const DEFAULT_TARGET: &str = "all: clean";
+	pub const DEPS_VAR: &str = "DEPS";
+	pub const DEPS_COMMENT: &str = "# System dependencies (managed by sticks)";
+	pub const INSTALL_DEPS_TARGET: &str = "install-deps";
+	pub const INSTALL_DEPS_COMMENT: &str =
+		"# Install system dependencies for the host distribution";
+	pub const PKG_CONFIG_VAR: &str = "PKG_CONFIG_DEPS";
+	pub const PKG_CONFIG_CFLAGS: &str = "CFLAGS += $(shell pkg-config --cflags $(PKG_CONFIG_DEPS))";
+	pub const PKG_CONFIG_LIBS: &str = "LDFLAGS += $(shell pkg-config --libs $(PKG_CONFIG_DEPS))";
+	pub const OBJECT_TARGET: &str = "$(BUILD_DIR)/%.o";
+	pub const OBJECT_MKDIR: &str = "@mkdir -p $(dir $@)";
+	pub const C_SOURCES_REGION: &str = "c-sources";
+}
+
+pub mod cmake {
+	pub const FILENAME: &str = "CMakeLists.txt";
+	pub const DEPENDENCIES_REGION: &str = "dependencies";
+	pub const DEPS_VAR: &str = "STICKS_DEPS";
+	pub const PKG_CONFIG_PREFIX: &str = "STICKS_PKG";
+	pub const FETCH_CONTENT_REGION_PREFIX: &str = "fetchcontent-";
+	pub const C_SOURCES_REGION: &str = "c-sources";
+	pub const C_SOURCES_GLOB: &str = "src/*.c";
+}
+
+pub mod conan {
+	pub const MANIFEST: &str = "conanfile.txt";
+	pub const OUTPUT_DIR: &str = ".conan";
+	pub const TOOLCHAIN_REGION: &str = "conan-toolchain";
+	pub const DEPENDENCIES_REGION: &str = "conan-dependencies";
+	pub const MAKEFILE_COMMENT: &str = "# Conan dependencies (managed by sticks)";
+	pub const INSTALL_TARGET: &str = "conan-install";
+	pub const INSTALL_COMMENT: &str = "# Install Conan dependencies and generate pkg-config files";
+}
+
+pub mod vcpkg {
+	pub const MANIFEST: &str = "vcpkg.json";
+	pub const CONFIGURATION: &str = "vcpkg-configuration.json";
+	pub const PRESETS: &str = "CMakePresets.json";
+	pub const PRESET_NAME: &str = "vcpkg";
+	pub const ROOT_ENV: &str = "VCPKG_ROOT";
+	pub const REGISTRY_URL: &str = "https://github.com/microsoft/vcpkg";
+	pub const TOOLCHAIN_REGION: &str = "vcpkg-toolchain";
+	pub const DEPENDENCIES_REGION: &str = "vcpkg-dependencies";
+}
+
+pub mod vendor {
+	pub const DIR: &str = "third_party";
+	pub const REGION_PREFIX: &str = "vendor-";
+	pub const GITMODULES: &str = ".gitmodules";
+}
+
+pub mod scripts {
+	pub const INSTALL_DEPS: &str = "scripts/install-deps.sh";
+}
+
+pub mod lockfile {
+	pub const FILENAME: &str = "sticks.lock";
+	pub const HEADER: &str =
+		"# Generated by sticks: resolved dependency versions. Do not edit by hand.";
+	pub const HOST: &str = "host";
+	pub const SYSTEM: &str = "system";
+	pub const CONAN: &str = "conan";
+	pub const CONAN_LOCK: &str = "conan.lock";
+	pub const VCPKG: &str = "vcpkg";
+	pub const VCPKG_STATUS: [&str; 2] = [
+		"vcpkg_installed/vcpkg/status",
+		"build/vcpkg_installed/vcpkg/status",
+	];
+	pub const GIT: &str = "git";
+	pub const VENDOR: &str = "vendor";
+}
+
+pub mod audit {
+	pub const SOURCE_EXTENSIONS: &[&str] = &[
+		"c", "h", "cpp", "hpp", "cc", "cxx", "hh", "hxx", "S", "s", "m", "mm", "f90",
+	];
+	pub const TOOLCHAIN_PACKAGES: &[&str] = &[
+		"libc6-dev",
+		"libc-dev",
+		"linux-libc-dev",
+		"libcrypt",
+		"libxcrypt",
+		"libstdc++",
+		"libgcc",
+		"gcc",
+		"g++",
+		"glibc",
+		"kernel-headers",
+		"linux-api-headers",
+		"linux-headers",
+		"musl",
+	];
+	pub const STANDARD_LIBRARIES: &[&str] =
+		&["c", "m", "dl", "rt", "stdc++", "gcc", "gcc_s", "atomic"];
+}
+
+pub mod search {
+	pub const MAX_RESULTS: usize = 15;
+}
+
+pub mod config {
+	pub const FILENAME: &str = "sticks.toml";
 }
 
 pub mod github {
@@ -37,3 +137,8 @@ pub mod install_paths {
 	pub const USR_BIN: &str = "/usr/bin/sticks";
 	pub const CARGO_BIN_SUFFIX: &str = ".cargo/bin/sticks";
 }
+
+pub mod project_templates {
+	pub const USER_DIR: &str = "sticks/templates";
+	pub const MANIFEST: &str = "template.toml";
+}
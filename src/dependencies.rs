@@ -1,44 +1,38 @@
 use anyhow::{Context, Result};
-use std::collections::HashSet;
+use std::collections::BTreeSet;
 use std::fs;
+use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 
-use crate::constants::makefile;
+use crate::cmake_editor::{self, Placement};
+use crate::constants::{cmake, makefile, project, scripts};
+use crate::features::{detect_build_system, detect_package_manager};
+use crate::fetch_content::remove_git_dependencies;
+use crate::makefile_editor::{Item, Makefile, Rule};
+use crate::package_managers::{
+	get_package_manager_generator, sync_package_manager_dependencies, PackageManager, PackageSpec,
+};
+use crate::system_packages::{find_known_package, generate_install_script, pkg_config_module};
+use crate::BuildSystem;
 
-pub fn add_dependencies(dependency_names: &[String]) -> Result<()> {
-	if !Path::new(makefile::FILENAME).exists() {
-		anyhow::bail!("Makefile not found in the current directory");
-	}
-
-	let mut makefile_content =
-		fs::read_to_string(makefile::FILENAME).context("Failed to read Makefile")?;
+#[derive(Debug, Default)]
+struct DeclaredDependencies {
+	deps: BTreeSet<String>,
+	pkg_config: BTreeSet<String>,
+}
 
-	if !makefile_content.contains("all: clean install-deps") {
-		makefile_content = makefile_content.replace("all: clean", "all: clean install-deps");
+pub fn add_dependencies(dependency_names: &[String]) -> Result<()> {
+	if let Some(pm) = detect_package_manager()? {
+		return add_manifest_dependencies(pm, dependency_names);
 	}
 
-	let install_deps_line =
-		if let Some(install_deps_pos) = makefile_content.find(makefile::INSTALL_DEPS_PREFIX) {
-			let existing_deps_start = install_deps_pos + makefile::INSTALL_DEPS_PREFIX.len();
-			let existing_deps = &makefile_content[existing_deps_start..]
-				.lines()
-				.next()
-				.unwrap_or("")
-				.trim();
-			existing_deps.to_string()
-		} else {
-			String::new()
-		};
-
-	let mut current_deps: HashSet<String> = install_deps_line
-		.split_whitespace()
-		.map(|s| s.to_string())
-		.collect();
-
+	let build_system = require_build_system()?;
+	let content = read_build_file(build_system)?;
+	let mut declared = parse_declared(build_system, &content);
 	let mut added_deps = Vec::new();
 
 	for dep in dependency_names {
-		if current_deps.insert(dep.clone()) {
+		if declared.deps.insert(dep.clone()) {
 			added_deps.push(dep);
 		}
 	}
@@ -48,118 +42,414 @@ pub fn add_dependencies(dependency_names: &[String]) -> Result<()> {
 		return Ok(());
 	}
 
-	let mut sorted_deps: Vec<_> = current_deps.into_iter().collect();
-	sorted_deps.sort();
-	let new_install_deps_line = format!(
-		"{} {}",
-		makefile::INSTALL_DEPS_PREFIX,
-		sorted_deps.join(" ")
+	for dep in &added_deps {
+		match pkg_config_module(dep) {
+			Some(module) => {
+				declared.pkg_config.insert(module);
+			}
+			None if find_known_package(dep).is_none() => println!(
+				"ℹ️  No pkg-config file found for {}; add its compiler and linker flags manually",
+				dep
+			),
+			None => {}
+		}
+	}
+
+	let content = match build_system {
+		BuildSystem::Makefile => write_makefile_dependencies(&content, &declared),
+		BuildSystem::CMake => write_cmake_dependencies(&content, &declared),
+	};
+
+	write_build_file(build_system, &content)?;
+	write_install_script(&declared.deps)?;
+
+	println!("Added dependencies: {:?}", added_deps);
+	println!(
+		"💡 Run '{}' to install them on this host",
+		install_hint(build_system)
 	);
 
-	if makefile_content.contains(makefile::INSTALL_DEPS_PREFIX) {
-		makefile_content =
-			makefile_content.replace(makefile::INSTALL_DEPS_PREFIX, &new_install_deps_line);
+	Ok(())
+}
+
+pub fn remove_dependencies(dependency_names: &[String]) -> Result<()> {
+	let removed_git = remove_git_dependencies(dependency_names)?;
+	let remaining: Vec<String> = dependency_names
+		.iter()
+		.filter(|name| !removed_git.contains(name))
+		.cloned()
+		.collect();
+	if remaining.is_empty() {
+		return Ok(());
+	}
+	let dependency_names = remaining.as_slice();
+
+	if let Some(pm) = detect_package_manager()? {
+		return remove_manifest_dependencies(pm, dependency_names);
+	}
+
+	let build_system = require_build_system()?;
+	let content = read_build_file(build_system)?;
+	let mut declared = parse_declared(build_system, &content);
+	let original_len = declared.deps.len();
+	declared.deps.retain(|dep| !dependency_names.contains(dep));
+
+	if declared.deps.len() == original_len {
+		println!(
+			"Dependencies {:?} not found in {}.",
+			dependency_names, build_system
+		);
+		return Ok(());
+	}
+
+	for dep in dependency_names {
+		let module = find_known_package(dep)
+			.map(|known| known.pkg_config.to_string())
+			.unwrap_or_else(|| dep.clone());
+		declared.pkg_config.remove(&module);
+	}
+
+	let updated = if declared.deps.is_empty() {
+		println!("All specified dependencies removed. Removing install-deps rule.");
+		remove_install_script();
+		match build_system {
+			BuildSystem::Makefile => remove_makefile_dependencies(&content),
+			BuildSystem::CMake => cmake_editor::remove_region(&content, cmake::DEPENDENCIES_REGION),
+		}
 	} else {
-		makefile_content.push_str(&format!("\ninstall-deps:\n\t{}\n", new_install_deps_line));
+		write_install_script(&declared.deps)?;
+		match build_system {
+			BuildSystem::Makefile => write_makefile_dependencies(&content, &declared),
+			BuildSystem::CMake => write_cmake_dependencies(&content, &declared),
+		}
+	};
+
+	println!(
+		"Dependencies {:?} removed from {}.",
+		dependency_names, build_system
+	);
+
+	write_build_file(build_system, &updated)
+}
+
+pub fn declared_dependencies() -> Result<Vec<String>> {
+	let build_system = match detect_build_system()? {
+		Some(bs) => bs,
+		None => return Ok(Vec::new()),
+	};
+	let content = read_build_file(build_system)?;
+	Ok(parse_declared(build_system, &content)
+		.deps
+		.into_iter()
+		.collect())
+}
+
+fn add_manifest_dependencies(pm: PackageManager, dependency_names: &[String]) -> Result<()> {
+	let generator = get_package_manager_generator(pm);
+	let specs = dependency_names
+		.iter()
+		.map(|name| name.parse())
+		.collect::<Result<Vec<PackageSpec>>>()?;
+	let manifest = read_manifest(pm)?;
+	let updated = generator.add_dependencies(&manifest, &specs)?;
+
+	if updated == manifest {
+		println!("All dependencies are already present.");
+		return Ok(());
 	}
 
-	fs::write(makefile::FILENAME, makefile_content).context("Failed to write updated Makefile")?;
+	write_manifest(pm, &updated)?;
+	sync_package_manager_dependencies(pm)?;
+	println!(
+		"Added dependencies to {}: {}",
+		generator.extension(),
+		specs
+			.iter()
+			.map(ToString::to_string)
+			.collect::<Vec<_>>()
+			.join(", ")
+	);
+	println!("💡 Run '{}' to fetch them", generator.install_command());
+	Ok(())
+}
 
-	println!("Added dependencies: {:?}", added_deps);
+fn remove_manifest_dependencies(pm: PackageManager, dependency_names: &[String]) -> Result<()> {
+	let generator = get_package_manager_generator(pm);
+	let names = dependency_names
+		.iter()
+		.map(|name| name.parse().map(|spec: PackageSpec| spec.name))
+		.collect::<Result<Vec<String>>>()?;
+	let manifest = read_manifest(pm)?;
+	let updated = generator.remove_dependencies(&manifest, &names)?;
+
+	if updated == manifest {
+		println!(
+			"Dependencies {:?} not found in {}.",
+			dependency_names,
+			generator.extension()
+		);
+		return Ok(());
+	}
 
+	write_manifest(pm, &updated)?;
+	sync_package_manager_dependencies(pm)?;
+	println!(
+		"Dependencies {:?} removed from {}.",
+		dependency_names,
+		generator.extension()
+	);
 	Ok(())
 }
 
-pub fn remove_dependencies(dependency_names: &[String]) -> Result<()> {
-	if !Path::new(makefile::FILENAME).exists() {
-		anyhow::bail!("Makefile not found in the current directory");
-	}
-
-	let makefile_content = fs::read_to_string(makefile::FILENAME)?;
-	let lines: Vec<&str> = makefile_content.lines().collect();
-	let mut updated_lines: Vec<String> = Vec::new();
-	let mut i = 0;
-	let mut install_deps_found = false;
-	let mut dependencies_removed = false;
-	let deps_to_remove: HashSet<&String> = dependency_names.iter().collect();
-
-	while i < lines.len() {
-		let line = lines[i];
-
-		if line.starts_with("all:") {
-			let parts: Vec<&str> = line.split(':').collect();
-			if parts.len() >= 2 {
-				let targets: Vec<&str> = parts[1]
-					.split_whitespace()
-					.filter(|t| *t != "install-deps")
-					.collect();
-				if !targets.is_empty() {
-					updated_lines.push(format!("all: {}", targets.join(" ")));
-				} else {
-					updated_lines.push("all: clean".to_string());
-				}
-			} else {
-				updated_lines.push(line.to_string());
-			}
-		} else if line.starts_with("install-deps:") {
-			install_deps_found = true;
-			if i + 1 < lines.len() {
-				let cmd_line = lines[i + 1];
-				if cmd_line
-					.trim_start()
-					.starts_with(makefile::INSTALL_DEPS_PREFIX)
-				{
-					let deps_str =
-						cmd_line.trim_start()[makefile::INSTALL_DEPS_PREFIX.len()..].trim();
-					let mut current_deps: HashSet<String> =
-						deps_str.split_whitespace().map(String::from).collect();
-					let original_len = current_deps.len();
-					current_deps.retain(|dep| !deps_to_remove.contains(&dep));
-					let removed = original_len - current_deps.len();
-
-					if removed > 0 {
-						dependencies_removed = true;
-						if !current_deps.is_empty() {
-							updated_lines.push("install-deps:".to_string());
-							let mut sorted_deps: Vec<_> = current_deps.into_iter().collect();
-							sorted_deps.sort();
-							updated_lines.push(format!(
-								"\t{} {}",
-								makefile::INSTALL_DEPS_PREFIX,
-								sorted_deps.join(" ")
-							));
-						} else {
-							println!(
-								"All specified dependencies removed. Removing install-deps rule."
-							);
-						}
-					} else {
-						updated_lines.push(line.to_string());
-						if i + 1 < lines.len() {
-							updated_lines.push(lines[i + 1].to_string());
-						}
-					}
-				} else {
-					updated_lines.push(line.to_string());
-				}
-				i += 1;
-			} else {
-				updated_lines.push(line.to_string());
+fn read_manifest(pm: PackageManager) -> Result<String> {
+	let manifest = get_package_manager_generator(pm).extension();
+	if !Path::new(manifest).exists() {
+		anyhow::bail!(
+			"Project uses {} but {} was not found; edit its requirements manually",
+			pm,
+			manifest
+		);
+	}
+	fs::read_to_string(manifest).with_context(|| format!("Failed to read {}", manifest))
+}
+
+fn write_manifest(pm: PackageManager, content: &str) -> Result<()> {
+	let manifest = get_package_manager_generator(pm).extension();
+	fs::write(manifest, content).with_context(|| format!("Failed to write updated {}", manifest))
+}
+
+fn require_build_system() -> Result<BuildSystem> {
+	detect_build_system()?.context("No Makefile or CMakeLists.txt found in the current directory")
+}
+
+fn build_file(build_system: BuildSystem) -> &'static str {
+	match build_system {
+		BuildSystem::Makefile => makefile::FILENAME,
+		BuildSystem::CMake => cmake::FILENAME,
+	}
+}
+
+fn read_build_file(build_system: BuildSystem) -> Result<String> {
+	fs::read_to_string(build_file(build_system))
+		.with_context(|| format!("Failed to read {}", build_file(build_system)))
+}
+
+fn write_build_file(build_system: BuildSystem, content: &str) -> Result<()> {
+	fs::write(build_file(build_system), content)
+		.with_context(|| format!("Failed to write updated {}", build_file(build_system)))
+}
+
+fn install_hint(build_system: BuildSystem) -> String {
+	match build_system {
+		BuildSystem::Makefile => format!("make {}", makefile::INSTALL_DEPS_TARGET),
+		BuildSystem::CMake => format!(
+			"cmake --build {} --target {}",
+			project::BUILD_DIR,
+			makefile::INSTALL_DEPS_TARGET
+		),
+	}
+}
+
+fn parse_declared(build_system: BuildSystem, content: &str) -> DeclaredDependencies {
+	match build_system {
+		BuildSystem::Makefile => parse_makefile_dependencies(content),
+		BuildSystem::CMake => parse_cmake_dependencies(content),
+	}
+}
+
+fn parse_makefile_dependencies(content: &str) -> DeclaredDependencies {
+	let makefile = Makefile::parse(content);
+	let mut declared = DeclaredDependencies::default();
+	for variable in makefile.variables(makefile::DEPS_VAR) {
+		declared
+			.deps
+			.extend(variable.value.split_whitespace().map(String::from));
+	}
+	for variable in makefile.variables(makefile::PKG_CONFIG_VAR) {
+		declared
+			.pkg_config
+			.extend(variable.value.split_whitespace().map(String::from));
+	}
+	if let Some(rule) = makefile.rule(makefile::INSTALL_DEPS_TARGET) {
+		for legacy in rule.recipe.iter().filter_map(|line| {
+			line.trim_start()
+				.strip_prefix(makefile::INSTALL_DEPS_PREFIX)
+		}) {
+			declared
+				.deps
+				.extend(legacy.split_whitespace().map(String::from));
+		}
+	}
+	declared
+}
+
+fn parse_cmake_dependencies(content: &str) -> DeclaredDependencies {
+	let mut declared = DeclaredDependencies::default();
+	let region = match cmake_editor::read_region(content, cmake::DEPENDENCIES_REGION) {
+		Some(region) => region,
+		None => return declared,
+	};
+
+	for command in cmake_editor::parse(&region).commands {
+		let args = command.values();
+		if command.is("set") {
+			if args.first() == Some(&cmake::DEPS_VAR) {
+				declared
+					.deps
+					.extend(args[1..].iter().map(|a| a.to_string()));
 			}
-		} else {
-			updated_lines.push(line.to_string());
+		} else if command.is("pkg_check_modules") {
+			declared.pkg_config.extend(
+				args.iter()
+					.skip(1)
+					.filter(|a| **a != "REQUIRED" && **a != "IMPORTED_TARGET")
+					.map(|a| a.to_string()),
+			);
 		}
+	}
+	declared
+}
 
-		i += 1;
+fn is_managed_makefile_item(item: &Item) -> bool {
+	match item {
+		Item::Comment(line) => line == makefile::DEPS_COMMENT,
+		Item::Variable(variable) => {
+			variable.name == makefile::DEPS_VAR
+				|| variable.name == makefile::PKG_CONFIG_VAR
+				|| item.lines() == [makefile::PKG_CONFIG_CFLAGS]
+				|| item.lines() == [makefile::PKG_CONFIG_LIBS]
+		}
+		_ => false,
 	}
+}
 
-	if dependencies_removed && install_deps_found {
-		println!("Dependencies {:?} removed from Makefile.", dependency_names);
-	} else {
-		println!("Dependencies {:?} not found in Makefile.", dependency_names);
+fn write_makefile_dependencies(content: &str, declared: &DeclaredDependencies) -> String {
+	let mut block = vec![
+		makefile::DEPS_COMMENT.to_string(),
+		format!("{} = {}", makefile::DEPS_VAR, join_set(&declared.deps)),
+	];
+	if !declared.pkg_config.is_empty() {
+		block.push(format!(
+			"{} = {}",
+			makefile::PKG_CONFIG_VAR,
+			join_set(&declared.pkg_config)
+		));
+		block.push(makefile::PKG_CONFIG_CFLAGS.to_string());
+		block.push(makefile::PKG_CONFIG_LIBS.to_string());
+	}
+
+	let mut editor = Makefile::parse(content);
+	editor.remove_rule(makefile::INSTALL_DEPS_TARGET);
+	match editor.remove_where(is_managed_makefile_item) {
+		Some(index) => editor.insert_lines(index, &block),
+		None => {
+			let index = editor
+				.position(|item| matches!(item, Item::Variable(v) if v.name == "LDFLAGS"))
+				.map(|index| index + 1)
+				.unwrap_or(0);
+			editor.insert_block(index, &block);
+		}
+	}
+
+	editor.set_rule(
+		Rule::new(
+			makefile::INSTALL_DEPS_TARGET,
+			&[],
+			&[format!(
+				"@sh {} $({})",
+				scripts::INSTALL_DEPS,
+				makefile::DEPS_VAR
+			)],
+		),
+		Some(makefile::INSTALL_DEPS_COMMENT),
+	);
+	editor.add_phony(makefile::INSTALL_DEPS_TARGET);
+	editor.render()
+}
+
+fn remove_makefile_dependencies(content: &str) -> String {
+	let mut editor = Makefile::parse(content);
+	editor.remove_rule(makefile::INSTALL_DEPS_TARGET);
+	editor.remove_block(is_managed_makefile_item);
+	editor.remove_prerequisite("all", makefile::INSTALL_DEPS_TARGET);
+	editor.remove_phony(makefile::INSTALL_DEPS_TARGET);
+	editor.render()
+}
+
+fn write_cmake_dependencies(content: &str, declared: &DeclaredDependencies) -> String {
+	let mut body = format!("set({} {})\n", cmake::DEPS_VAR, join_set(&declared.deps));
+	let mut targets = Vec::new();
+	let mut modules = declared.pkg_config.clone();
+
+	for known in declared
+		.deps
+		.iter()
+		.filter_map(|dep| find_known_package(dep))
+	{
+		if known.cmake_package.is_empty() {
+			continue;
+		}
+		body.push_str(&format!("find_package({} REQUIRED)\n", known.cmake_package));
+		targets.extend(known.cmake_targets.iter().map(|t| t.to_string()));
+		modules.remove(known.pkg_config);
+	}
+
+	if !modules.is_empty() {
+		body.push_str(&format!(
+			"find_package(PkgConfig REQUIRED)\n\
+			pkg_check_modules({} REQUIRED IMPORTED_TARGET {})\n",
+			cmake::PKG_CONFIG_PREFIX,
+			join_set(&modules)
+		));
+		targets.push(format!("PkgConfig::{}", cmake::PKG_CONFIG_PREFIX));
+	}
+
+	if !targets.is_empty() {
+		body.push_str(&format!(
+			"target_link_libraries(${{PROJECT_NAME}} PRIVATE {})\n",
+			targets.join(" ")
+		));
+	}
+	body.push_str(&format!(
+		"add_custom_target({} COMMAND sh ${{CMAKE_SOURCE_DIR}}/{} ${{{}}} VERBATIM)\n",
+		makefile::INSTALL_DEPS_TARGET,
+		scripts::INSTALL_DEPS,
+		cmake::DEPS_VAR
+	));
+
+	cmake_editor::set_region(
+		content,
+		cmake::DEPENDENCIES_REGION,
+		&body,
+		Placement::AfterTarget,
+	)
+}
+
+fn join_set(set: &BTreeSet<String>) -> String {
+	set.iter().cloned().collect::<Vec<_>>().join(" ")
+}
+
+fn remove_install_script() {
+	let script = Path::new(scripts::INSTALL_DEPS);
+	fs::remove_file(script).ok();
+	if let Some(parent) = script.parent() {
+		fs::remove_dir(parent).ok();
+	}
+}
+
+fn write_install_script(deps: &BTreeSet<String>) -> Result<()> {
+	let deps: Vec<String> = deps.iter().cloned().collect();
+	let script = Path::new(scripts::INSTALL_DEPS);
+	if let Some(parent) = script.parent() {
+		fs::create_dir_all(parent).context("Failed to create scripts directory")?;
 	}
+	fs::write(script, generate_install_script(&deps))
+		.context("Failed to write dependency install script")?;
 
-	fs::write(makefile::FILENAME, updated_lines.join("\n"))
-		.context("Failed to write updated Makefile")?;
+	let mut perms = fs::metadata(script)
+		.context("Failed to get script permissions")?
+		.permissions();
+	perms.set_mode(0o755);
+	fs::set_permissions(script, perms).context("Failed to make install script executable")?;
 	Ok(())
 }
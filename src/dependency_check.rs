@@ -0,0 +1,579 @@
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::cmake_editor::{self, REGION_BEGIN, REGION_END};
+use crate::constants::{audit, cmake, makefile, project};
+use crate::dependencies::declared_dependencies;
+use crate::features::detect_build_system;
+use crate::languages::{Language, LanguageConsts};
+use crate::system_packages::{
+	find_known_package, pkg_config_exists, pkg_config_module, translate_package,
+	HostPackageManager, KnownPackage, KNOWN_PACKAGES,
+};
+use crate::BuildSystem;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DependencyCheck {
+	pub name: String,
+	pub package: Option<String>,
+	pub installed: Option<String>,
+	pub pkg_config: Option<String>,
+	pub pkg_config_found: bool,
+	pub header: Option<String>,
+	pub header_found: bool,
+}
+
+impl DependencyCheck {
+	pub fn problems(&self) -> Vec<String> {
+		let mut problems = Vec::new();
+		if let Some(package) = &self.package {
+			if self.installed.is_none() {
+				problems.push(format!("package {} is not installed", package));
+			}
+		}
+		match &self.pkg_config {
+			Some(module) if !self.pkg_config_found => {
+				problems.push(format!("no pkg-config file for {}", module))
+			}
+			None if !self.pkg_config_found => problems.push("no pkg-config file".to_string()),
+			_ => {}
+		}
+		if let Some(header) = &self.header {
+			if !self.header_found {
+				problems.push(format!("header <{}> not found", header));
+			}
+		}
+		problems
+	}
+}
+
+pub fn check_dependencies() -> Result<Vec<DependencyCheck>> {
+	let pm = HostPackageManager::detect();
+	let language = project_language();
+	Ok(declared_dependencies()?
+		.into_iter()
+		.map(|dep| check_dependency(&dep, pm, language))
+		.collect())
+}
+
+pub fn check_dependency(
+	name: &str,
+	pm: Option<HostPackageManager>,
+	language: Language,
+) -> DependencyCheck {
+	let known = find_known_package(name);
+	let package = pm.and_then(|pm| translate_package(name, pm));
+	let installed = match (pm, &package) {
+		(Some(pm), Some(package)) => pm.installed_version(package),
+		_ => None,
+	};
+
+	let pkg_config = match known {
+		Some(known) if known.pkg_config.is_empty() => None,
+		Some(known) => Some(known.pkg_config.to_string()),
+		None => Some(name.to_string()),
+	};
+	let pkg_config_found = match &pkg_config {
+		Some(module) => pkg_config_exists(module),
+		None => true,
+	};
+
+	let header = known.map(|known| known.header.to_string());
+	let header_found = match &header {
+		Some(header) => {
+			let cflags = pkg_config
+				.as_deref()
+				.filter(|_| pkg_config_found)
+				.map(pkg_config_cflags)
+				.unwrap_or_default();
+			header_exists(header, language, &cflags)
+		}
+		None => true,
+	};
+
+	DependencyCheck {
+		name: name.to_string(),
+		package,
+		installed,
+		pkg_config,
+		pkg_config_found,
+		header,
+		header_found,
+	}
+}
+
+pub fn run_check() -> Result<()> {
+	let checks = check_dependencies()?;
+	if checks.is_empty() {
+		println!("No system dependencies are declared in this project.");
+		return Ok(());
+	}
+
+	match HostPackageManager::detect() {
+		Some(pm) => println!(
+			"🔍 Checking {} declared dependencies with {}:",
+			checks.len(),
+			pm
+		),
+		None => println!(
+			"🔍 Checking {} declared dependencies (no supported package manager found):",
+			checks.len()
+		),
+	}
+
+	let mut failed = 0;
+	for check in &checks {
+		let problems = check.problems();
+		if problems.is_empty() {
+			let details = [
+				check
+					.package
+					.as_ref()
+					.zip(check.installed.as_ref())
+					.map(|(package, version)| format!("{} {}", package, version)),
+				check.header.as_ref().map(|header| format!("<{}>", header)),
+			];
+			println!(
+				"  ✓ {:<12} {}",
+				check.name,
+				details.into_iter().flatten().collect::<Vec<_>>().join(", ")
+			);
+		} else {
+			failed += 1;
+			println!("  ✗ {:<12} {}", check.name, problems.join("; "));
+		}
+	}
+
+	if failed > 0 {
+		anyhow::bail!(
+			"{} of {} dependencies are not usable on this host; run 'sticks install' to install them",
+			failed,
+			checks.len()
+		);
+	}
+	println!("✓ All dependencies are installed");
+	Ok(())
+}
+
+fn project_language() -> Language {
+	Language::detect().ok().flatten().unwrap_or(Language::C)
+}
+
+fn pkg_config_cflags(module: &str) -> Vec<String> {
+	Command::new("pkg-config")
+		.args(["--cflags", module])
+		.output()
+		.ok()
+		.filter(|output| output.status.success())
+		.map(|output| {
+			String::from_utf8_lossy(&output.stdout)
+				.split_whitespace()
+				.map(String::from)
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+fn header_exists(header: &str, language: Language, cflags: &[String]) -> bool {
+	let child = Command::new(language.cc())
+		.args(["-E", "-x", source_language(language), "-o", "/dev/null"])
+		.args(cflags)
+		.arg("-")
+		.stdin(Stdio::piped())
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.spawn();
+	let mut child = match child {
+		Ok(child) => child,
+		Err(_) => return false,
+	};
+	if let Some(mut stdin) = child.stdin.take() {
+		let _ = writeln!(stdin, "#include <{}>", header);
+	}
+	child.wait().map(|status| status.success()).unwrap_or(false)
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditReport {
+	pub unused: Vec<String>,
+	pub undeclared: Vec<UndeclaredUsage>,
+	pub unresolved: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UndeclaredUsage {
+	pub usage: String,
+	pub suggestion: String,
+	pub files: Vec<String>,
+}
+
+impl AuditReport {
+	pub fn is_clean(&self) -> bool {
+		self.unused.is_empty() && self.undeclared.is_empty()
+	}
+}
+
+struct DeclaredDependency {
+	name: String,
+	package: Option<String>,
+	header: Option<String>,
+	include_dirs: Vec<PathBuf>,
+	libraries: Vec<String>,
+}
+
+pub fn scan_includes() -> Result<BTreeMap<String, Vec<String>>> {
+	let mut includes: BTreeMap<String, Vec<String>> = BTreeMap::new();
+	for dir in [project::SRC_DIR, project::INCLUDE_DIR] {
+		for file in source_files(Path::new(dir))? {
+			let content = fs::read_to_string(&file)
+				.with_context(|| format!("Failed to read {}", file.display()))?;
+			for (header, quoted) in content.lines().filter_map(include_directive) {
+				if is_local_header(&file, header, quoted) {
+					continue;
+				}
+				let files = includes.entry(header.to_string()).or_default();
+				let file = file.display().to_string();
+				if !files.contains(&file) {
+					files.push(file);
+				}
+			}
+		}
+	}
+	Ok(includes)
+}
+
+pub fn linked_libraries() -> Result<Vec<String>> {
+	let build_system = match detect_build_system()? {
+		Some(bs) => bs,
+		None => return Ok(Vec::new()),
+	};
+	let file = match build_system {
+		BuildSystem::Makefile => makefile::FILENAME,
+		BuildSystem::CMake => cmake::FILENAME,
+	};
+	let content = fs::read_to_string(file).with_context(|| format!("Failed to read {}", file))?;
+
+	let mut names: Vec<String> = Vec::new();
+	match build_system {
+		BuildSystem::Makefile => {
+			let mut in_region = false;
+			for line in content.lines().map(str::trim) {
+				if line.starts_with(REGION_BEGIN) {
+					in_region = true;
+				} else if line.starts_with(REGION_END) {
+					in_region = false;
+				}
+				if in_region || line.starts_with('#') {
+					continue;
+				}
+				names.extend(link_flags(line.split_whitespace()));
+			}
+		}
+		BuildSystem::CMake => {
+			for command in cmake_editor::parse(&content).user_commands() {
+				let args = command.values();
+				names.extend(link_flags(args.iter().flat_map(|a| a.split_whitespace())));
+				if command.is("target_link_libraries") {
+					names.extend(
+						args.iter()
+							.skip(1)
+							.filter(|arg| {
+								!matches!(**arg, "PRIVATE" | "PUBLIC" | "INTERFACE")
+									&& !arg.contains("::") && !arg.contains('$')
+									&& !arg.starts_with('-')
+							})
+							.map(|arg| arg.to_string()),
+					);
+				}
+			}
+		}
+	}
+
+	let mut libraries: Vec<String> = Vec::new();
+	for name in names {
+		if !name.is_empty() && !libraries.contains(&name) {
+			libraries.push(name);
+		}
+	}
+	Ok(libraries)
+}
+
+fn link_flags<'a>(tokens: impl Iterator<Item = &'a str>) -> Vec<String> {
+	tokens
+		.filter_map(|token| token.strip_prefix("-l"))
+		.map(String::from)
+		.collect()
+}
+
+pub fn audit_dependencies() -> Result<AuditReport> {
+	let pm = HostPackageManager::detect();
+	let language = project_language();
+	let declared: Vec<DeclaredDependency> = declared_dependencies()?
+		.into_iter()
+		.map(|name| declared_dependency(name, pm))
+		.collect();
+
+	let mut search_dirs = compiler_include_dirs(language);
+	for dep in &declared {
+		search_dirs.extend(dep.include_dirs.iter().cloned());
+	}
+
+	let mut used: BTreeSet<String> = BTreeSet::new();
+	let mut report = AuditReport::default();
+
+	for (header, files) in scan_includes()? {
+		if let Some(dep) = declared
+			.iter()
+			.find(|dep| dep.header.as_deref() == Some(header.as_str()))
+		{
+			used.insert(dep.name.clone());
+			continue;
+		}
+
+		let path = match search_dirs
+			.iter()
+			.map(|dir| dir.join(&header))
+			.find(|path| path.is_file())
+		{
+			Some(path) => path,
+			None => {
+				report.unresolved.push(header);
+				continue;
+			}
+		};
+		let owner = pm.and_then(|pm| pm.owning_package(&path));
+
+		let provider = declared.iter().find(|dep| {
+			dep.include_dirs.iter().any(|dir| path.starts_with(dir))
+				|| (owner.is_some() && dep.package == owner)
+		});
+		if let Some(dep) = provider {
+			used.insert(dep.name.clone());
+			continue;
+		}
+
+		let owner = match owner {
+			Some(owner) if !is_toolchain_package(&owner) => owner,
+			_ => continue,
+		};
+		let suggestion = pm
+			.and_then(|pm| {
+				KNOWN_PACKAGES
+					.iter()
+					.find(|known| known.package_for(pm) == owner)
+			})
+			.map(|known| known.name.to_string())
+			.unwrap_or(owner);
+		report.undeclared.push(UndeclaredUsage {
+			usage: format!("<{}>", header),
+			suggestion,
+			files,
+		});
+	}
+
+	for library in linked_libraries()? {
+		if audit::STANDARD_LIBRARIES.contains(&library.as_str()) {
+			continue;
+		}
+		match declared.iter().find(|dep| dep.libraries.contains(&library)) {
+			Some(dep) => {
+				used.insert(dep.name.clone());
+			}
+			None => report.undeclared.push(UndeclaredUsage {
+				usage: format!("-l{}", library),
+				suggestion: known_library(&library)
+					.map(|known| known.name.to_string())
+					.unwrap_or_else(|| library.clone()),
+				files: vec![build_file_name()?.to_string()],
+			}),
+		}
+	}
+
+	report.unused = declared
+		.into_iter()
+		.map(|dep| dep.name)
+		.filter(|name| !used.contains(name))
+		.collect();
+	Ok(report)
+}
+
+pub fn run_audit() -> Result<()> {
+	let report = audit_dependencies()?;
+
+	if report.unused.is_empty() {
+		println!("✓ Every declared dependency is used");
+	} else {
+		println!("⚠️  Declared but never used:");
+		for name in &report.unused {
+			println!("  {:<16} remove with 'sticks remove {}'", name, name);
+		}
+	}
+
+	if report.undeclared.is_empty() {
+		println!("✓ No undeclared dependencies found");
+	} else {
+		println!("⚠️  Used but not declared:");
+		for usage in &report.undeclared {
+			println!(
+				"  {:<24} in {} → sticks add {}",
+				usage.usage,
+				usage.files.join(", "),
+				usage.suggestion
+			);
+		}
+	}
+
+	if !report.unresolved.is_empty() {
+		println!(
+			"ℹ️  Headers that could not be found (run 'sticks deps check'): {}",
+			report.unresolved.join(", ")
+		);
+	}
+
+	if !report.is_clean() {
+		anyhow::bail!(
+			"Dependency audit found {} unused and {} undeclared dependencies",
+			report.unused.len(),
+			report.undeclared.len()
+		);
+	}
+	Ok(())
+}
+
+fn declared_dependency(name: String, pm: Option<HostPackageManager>) -> DeclaredDependency {
+	let known = find_known_package(&name);
+	let module = pkg_config_module(&name).filter(|module| pkg_config_exists(module));
+	let cflags = module.as_deref().map(pkg_config_cflags).unwrap_or_default();
+
+	let mut libraries: Vec<String> = module
+		.as_deref()
+		.map(pkg_config_libraries)
+		.unwrap_or_default();
+	libraries.push(name.clone());
+	if let Some(known) = known {
+		libraries.push(known.name.to_string());
+		libraries.extend(known.aliases.iter().map(|a| a.to_string()));
+	}
+
+	DeclaredDependency {
+		package: pm.and_then(|pm| translate_package(&name, pm)),
+		header: known.map(|known| known.header.to_string()),
+		include_dirs: cflags
+			.iter()
+			.filter_map(|flag| flag.strip_prefix("-I"))
+			.map(PathBuf::from)
+			.collect(),
+		libraries,
+		name,
+	}
+}
+
+fn known_library(library: &str) -> Option<&'static KnownPackage> {
+	find_known_package(library).or_else(|| find_known_package(&format!("lib{}", library)))
+}
+
+fn is_toolchain_package(package: &str) -> bool {
+	audit::TOOLCHAIN_PACKAGES
+		.iter()
+		.any(|prefix| package.starts_with(prefix))
+}
+
+fn build_file_name() -> Result<&'static str> {
+	Ok(match detect_build_system()? {
+		Some(BuildSystem::CMake) => cmake::FILENAME,
+		_ => makefile::FILENAME,
+	})
+}
+
+fn pkg_config_libraries(module: &str) -> Vec<String> {
+	Command::new("pkg-config")
+		.args(["--libs-only-l", module])
+		.output()
+		.ok()
+		.filter(|output| output.status.success())
+		.map(|output| {
+			String::from_utf8_lossy(&output.stdout)
+				.split_whitespace()
+				.filter_map(|flag| flag.strip_prefix("-l"))
+				.map(String::from)
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+fn compiler_include_dirs(language: Language) -> Vec<PathBuf> {
+	let output = Command::new(language.cc())
+		.args(["-E", "-x", source_language(language), "-", "-v"])
+		.stdin(Stdio::null())
+		.output();
+	let stderr = match output {
+		Ok(output) => String::from_utf8_lossy(&output.stderr).to_string(),
+		Err(_) => {
+			return vec![
+				PathBuf::from("/usr/local/include"),
+				PathBuf::from("/usr/include"),
+			]
+		}
+	};
+	stderr
+		.lines()
+		.skip_while(|line| !line.starts_with("#include <...>"))
+		.skip(1)
+		.take_while(|line| line.starts_with(' '))
+		.map(|line| {
+			let dir = PathBuf::from(line.trim());
+			dir.canonicalize().unwrap_or(dir)
+		})
+		.collect()
+}
+
+fn include_directive(line: &str) -> Option<(&str, bool)> {
+	let rest = line.trim_start().strip_prefix('#')?.trim_start();
+	let rest = rest.strip_prefix("include")?.trim_start();
+	if let Some(header) = rest.strip_prefix('<') {
+		return Some((header.split('>').next()?, false));
+	}
+	let header = rest.strip_prefix('"')?;
+	Some((header.split('"').next()?, true))
+}
+
+fn is_local_header(file: &Path, header: &str, quoted: bool) -> bool {
+	let relative = quoted && file.parent().is_some_and(|dir| dir.join(header).exists());
+	relative
+		|| Path::new(project::INCLUDE_DIR).join(header).exists()
+		|| Path::new(project::SRC_DIR).join(header).exists()
+}
+
+pub fn source_files(dir: &Path) -> Result<Vec<PathBuf>> {
+	let mut files = Vec::new();
+	if !dir.is_dir() {
+		return Ok(files);
+	}
+	for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+		let path = entry.context("Failed to read directory entry")?.path();
+		if path.is_dir() {
+			files.extend(source_files(&path)?);
+		} else if path
+			.extension()
+			.and_then(|ext| ext.to_str())
+			.is_some_and(|ext| audit::SOURCE_EXTENSIONS.contains(&ext))
+		{
+			files.push(path);
+		}
+	}
+	files.sort();
+	Ok(files)
+}
+
+fn source_language(language: Language) -> &'static str {
+	match language {
+		Language::C | Language::Mixed | Language::ObjC | Language::Fortran | Language::CFortran => {
+			"c"
+		}
+		Language::Cpp | Language::ObjCpp => "c++",
+	}
+}
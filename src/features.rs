@@ -15,8 +15,10 @@ pub fn detect_build_system() -> Result<Option<BuildSystem>> {
 }
 
 pub fn detect_package_manager() -> Result<Option<PackageManager>> {
-	if Path::new("conanfile.txt").exists() || Path::new("conanfile.py").exists() {
+	if Path::new("conanfile.txt").exists() {
 		Ok(Some(PackageManager::Conan))
+	} else if Path::new("conanfile.py").exists() {
+		Ok(Some(PackageManager::ConanRecipe))
 	} else if Path::new("vcpkg.json").exists() {
 		Ok(Some(PackageManager::Vcpkg))
 	} else {
@@ -67,6 +69,7 @@ fn convert_build_system_with_prompt(
 	let build_file_content = generator.generate_build_file(language, project_name);
 	fs::write(generator.extension(), build_file_content)
 		.context("Failed to write new build system file")?;
+	crate::sources::ensure_mixed_sources()?;
 
 	println!("✓ Successfully converted project from {} to {}", from, to);
 	Ok(())
@@ -89,6 +92,10 @@ pub fn add_package_manager_to_project(pm: PackageManager, project_name: &str) ->
 	fs::write(pm_generator.extension(), manifest)
 		.with_context(|| format!("Failed to write {} manifest", pm_generator.name()))?;
 
+	if let Some(build_system) = detect_build_system()? {
+		crate::package_managers::integrate_package_manager(pm, build_system)?;
+	}
+
 	println!("✓ Generated {} configuration", pm);
 	println!(
 		"📝 Next steps: {}",
@@ -111,6 +118,10 @@ pub fn remove_package_manager_from_project(pm: PackageManager) -> Result<()> {
 	fs::remove_file(pm_generator.extension())
 		.with_context(|| format!("Failed to remove {} file", pm_generator.name()))?;
 
+	if let Some(build_system) = detect_build_system()? {
+		crate::package_managers::remove_package_manager_integration(pm, build_system)?;
+	}
+
 	println!("✓ Removed {} configuration", pm);
 	Ok(())
 }
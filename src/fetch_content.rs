@@ -0,0 +1,180 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::process::Command;
+
+use crate::cmake_editor::{self, Placement};
+use crate::constants::cmake;
+use crate::features::detect_build_system;
+use crate::BuildSystem;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitDependency {
+	pub name: String,
+	pub url: String,
+	pub git_ref: String,
+	pub link: String,
+}
+
+pub fn add_git_dependency(
+	name: &str,
+	url: &str,
+	tag: Option<&str>,
+	link: Option<&str>,
+) -> Result<()> {
+	if detect_build_system()? != Some(BuildSystem::CMake) {
+		anyhow::bail!(
+			"Git dependencies use CMake FetchContent and need a CMakeLists.txt; \
+			use 'sticks vendor add' for Makefile projects"
+		);
+	}
+	if name.is_empty()
+		|| !name
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+	{
+		anyhow::bail!(
+			"Invalid dependency name '{}': use letters, digits, '_', '-' or '.'",
+			name
+		);
+	}
+
+	let git_ref = resolve_git_ref(url, tag)?;
+	let dependency = GitDependency {
+		name: name.to_string(),
+		url: url.to_string(),
+		git_ref,
+		link: link.unwrap_or(name).to_string(),
+	};
+
+	let content = fs::read_to_string(cmake::FILENAME)
+		.with_context(|| format!("Failed to read {}", cmake::FILENAME))?;
+	let updated = cmake_editor::set_region(
+		&content,
+		&region_name(name),
+		&fetch_content_block(&dependency),
+		Placement::AfterTarget,
+	);
+	fs::write(cmake::FILENAME, updated)
+		.with_context(|| format!("Failed to write updated {}", cmake::FILENAME))?;
+
+	println!(
+		"✓ Added {} from {} at {} (linked as {})",
+		dependency.name, dependency.url, dependency.git_ref, dependency.link
+	);
+	Ok(())
+}
+
+pub fn remove_git_dependencies(names: &[String]) -> Result<Vec<String>> {
+	if detect_build_system()? != Some(BuildSystem::CMake) {
+		return Ok(Vec::new());
+	}
+	let mut content = fs::read_to_string(cmake::FILENAME)
+		.with_context(|| format!("Failed to read {}", cmake::FILENAME))?;
+	let mut removed = Vec::new();
+
+	for name in names {
+		if cmake_editor::read_region(&content, &region_name(name)).is_some() {
+			content = cmake_editor::remove_region(&content, &region_name(name));
+			removed.push(name.clone());
+		}
+	}
+
+	if !removed.is_empty() {
+		fs::write(cmake::FILENAME, content)
+			.with_context(|| format!("Failed to write updated {}", cmake::FILENAME))?;
+		println!("✓ Removed git dependencies: {}", removed.join(", "));
+	}
+	Ok(removed)
+}
+
+pub fn git_dependencies(content: &str) -> Vec<GitDependency> {
+	cmake_editor::region_names(content)
+		.iter()
+		.filter_map(|region| region.strip_prefix(cmake::FETCH_CONTENT_REGION_PREFIX))
+		.filter_map(|name| {
+			let region =
+				cmake_editor::parse(&cmake_editor::read_region(content, &region_name(name))?);
+			let declare = region.command("FetchContent_Declare")?.values();
+			let field = |key: &str| {
+				let index = declare.iter().position(|arg| *arg == key)?;
+				declare.get(index + 1).map(|value| value.to_string())
+			};
+			let link = region
+				.command("target_link_libraries")?
+				.values()
+				.into_iter()
+				.skip_while(|arg| *arg != "PRIVATE")
+				.skip(1)
+				.collect::<Vec<_>>()
+				.join(" ");
+			Some(GitDependency {
+				name: name.to_string(),
+				url: field("GIT_REPOSITORY")?,
+				git_ref: field("GIT_TAG")?,
+				link,
+			})
+		})
+		.collect()
+}
+
+pub fn resolve_git_ref(url: &str, tag: Option<&str>) -> Result<String> {
+	let mut args = vec!["ls-remote", url];
+	args.push(tag.unwrap_or("HEAD"));
+	let output = Command::new("git")
+		.args(&args)
+		.output()
+		.context("Failed to run git. Is it installed?")?;
+	if !output.status.success() {
+		anyhow::bail!(
+			"Could not reach git repository {}: {}",
+			url,
+			String::from_utf8_lossy(&output.stderr).trim()
+		);
+	}
+	let refs = String::from_utf8_lossy(&output.stdout);
+
+	match tag {
+		Some(tag) => {
+			let found = refs.lines().any(|line| {
+				line.split_whitespace().nth(1).is_some_and(|r| {
+					r == format!("refs/tags/{}", tag) || r == format!("refs/heads/{}", tag)
+				})
+			});
+			if found || is_commit_hash(tag) {
+				Ok(tag.to_string())
+			} else {
+				anyhow::bail!("Tag or branch '{}' not found in {}", tag, url)
+			}
+		}
+		None => refs
+			.split_whitespace()
+			.next()
+			.filter(|sha| is_commit_hash(sha))
+			.map(String::from)
+			.with_context(|| format!("Could not resolve HEAD of {}", url)),
+	}
+}
+
+fn is_commit_hash(value: &str) -> bool {
+	(7..=40).contains(&value.len()) && value.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn region_name(name: &str) -> String {
+	format!("{}{}", cmake::FETCH_CONTENT_REGION_PREFIX, name)
+}
+
+fn fetch_content_block(dependency: &GitDependency) -> String {
+	format!(
+		"include(FetchContent)\n\
+		FetchContent_Declare({name}\n\
+		\tGIT_REPOSITORY {url}\n\
+		\tGIT_TAG {git_ref}\n\
+		)\n\
+		FetchContent_MakeAvailable({name})\n\
+		target_link_libraries(${{PROJECT_NAME}} PRIVATE {link})\n",
+		name = dependency.name,
+		url = dependency.url,
+		git_ref = dependency.git_ref,
+		link = dependency.link
+	)
+}
@@ -18,3 +18,17 @@ pub fn create_dir(project_name: &str) -> Result<()> {
 
 	Ok(())
 }
+
+pub(crate) fn read(path: &str) -> Result<String> {
+	fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))
+}
+
+pub(crate) fn write(path: &str, content: &str) -> Result<()> {
+	fs::write(path, content).with_context(|| format!("Failed to write {}", path))
+}
+
+pub(crate) fn join_lines(lines: &[String]) -> String {
+	let mut content = lines.join("\n");
+	content.push('\n');
+	content
+}
@@ -26,12 +26,25 @@ pub fn run_interactive() -> Result<()> {
 }
 
 pub fn select_language_interactive() -> Result<Language> {
-	let options = vec!["C", "C++"];
+	let options = vec![
+		"C",
+		"C++",
+		"Mixed (C/C++/ASM)",
+		"Objective-C",
+		"Objective-C++",
+		"Fortran",
+		"C + Fortran",
+	];
 	let selected = interactive_select(&options)?;
 
 	Ok(match selected {
 		0 => Language::C,
 		1 => Language::Cpp,
+		2 => Language::Mixed,
+		3 => Language::ObjC,
+		4 => Language::ObjCpp,
+		5 => Language::Fortran,
+		6 => Language::CFortran,
 		_ => unreachable!(),
 	})
 }
@@ -47,6 +60,7 @@ pub fn select_build_system_interactive() -> Result<BuildSystem> {
 	})
 }
 
+#[allow(clippy::collapsible_match)]
 fn interactive_select(options: &[&str]) -> Result<usize> {
 	let mut selected = 0;
 	let num_options = options.len();
@@ -0,0 +1,349 @@
+use anyhow::{Context, Result};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+	Null,
+	Bool(bool),
+	Number(String),
+	String(String),
+	Array(Vec<JsonValue>),
+	Object(Vec<(String, JsonValue)>),
+}
+
+impl JsonValue {
+	pub fn parse(input: &str) -> Result<JsonValue> {
+		let mut parser = Parser {
+			chars: input.chars().collect(),
+			pos: 0,
+		};
+		let value = parser.value()?;
+		parser.skip_whitespace();
+		if parser.pos < parser.chars.len() {
+			anyhow::bail!("Unexpected trailing characters at offset {}", parser.pos);
+		}
+		Ok(value)
+	}
+
+	pub fn object() -> JsonValue {
+		JsonValue::Object(Vec::new())
+	}
+
+	pub fn string(value: &str) -> JsonValue {
+		JsonValue::String(value.to_string())
+	}
+
+	pub fn get(&self, key: &str) -> Option<&JsonValue> {
+		match self {
+			JsonValue::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+			_ => None,
+		}
+	}
+
+	pub fn get_mut(&mut self, key: &str) -> Option<&mut JsonValue> {
+		match self {
+			JsonValue::Object(entries) => {
+				entries.iter_mut().find(|(k, _)| k == key).map(|(_, v)| v)
+			}
+			_ => None,
+		}
+	}
+
+	pub fn set(&mut self, key: &str, value: JsonValue) {
+		if let JsonValue::Object(entries) = self {
+			match entries.iter_mut().find(|(k, _)| k == key) {
+				Some((_, existing)) => *existing = value,
+				None => entries.push((key.to_string(), value)),
+			}
+		}
+	}
+
+	pub fn remove(&mut self, key: &str) -> Option<JsonValue> {
+		match self {
+			JsonValue::Object(entries) => {
+				let index = entries.iter().position(|(k, _)| k == key)?;
+				Some(entries.remove(index).1)
+			}
+			_ => None,
+		}
+	}
+
+	pub fn as_str(&self) -> Option<&str> {
+		match self {
+			JsonValue::String(value) => Some(value),
+			_ => None,
+		}
+	}
+
+	pub fn as_array(&self) -> Option<&[JsonValue]> {
+		match self {
+			JsonValue::Array(items) => Some(items),
+			_ => None,
+		}
+	}
+
+	pub fn as_array_mut(&mut self) -> Option<&mut Vec<JsonValue>> {
+		match self {
+			JsonValue::Array(items) => Some(items),
+			_ => None,
+		}
+	}
+
+	pub fn render(&self) -> String {
+		self.render_with_indent("  ")
+	}
+
+	pub fn render_with_indent(&self, indent: &str) -> String {
+		let mut output = String::new();
+		self.write(&mut output, indent, 0);
+		output.push('\n');
+		output
+	}
+
+	fn write(&self, output: &mut String, unit: &str, indent: usize) {
+		match self {
+			JsonValue::Null => output.push_str("null"),
+			JsonValue::Bool(value) => output.push_str(if *value { "true" } else { "false" }),
+			JsonValue::Number(value) => output.push_str(value),
+			JsonValue::String(value) => write_string(output, value),
+			JsonValue::Array(items) if items.is_empty() => output.push_str("[]"),
+			JsonValue::Object(entries) if entries.is_empty() => output.push_str("{}"),
+			JsonValue::Array(items) => {
+				output.push_str("[\n");
+				for (index, item) in items.iter().enumerate() {
+					output.push_str(&unit.repeat(indent + 1));
+					item.write(output, unit, indent + 1);
+					if index + 1 < items.len() {
+						output.push(',');
+					}
+					output.push('\n');
+				}
+				output.push_str(&unit.repeat(indent));
+				output.push(']');
+			}
+			JsonValue::Object(entries) => {
+				output.push_str("{\n");
+				for (index, (key, value)) in entries.iter().enumerate() {
+					output.push_str(&unit.repeat(indent + 1));
+					write_string(output, key);
+					output.push_str(": ");
+					value.write(output, unit, indent + 1);
+					if index + 1 < entries.len() {
+						output.push(',');
+					}
+					output.push('\n');
+				}
+				output.push_str(&unit.repeat(indent));
+				output.push('}');
+			}
+		}
+	}
+}
+
+pub fn detect_indent(input: &str) -> &str {
+	input
+		.lines()
+		.skip(1)
+		.filter(|line| !line.trim().is_empty())
+		.map(|line| &line[..line.len() - line.trim_start().len()])
+		.find(|indent| !indent.is_empty())
+		.unwrap_or("  ")
+}
+
+fn write_string(output: &mut String, value: &str) {
+	output.push('"');
+	for c in value.chars() {
+		match c {
+			'"' => output.push_str("\\\""),
+			'\\' => output.push_str("\\\\"),
+			'\n' => output.push_str("\\n"),
+			'\r' => output.push_str("\\r"),
+			'\t' => output.push_str("\\t"),
+			c if (c as u32) < 0x20 => output.push_str(&format!("\\u{:04x}", c as u32)),
+			c => output.push(c),
+		}
+	}
+	output.push('"');
+}
+
+struct Parser {
+	chars: Vec<char>,
+	pos: usize,
+}
+
+impl Parser {
+	fn skip_whitespace(&mut self) {
+		while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+			self.pos += 1;
+		}
+	}
+
+	fn expect(&mut self, expected: char) -> Result<()> {
+		self.skip_whitespace();
+		match self.chars.get(self.pos) {
+			Some(&c) if c == expected => {
+				self.pos += 1;
+				Ok(())
+			}
+			Some(&c) => anyhow::bail!(
+				"Expected '{}' but found '{}' at offset {}",
+				expected,
+				c,
+				self.pos
+			),
+			None => anyhow::bail!("Expected '{}' but reached end of input", expected),
+		}
+	}
+
+	fn value(&mut self) -> Result<JsonValue> {
+		self.skip_whitespace();
+		match self.chars.get(self.pos) {
+			Some('{') => self.object(),
+			Some('[') => self.array(),
+			Some('"') => Ok(JsonValue::String(self.string()?)),
+			Some('t') => self.literal("true", JsonValue::Bool(true)),
+			Some('f') => self.literal("false", JsonValue::Bool(false)),
+			Some('n') => self.literal("null", JsonValue::Null),
+			Some(c) if *c == '-' || c.is_ascii_digit() => Ok(self.number()),
+			Some(c) => anyhow::bail!("Unexpected character '{}' at offset {}", c, self.pos),
+			None => anyhow::bail!("Unexpected end of input"),
+		}
+	}
+
+	fn object(&mut self) -> Result<JsonValue> {
+		self.expect('{')?;
+		let mut entries = Vec::new();
+		self.skip_whitespace();
+		if self.chars.get(self.pos) == Some(&'}') {
+			self.pos += 1;
+			return Ok(JsonValue::Object(entries));
+		}
+		loop {
+			self.skip_whitespace();
+			let key = self.string()?;
+			self.expect(':')?;
+			entries.push((key, self.value()?));
+			self.skip_whitespace();
+			match self.chars.get(self.pos) {
+				Some(',') => self.pos += 1,
+				Some('}') => {
+					self.pos += 1;
+					return Ok(JsonValue::Object(entries));
+				}
+				_ => anyhow::bail!("Expected ',' or '}}' at offset {}", self.pos),
+			}
+		}
+	}
+
+	fn array(&mut self) -> Result<JsonValue> {
+		self.expect('[')?;
+		let mut items = Vec::new();
+		self.skip_whitespace();
+		if self.chars.get(self.pos) == Some(&']') {
+			self.pos += 1;
+			return Ok(JsonValue::Array(items));
+		}
+		loop {
+			items.push(self.value()?);
+			self.skip_whitespace();
+			match self.chars.get(self.pos) {
+				Some(',') => self.pos += 1,
+				Some(']') => {
+					self.pos += 1;
+					return Ok(JsonValue::Array(items));
+				}
+				_ => anyhow::bail!("Expected ',' or ']' at offset {}", self.pos),
+			}
+		}
+	}
+
+	fn string(&mut self) -> Result<String> {
+		self.expect('"')?;
+		let mut value = String::new();
+		loop {
+			let c = *self
+				.chars
+				.get(self.pos)
+				.context("Unterminated string literal")?;
+			self.pos += 1;
+			match c {
+				'"' => return Ok(value),
+				'\\' => {
+					let escaped = *self
+						.chars
+						.get(self.pos)
+						.context("Unterminated escape sequence")?;
+					self.pos += 1;
+					match escaped {
+						'"' | '\\' | '/' => value.push(escaped),
+						'b' => value.push('\u{8}'),
+						'f' => value.push('\u{c}'),
+						'n' => value.push('\n'),
+						'r' => value.push('\r'),
+						't' => value.push('\t'),
+						'u' => {
+							let code = match self.unicode_escape()? {
+								high @ 0xd800..=0xdbff => {
+									let low = match self.chars.get(self.pos..self.pos + 2) {
+										Some(['\\', 'u']) => {
+											self.pos += 2;
+											self.unicode_escape()?
+										}
+										_ => anyhow::bail!("Unpaired surrogate \\u{:04x}", high),
+									};
+									if !(0xdc00..=0xdfff).contains(&low) {
+										anyhow::bail!("Unpaired surrogate \\u{:04x}", high);
+									}
+									0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+								}
+								low @ 0xdc00..=0xdfff => {
+									anyhow::bail!("Unpaired surrogate \\u{:04x}", low)
+								}
+								code => code,
+							};
+							value.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+						}
+						other => anyhow::bail!("Invalid escape sequence \\{}", other),
+					}
+				}
+				c => value.push(c),
+			}
+		}
+	}
+
+	fn unicode_escape(&mut self) -> Result<u32> {
+		let hex: String = self
+			.chars
+			.get(self.pos..self.pos + 4)
+			.context("Truncated unicode escape")?
+			.iter()
+			.collect();
+		self.pos += 4;
+		u32::from_str_radix(&hex, 16).with_context(|| format!("Invalid unicode escape \\u{}", hex))
+	}
+
+	fn number(&mut self) -> JsonValue {
+		let start = self.pos;
+		while self
+			.chars
+			.get(self.pos)
+			.is_some_and(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+		{
+			self.pos += 1;
+		}
+		JsonValue::Number(self.chars[start..self.pos].iter().collect())
+	}
+
+	fn literal(&mut self, word: &str, value: JsonValue) -> Result<JsonValue> {
+		let end = self.pos + word.len();
+		if self
+			.chars
+			.get(self.pos..end)
+			.map(|s| s.iter().collect::<String>())
+			!= Some(word.to_string())
+		{
+			anyhow::bail!("Invalid literal at offset {}", self.pos);
+		}
+		self.pos = end;
+		Ok(value)
+	}
+}
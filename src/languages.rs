@@ -3,70 +3,298 @@ use std::fs;
 use std::path::Path;
 use std::str::FromStr;
 
+use crate::build_systems::BuildProfile;
+use crate::config::ProjectConfig;
+
 pub trait LanguageConsts {
 	fn cc(&self) -> &'static str;
 	fn extension(&self) -> &'static str;
 	fn generate_helloworld_content(&self) -> String;
 
 	fn generate_makefile_content(&self, project_name: &str) -> String {
-		format!(
-			"# Compiler and flags\n\
-			CC = {}\n\
-			CFLAGS = -Wall -Wextra -Werror -O2 -g\n\
-			LDFLAGS =\n\
-			\n\
-			# Directories\n\
-			SRC_DIR = src\n\
-			BUILD_DIR = build\n\
-			BIN_DIR = bin\n\
-			\n\
-			# Source files\n\
-			SRCS = $(wildcard $(SRC_DIR)/*.{})\n\
-			OBJS = $(SRCS:$(SRC_DIR)/%.{}=$(BUILD_DIR)/%.o)\n\
-			\n\
-			# Target executable\n\
-			TARGET = $(BIN_DIR)/{}\n\
-			\n\
-			# Default target\n\
-			all: $(TARGET)\n\
-			\n\
-			# Build target\n\
-			$(TARGET): $(OBJS)\n\
-			\t@mkdir -p $(BUILD_DIR) $(BIN_DIR)\n\
-			\t$(CC) $(CFLAGS) -o $@ $^ $(LDFLAGS)\n\
-			\t@echo \"Build complete: $(TARGET)\"\n\
-			\n\
-			# Compile source files\n\
-			$(BUILD_DIR)/%.o: $(SRC_DIR)/%.{}\n\
-			\t@mkdir -p $(BUILD_DIR)\n\
+		self.generate_makefile_content_with_profile(project_name, &BuildProfile::default())
+	}
+
+	fn generate_makefile_content_with_profile(
+		&self,
+		project_name: &str,
+		profile: &BuildProfile,
+	) -> String;
+}
+
+const CFLAGS: &str = "-Wall -Wextra -Werror -O2 -g -Iinclude";
+const GNUSTEP_CFLAGS: &str = "-Wall -Wextra -O2 -g -Iinclude $(shell gnustep-config --objc-flags)";
+const GNUSTEP_LDFLAGS: &str = "$(shell gnustep-config --base-libs)";
+const PROMPT_LANGUAGES: [Language; 7] = [
+	Language::C,
+	Language::Cpp,
+	Language::Mixed,
+	Language::ObjC,
+	Language::ObjCpp,
+	Language::Fortran,
+	Language::CFortran,
+];
+pub const CPP_EXTENSIONS: &[&str] = &["cpp", "cc", "cxx", "C"];
+
+fn makefile_template(
+	compiler: &str,
+	cflags: &str,
+	ldflags: &str,
+	extensions: &[&str],
+	project_name: &str,
+) -> String {
+	let ldflags = if ldflags.is_empty() {
+		String::new()
+	} else {
+		format!(" {}", ldflags)
+	};
+	let find = extensions
+		.iter()
+		.map(|ext| format!("-name '*.{}'", ext))
+		.collect::<Vec<_>>()
+		.join(" -o ");
+	let objects = match extensions {
+		[extension] => format!("$(SRCS:$(SRC_DIR)/%.{}=$(BUILD_DIR)/%.o)", extension),
+		_ => "$(patsubst $(SRC_DIR)/%,$(BUILD_DIR)/%.o,$(basename $(SRCS)))".to_string(),
+	};
+	let rules = extensions
+		.iter()
+		.map(|ext| {
+			format!(
+				"$(BUILD_DIR)/%.o: $(SRC_DIR)/%.{}\n\
+				\t@mkdir -p $(dir $@)\n\
+				\t$(CC) $(CFLAGS) -c $< -o $@\n",
+				ext
+			)
+		})
+		.collect::<Vec<_>>()
+		.join("\n");
+	format!(
+		"# Compiler and flags\n\
+		CC = {}\n\
+		CFLAGS = {}\n\
+		LDFLAGS ={}\n\
+		\n\
+		# Directories\n\
+		SRC_DIR = src\n\
+		BUILD_DIR = build\n\
+		BIN_DIR = bin\n\
+		\n\
+		# Source files\n\
+		SRCS = $(shell find $(SRC_DIR) {})\n\
+		OBJS = {}\n\
+		\n\
+		# Target executable\n\
+		TARGET = $(BIN_DIR)/{}\n\
+		\n\
+		# Default target\n\
+		all: $(TARGET)\n\
+		\n\
+		# Build target\n\
+		$(TARGET): $(OBJS)\n\
+		\t@mkdir -p $(BUILD_DIR) $(BIN_DIR)\n\
+		\t$(CC) $(CFLAGS) -o $@ $^ $(LDFLAGS)\n\
+		\t@echo \"Build complete: $(TARGET)\"\n\
+		\n\
+		# Compile source files\n\
+		{}\
+		\n\
+		# Clean build artifacts\n\
+		clean:\n\
+		\t@rm -rf $(BUILD_DIR) $(BIN_DIR)\n\
+		\t@echo \"Cleaned build artifacts\"\n\
+		\n\
+		# Run the program\n\
+		run: $(TARGET)\n\
+		\t./$(TARGET)\n\
+		\n\
+		# Rebuild\n\
+		rebuild: clean all\n\
+		\n\
+		.PHONY: all clean run rebuild\n",
+		compiler, cflags, ldflags, find, objects, project_name, rules
+	)
+}
+
+fn mixed_makefile_template(profile: &BuildProfile, project_name: &str) -> String {
+	let ldflags = match profile.ldflags() {
+		"" => String::new(),
+		flags => format!(" {}", flags),
+	};
+	let mut rules = String::new();
+	for (extension, compiler, flags) in [
+		("c", "CC", "CFLAGS"),
+		("cpp", "CXX", "CXXFLAGS"),
+		("cc", "CXX", "CXXFLAGS"),
+		("cxx", "CXX", "CXXFLAGS"),
+		("C", "CXX", "CXXFLAGS"),
+		("S", "AS", "ASFLAGS"),
+		("s", "AS", "ASFLAGS"),
+	] {
+		rules.push_str(&format!(
+			"$(BUILD_DIR)/%.{ext}.o: $(SRC_DIR)/%.{ext}\n\
+			\t@mkdir -p $(dir $@)\n\
+			\t$({compiler}) $({flags}) -c $< -o $@\n\
+			\n",
+			ext = extension,
+			compiler = compiler,
+			flags = flags
+		));
+	}
+	format!(
+		"# Compilers and flags\n\
+		CC = {cc}\n\
+		CXX = {cxx}\n\
+		AS = {cc}\n\
+		CFLAGS = {cflags}\n\
+		CXXFLAGS = $(CFLAGS)\n\
+		ASFLAGS = -g -Iinclude\n\
+		LDFLAGS ={ldflags}\n\
+		\n\
+		# Directories\n\
+		SRC_DIR = src\n\
+		BUILD_DIR = build\n\
+		BIN_DIR = bin\n\
+		\n\
+		# Source files, one list per language\n\
+		C_SRCS = $(shell find $(SRC_DIR) -name '*.c')\n\
+		CXX_SRCS = $(shell find $(SRC_DIR) -name '*.cpp' -o -name '*.cc' -o -name '*.cxx' -o -name '*.C')\n\
+		ASM_SRCS = $(shell find $(SRC_DIR) -name '*.S' -o -name '*.s')\n\
+		OBJS = $(patsubst $(SRC_DIR)/%,$(BUILD_DIR)/%.o,$(C_SRCS) $(CXX_SRCS) $(ASM_SRCS))\n\
+		\n\
+		# Target executable\n\
+		TARGET = $(BIN_DIR)/{name}\n\
+		\n\
+		# Default target\n\
+		all: $(TARGET)\n\
+		\n\
+		# Build target\n\
+		$(TARGET): $(OBJS)\n\
+		\t@mkdir -p $(BUILD_DIR) $(BIN_DIR)\n\
+		\t$(CXX) $(CXXFLAGS) -o $@ $^ $(LDFLAGS)\n\
+		\t@echo \"Build complete: $(TARGET)\"\n\
+		\n\
+		# Compile source files\n\
+		{rules}\
+		# Clean build artifacts\n\
+		clean:\n\
+		\t@rm -rf $(BUILD_DIR) $(BIN_DIR)\n\
+		\t@echo \"Cleaned build artifacts\"\n\
+		\n\
+		# Run the program\n\
+		run: $(TARGET)\n\
+		\t./$(TARGET)\n\
+		\n\
+		# Rebuild\n\
+		rebuild: clean all\n\
+		\n\
+		.PHONY: all clean run rebuild\n",
+		cc = profile.compiler(Language::C).command_line(),
+		cxx = profile.compiler(Language::Cpp).command_line(),
+		cflags = CFLAGS,
+		ldflags = ldflags,
+		name = project_name,
+		rules = rules
+	)
+}
+
+fn fortran_makefile_template(profile: &BuildProfile, project_name: &str, with_c: bool) -> String {
+	let ldflags = match profile.ldflags() {
+		"" => String::new(),
+		flags => format!(" {}", flags),
+	};
+	let (c_compiler, c_sources, c_objects, c_rule) = if with_c {
+		(
+			format!(
+				"CC = {}\n\
+				CFLAGS = {}\n",
+				profile.compiler(Language::C).command_line(),
+				CFLAGS
+			),
+			"C_SRCS = $(shell find $(SRC_DIR) -name '*.c')\n",
+			"$(C_SRCS) ",
+			"$(BUILD_DIR)/%.c.o: $(SRC_DIR)/%.c\n\
+			\t@mkdir -p $(dir $@)\n\
 			\t$(CC) $(CFLAGS) -c $< -o $@\n\
-			\n\
-			# Clean build artifacts\n\
-			clean:\n\
-			\t@rm -rf $(BUILD_DIR) $(BIN_DIR)\n\
-			\t@echo \"Cleaned build artifacts\"\n\
-			\n\
-			# Run the program\n\
-			run: $(TARGET)\n\
-			\t./$(TARGET)\n\
-			\n\
-			# Rebuild\n\
-			rebuild: clean all\n\
-			\n\
-			.PHONY: all clean run rebuild\n",
-			self.cc(),
-			self.extension(),
-			self.extension(),
-			project_name,
-			self.extension()
+			\n",
 		)
-	}
+	} else {
+		(String::new(), "", "", "")
+	};
+	format!(
+		"# Compilers and flags\n\
+		{c_compiler}\
+		FC = {fc}\n\
+		FFLAGS = -Wall -Wextra -Werror -O2 -g -J$(MOD_DIR)\n\
+		LDFLAGS ={ldflags}\n\
+		\n\
+		# Directories\n\
+		SRC_DIR = src\n\
+		BUILD_DIR = build\n\
+		BIN_DIR = bin\n\
+		MOD_DIR = $(BUILD_DIR)/modules\n\
+		\n\
+		# Source files\n\
+		{c_sources}\
+		F_SRCS = $(shell find $(SRC_DIR) -name '*.f90')\n\
+		OBJS = $(patsubst $(SRC_DIR)/%,$(BUILD_DIR)/%.o,{c_objects}$(F_SRCS))\n\
+		\n\
+		# Fortran sources that define modules, compiled before the sources that use them\n\
+		MOD_SRCS = $(shell grep -liE '^[[:space:]]*module[[:space:]]+[a-z_][a-z0-9_]*[[:space:]]*(!.*)?$$' $(F_SRCS) /dev/null)\n\
+		MOD_OBJS = $(patsubst $(SRC_DIR)/%,$(BUILD_DIR)/%.o,$(MOD_SRCS))\n\
+		\n\
+		# Target executable\n\
+		TARGET = $(BIN_DIR)/{name}\n\
+		\n\
+		# Default target\n\
+		all: $(TARGET)\n\
+		\n\
+		# Build target\n\
+		$(TARGET): $(OBJS)\n\
+		\t@mkdir -p $(BUILD_DIR) $(BIN_DIR)\n\
+		\t$(FC) $(FFLAGS) -o $@ $^ $(LDFLAGS)\n\
+		\t@echo \"Build complete: $(TARGET)\"\n\
+		\n\
+		# Compile source files\n\
+		$(filter-out $(MOD_OBJS),$(OBJS)): | $(MOD_OBJS)\n\
+		\n\
+		{c_rule}\
+		$(BUILD_DIR)/%.f90.o: $(SRC_DIR)/%.f90\n\
+		\t@mkdir -p $(dir $@) $(MOD_DIR)\n\
+		\t$(FC) $(FFLAGS) -c $< -o $@\n\
+		\n\
+		# Clean build artifacts\n\
+		clean:\n\
+		\t@rm -rf $(BUILD_DIR) $(BIN_DIR)\n\
+		\t@echo \"Cleaned build artifacts\"\n\
+		\n\
+		# Run the program\n\
+		run: $(TARGET)\n\
+		\t./$(TARGET)\n\
+		\n\
+		# Rebuild\n\
+		rebuild: clean all\n\
+		\n\
+		.PHONY: all clean run rebuild\n",
+		c_compiler = c_compiler,
+		fc = profile.compiler(Language::Fortran).command_line(),
+		ldflags = ldflags,
+		c_sources = c_sources,
+		c_objects = c_objects,
+		name = project_name,
+		c_rule = c_rule
+	)
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Language {
 	C,
 	Cpp,
+	Mixed,
+	ObjC,
+	ObjCpp,
+	Fortran,
+	CFortran,
 }
 
 impl std::fmt::Display for Language {
@@ -74,6 +302,11 @@ impl std::fmt::Display for Language {
 		match self {
 			Language::C => write!(f, "C"),
 			Language::Cpp => write!(f, "C++"),
+			Language::Mixed => write!(f, "C/C++/ASM"),
+			Language::ObjC => write!(f, "Objective-C"),
+			Language::ObjCpp => write!(f, "Objective-C++"),
+			Language::Fortran => write!(f, "Fortran"),
+			Language::CFortran => write!(f, "C/Fortran"),
 		}
 	}
 }
@@ -81,21 +314,57 @@ impl std::fmt::Display for Language {
 impl LanguageConsts for Language {
 	fn cc(&self) -> &'static str {
 		match self {
-			Language::C => "gcc",
-			Language::Cpp => "g++",
+			Language::C | Language::ObjC => "gcc",
+			Language::Cpp | Language::Mixed | Language::ObjCpp => "g++",
+			Language::Fortran | Language::CFortran => "gfortran",
 		}
 	}
 
 	fn extension(&self) -> &'static str {
 		match self {
-			Language::C => "c",
+			Language::C | Language::Mixed | Language::CFortran => "c",
 			Language::Cpp => "cpp",
+			Language::ObjC => "m",
+			Language::ObjCpp => "mm",
+			Language::Fortran => "f90",
+		}
+	}
+
+	fn generate_makefile_content_with_profile(
+		&self,
+		project_name: &str,
+		profile: &BuildProfile,
+	) -> String {
+		match self {
+			Language::Mixed => return mixed_makefile_template(profile, project_name),
+			Language::Fortran => return fortran_makefile_template(profile, project_name, false),
+			Language::CFortran => return fortran_makefile_template(profile, project_name, true),
+			_ => {}
 		}
+		let (cflags, ldflags) = match self {
+			Language::ObjC | Language::ObjCpp => (
+				GNUSTEP_CFLAGS,
+				format!("{} {}", GNUSTEP_LDFLAGS, profile.ldflags()),
+			),
+			_ => (CFLAGS, profile.ldflags().to_string()),
+		};
+		let extension = [self.extension()];
+		let extensions = match self {
+			Language::Cpp => CPP_EXTENSIONS,
+			_ => &extension,
+		};
+		makefile_template(
+			&profile.compiler(*self).command_line(),
+			cflags,
+			ldflags.trim(),
+			extensions,
+			project_name,
+		)
 	}
 
 	fn generate_helloworld_content(&self) -> String {
 		match self {
-			Language::C => String::from(
+			Language::C | Language::Mixed => String::from(
 				"#include <stdio.h>\n\n\
                  int main() {\n\
                  \tprintf(\"Hello, World!\\n\");\n\
@@ -107,6 +376,39 @@ impl LanguageConsts for Language {
                  int main() {\n\
                  \tstd::cout << \"Hello, World!\" << std::endl;\n\
                  \treturn 0;\n\
+                 }\n",
+			),
+			Language::ObjC => String::from(
+				"#import <Foundation/Foundation.h>\n\n\
+                 int main(void) {\n\
+                 \tNSAutoreleasePool *pool = [[NSAutoreleasePool alloc] init];\n\
+                 \tNSLog(@\"Hello, World!\");\n\
+                 \t[pool drain];\n\
+                 \treturn 0;\n\
+                 }\n",
+			),
+			Language::ObjCpp => String::from(
+				"#import <Foundation/Foundation.h>\n\
+                 #include <iostream>\n\n\
+                 int main() {\n\
+                 \tNSAutoreleasePool *pool = [[NSAutoreleasePool alloc] init];\n\
+                 \tNSString *greeting = @\"Hello, World!\";\n\
+                 \tstd::cout << [greeting UTF8String] << std::endl;\n\
+                 \t[pool drain];\n\
+                 \treturn 0;\n\
+                 }\n",
+			),
+			Language::Fortran => String::from(
+				"program main\n\
+                 \x20   implicit none\n\n\
+                 \x20   print '(a)', 'Hello, World!'\n\
+                 end program main\n",
+			),
+			Language::CFortran => String::from(
+				"void greet(void);\n\n\
+                 int main(void) {\n\
+                 \tgreet();\n\
+                 \treturn 0;\n\
                  }\n",
 			),
 		}
@@ -120,34 +422,78 @@ impl FromStr for Language {
 		match input.to_lowercase().as_str() {
 			"c" => Ok(Language::C),
 			"cpp" => Ok(Language::Cpp),
-			_ => anyhow::bail!("Unsupported language: {}. Use 'c' or 'cpp'", input),
+			"mixed" => Ok(Language::Mixed),
+			"objc" => Ok(Language::ObjC),
+			"objcpp" => Ok(Language::ObjCpp),
+			"fortran" => Ok(Language::Fortran),
+			"c-fortran" => Ok(Language::CFortran),
+			_ => anyhow::bail!(
+				"Unsupported language: {}. Use 'c', 'cpp', 'mixed', 'objc', 'objcpp', 'fortran' or 'c-fortran'",
+				input
+			),
 		}
 	}
 }
 
 impl Language {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Language::C => "c",
+			Language::Cpp => "cpp",
+			Language::Mixed => "mixed",
+			Language::ObjC => "objc",
+			Language::ObjCpp => "objcpp",
+			Language::Fortran => "fortran",
+			Language::CFortran => "c-fortran",
+		}
+	}
+
 	pub fn from_project_structure() -> Result<Language, anyhow::Error> {
 		Self::from_project_structure_with_prompt(true)
 	}
 
+	pub fn from_extension(extension: &str) -> Option<Language> {
+		match extension {
+			"c" => Some(Language::C),
+			"cpp" | "cc" | "cxx" | "C" | "c++" => Some(Language::Cpp),
+			"m" => Some(Language::ObjC),
+			"mm" => Some(Language::ObjCpp),
+			"f90" | "f95" | "f03" | "f08" | "F90" => Some(Language::Fortran),
+			_ => None,
+		}
+	}
+
+	pub fn companion_sources(&self) -> Vec<(&'static str, String)> {
+		match self {
+			Language::CFortran => vec![(
+				"greet.f90",
+				String::from(
+					"subroutine greet() bind(c, name='greet')\n\
+                     \x20   implicit none\n\n\
+                     \x20   print '(a)', 'Hello, World!'\n\
+                     end subroutine greet\n",
+				),
+			)],
+			_ => Vec::new(),
+		}
+	}
+
+	pub fn is_assembly(extension: &str) -> bool {
+		matches!(extension, "S" | "s")
+	}
+
+	pub fn detect() -> Result<Option<Language>, anyhow::Error> {
+		if let Some(language) = ProjectConfig::load()?.and_then(|config| config.language) {
+			return Ok(Some(language));
+		}
+		Ok(SourceCounts::scan(Path::new("src"))?.primary())
+	}
+
 	pub fn from_project_structure_with_prompt(
 		interactive: bool,
 	) -> Result<Language, anyhow::Error> {
-		if Path::new("src").exists() {
-			let entries = fs::read_dir("src").context("Failed to read src directory")?;
-
-			for entry in entries {
-				let entry = entry.context("Failed to read directory entry")?;
-				let path = entry.path();
-
-				if let Some(ext) = path.extension() {
-					match ext.to_str() {
-						Some("cpp") | Some("cc") | Some("cxx") => return Ok(Language::Cpp),
-						Some("c") => return Ok(Language::C),
-						_ => continue,
-					}
-				}
-			}
+		if let Some(language) = Self::detect()? {
+			return Ok(language);
 		}
 
 		if !interactive {
@@ -156,29 +502,37 @@ impl Language {
 
 		println!("⚠️  No source files found to detect language.");
 		println!("   Please select the target language:");
-		println!("   [1] C");
-		println!("   [2] C++");
-		print!("   Choice (1-2): ");
+		for (index, language) in PROMPT_LANGUAGES.iter().enumerate() {
+			println!("   [{}] {}", index + 1, language);
+		}
+		print!("   Choice (1-{}): ", PROMPT_LANGUAGES.len());
 
 		use std::io::{self, Write};
 		io::stdout().flush().unwrap();
 
 		let mut input = String::new();
 		match io::stdin().read_line(&mut input) {
-			Ok(_) => match input.trim() {
-				"1" | "c" | "C" => {
-					println!("✓ Selected C language");
-					Ok(Language::C)
-				}
-				"2" | "cpp" | "C++" | "c++" => {
-					println!("✓ Selected C++ language");
-					Ok(Language::Cpp)
-				}
-				_ => {
-					println!("Invalid choice. Defaulting to C.");
-					Ok(Language::C)
+			Ok(_) => {
+				let choice = input.trim();
+				let selected = match choice.parse::<usize>() {
+					Ok(number) => number
+						.checked_sub(1)
+						.and_then(|index| PROMPT_LANGUAGES.get(index))
+						.copied(),
+					Err(_) if choice == "C++" || choice == "c++" => Some(Language::Cpp),
+					Err(_) => choice.parse::<Language>().ok(),
+				};
+				match selected {
+					Some(language) => {
+						println!("✓ Selected {}", language);
+						Ok(language)
+					}
+					None => {
+						println!("Invalid choice. Defaulting to C.");
+						Ok(Language::C)
+					}
 				}
-			},
+			}
 			Err(_) => {
 				println!("Failed to read input. Defaulting to C.");
 				Ok(Language::C)
@@ -186,3 +540,72 @@ impl Language {
 		}
 	}
 }
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SourceCounts {
+	pub c: usize,
+	pub cpp: usize,
+	pub asm: usize,
+	pub objc: usize,
+	pub objcpp: usize,
+	pub fortran: usize,
+}
+
+impl SourceCounts {
+	pub fn scan(dir: &Path) -> Result<SourceCounts, anyhow::Error> {
+		let mut counts = SourceCounts::default();
+		if dir.is_dir() {
+			counts.visit(dir)?;
+		}
+		Ok(counts)
+	}
+
+	fn visit(&mut self, dir: &Path) -> Result<(), anyhow::Error> {
+		let entries =
+			fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+		for entry in entries {
+			let path = entry.context("Failed to read directory entry")?.path();
+			if path.is_dir() {
+				self.visit(&path)?;
+				continue;
+			}
+			let extension = path
+				.extension()
+				.and_then(|ext| ext.to_str())
+				.unwrap_or_default();
+			if Language::is_assembly(extension) {
+				self.asm += 1;
+				continue;
+			}
+			match Language::from_extension(extension) {
+				Some(Language::C) => self.c += 1,
+				Some(Language::Cpp) => self.cpp += 1,
+				Some(Language::ObjC) => self.objc += 1,
+				Some(Language::ObjCpp) => self.objcpp += 1,
+				Some(Language::Fortran) => self.fortran += 1,
+				_ => {}
+			}
+		}
+		Ok(())
+	}
+
+	pub fn primary(&self) -> Option<Language> {
+		if self.asm > 0 {
+			Some(Language::Mixed)
+		} else if self.fortran > 0 && self.c > 0 {
+			Some(Language::CFortran)
+		} else if self.fortran > 0 {
+			Some(Language::Fortran)
+		} else if self.objcpp > 0 {
+			Some(Language::ObjCpp)
+		} else if self.objc > 0 {
+			Some(Language::ObjC)
+		} else if self.cpp > 0 {
+			Some(Language::Cpp)
+		} else if self.c > 0 {
+			Some(Language::C)
+		} else {
+			None
+		}
+	}
+}
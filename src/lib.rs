@@ -1,30 +1,51 @@
 pub mod build_systems;
+pub mod builder;
+pub mod classes;
+pub mod cmake_editor;
+pub mod conan;
+pub mod config;
 pub mod constants;
 pub mod dependencies;
+pub mod dependency_check;
 pub mod features;
+pub mod fetch_content;
 mod file_handler;
 pub mod interactive;
+pub mod json;
 pub mod languages;
+pub mod lockfile;
+pub mod makefile_editor;
 pub mod package_managers;
+pub mod project_templates;
+pub mod search;
 pub mod sources;
+pub mod system_packages;
 pub mod templates;
 pub mod updater;
+pub mod vcpkg;
+pub mod vendor;
 
 pub use build_systems::{
-	get_generator, BuildSystem, BuildSystemGenerator, CMakeGenerator, MakefileGenerator,
+	get_generator, BuildProfile, BuildSystem, BuildSystemGenerator, CMakeGenerator, Libc,
+	MakefileGenerator,
 };
-pub use dependencies::{add_dependencies, remove_dependencies};
+pub use builder::build_project;
+pub use config::ProjectConfig;
+pub use dependencies::{add_dependencies, declared_dependencies, remove_dependencies};
 pub use features::{
 	add_package_manager_to_project, convert_build_system, convert_build_system_interactive,
 	detect_build_system, detect_package_manager, list_features,
 	remove_package_manager_from_project,
 };
+pub use fetch_content::add_git_dependency;
 pub use file_handler::create_dir;
 pub use languages::{Language, LanguageConsts};
 pub use package_managers::{
-	get_package_manager_generator, PackageManager, PackageManagerGenerator,
+	get_package_manager_generator, integrate_package_manager, PackageManager,
+	PackageManagerGenerator, PackageSpec,
 };
-pub use sources::add_sources;
+pub use sources::{add_sources, add_sources_with_visibility};
+pub use system_packages::HostPackageManager;
 pub use templates::*;
 pub use updater::update_project;
 
@@ -41,7 +62,13 @@ pub fn create_project_with_system(
 	language: Language,
 	build_system: BuildSystem,
 ) -> Result<()> {
-	create_project_with_full_config(project_name, language, build_system, None)
+	create_project_with_full_config(
+		project_name,
+		language,
+		build_system,
+		None,
+		BuildProfile::default(),
+	)
 }
 
 pub fn create_project_with_system_and_pm(
@@ -50,7 +77,13 @@ pub fn create_project_with_system_and_pm(
 	build_system: BuildSystem,
 	package_manager: PackageManager,
 ) -> Result<()> {
-	create_project_with_full_config(project_name, language, build_system, Some(package_manager))
+	create_project_with_full_config(
+		project_name,
+		language,
+		build_system,
+		Some(package_manager),
+		BuildProfile::default(),
+	)
 }
 
 fn create_project_with_full_config(
@@ -58,10 +91,12 @@ fn create_project_with_full_config(
 	language: Language,
 	build_system: BuildSystem,
 	package_manager: Option<PackageManager>,
+	profile: BuildProfile,
 ) -> Result<()> {
 	let hello_world_content = language.generate_helloworld_content();
 	let generator = get_generator(build_system);
-	let build_file_content = generator.generate_build_file(language, project_name);
+	let build_file_content =
+		generator.generate_build_file_with_profile(language, project_name, &profile);
 
 	fs::create_dir_all("src").context("Failed to create src directory")?;
 
@@ -70,9 +105,15 @@ fn create_project_with_full_config(
 		hello_world_content,
 	)
 	.context("Failed to write hello world file")?;
+	for (file_name, content) in language.companion_sources() {
+		fs::write(format!("src/{}", file_name), content)
+			.with_context(|| format!("Failed to write src/{}", file_name))?;
+	}
 
 	fs::write(generator.extension(), build_file_content).context("Failed to write build file")?;
 
+	ProjectConfig::new(project_name, language, profile).save()?;
+
 	fs::write(".gitignore", templates::generate_gitignore(language))
 		.context("Failed to write .gitignore")?;
 
@@ -123,6 +164,7 @@ fn create_project_with_full_config(
 		let manifest = pm_generator.generate_manifest(project_name);
 		fs::write(pm_generator.extension(), manifest)
 			.with_context(|| format!("Failed to write {} manifest", pm_generator.name()))?;
+		integrate_package_manager(pm, build_system)?;
 		println!("📦 Generated {} configuration", pm);
 	}
 
@@ -141,6 +183,13 @@ fn create_project_with_full_config(
 		language, project_name, build_system
 	);
 
+	if profile.is_static() {
+		println!(
+			"🔗 Static linking enabled ({}); verify with 'sticks build'",
+			profile.libc
+		);
+	}
+
 	Ok(())
 }
 
@@ -158,12 +207,30 @@ pub fn new_project_with_system(
 	Ok(())
 }
 
+pub fn new_project_with_profile(
+	project_name: &str,
+	language: Language,
+	build_system: BuildSystem,
+	package_manager: Option<PackageManager>,
+	profile: BuildProfile,
+) -> Result<()> {
+	profile.check_toolchain(language)?;
+	create_dir(project_name)?;
+	create_project_with_full_config(
+		project_name,
+		language,
+		build_system,
+		package_manager,
+		profile,
+	)
+}
+
 pub fn init_project(language: Language) -> Result<()> {
 	init_project_with_system(language, BuildSystem::Makefile)
 }
 
 pub fn init_project_with_system(language: Language, build_system: BuildSystem) -> Result<()> {
-	init_project_with_system_and_pm_internal(language, build_system, None)
+	init_project_with_profile(language, build_system, None, BuildProfile::default())
 }
 
 pub fn init_project_with_system_and_pm(
@@ -171,21 +238,34 @@ pub fn init_project_with_system_and_pm(
 	build_system: BuildSystem,
 	package_manager: PackageManager,
 ) -> Result<()> {
-	init_project_with_system_and_pm_internal(language, build_system, Some(package_manager))
+	init_project_with_profile(
+		language,
+		build_system,
+		Some(package_manager),
+		BuildProfile::default(),
+	)
 }
 
-fn init_project_with_system_and_pm_internal(
+pub fn init_project_with_profile(
 	language: Language,
 	build_system: BuildSystem,
 	package_manager: Option<PackageManager>,
+	profile: BuildProfile,
 ) -> Result<()> {
+	profile.check_toolchain(language)?;
 	let current_dir = std::env::current_dir().context("Failed to get current directory")?;
 	let current_dir_name = current_dir
 		.file_name()
 		.context("Failed to get directory name")?
 		.to_str()
 		.context("Failed to convert directory name to string")?;
-	create_project_with_full_config(current_dir_name, language, build_system, package_manager)?;
+	create_project_with_full_config(
+		current_dir_name,
+		language,
+		build_system,
+		package_manager,
+		profile,
+	)?;
 	println!("✓ Initialized {} project in current directory", language);
 	Ok(())
 }
@@ -0,0 +1,379 @@
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::config::{ConfigFile, ConfigValue};
+use crate::constants::{cmake, lockfile, scripts, vcpkg, vendor};
+use crate::dependencies::declared_dependencies;
+use crate::features::{detect_build_system, detect_package_manager};
+use crate::fetch_content::git_dependencies;
+use crate::json::JsonValue;
+use crate::package_managers::{get_package_manager_generator, PackageManager};
+use crate::system_packages::{translate_package, HostPackageManager};
+use crate::vendor::vendored_dependencies;
+use crate::BuildSystem;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Lockfile {
+	sections: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Lockfile {
+	pub fn parse(content: &str) -> Result<Self> {
+		let file = ConfigFile::parse(content)?;
+		let mut lock = Lockfile::default();
+		for (section, entries) in file.sections() {
+			for (name, value) in entries {
+				let version = value.as_str().with_context(|| {
+					format!("Expected a version string for {} in [{}]", name, section)
+				})?;
+				lock.set(section, name, version);
+			}
+		}
+		Ok(lock)
+	}
+
+	pub fn render(&self) -> String {
+		let mut file = ConfigFile::default();
+		for (section, entries) in &self.sections {
+			for (name, version) in entries {
+				file.set(section, name, ConfigValue::String(version.clone()));
+			}
+		}
+		format!("{}\n\n{}", lockfile::HEADER, file.render())
+	}
+
+	pub fn get(&self, section: &str, name: &str) -> Option<&str> {
+		self.sections
+			.get(section)
+			.and_then(|entries| entries.get(name))
+			.map(String::as_str)
+	}
+
+	pub fn set(&mut self, section: &str, name: &str, version: &str) {
+		self.sections
+			.entry(section.to_string())
+			.or_default()
+			.insert(name.to_string(), version.to_string());
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.sections.values().all(BTreeMap::is_empty)
+	}
+
+	pub fn load() -> Result<Option<Self>> {
+		if !Path::new(lockfile::FILENAME).exists() {
+			return Ok(None);
+		}
+		let content = fs::read_to_string(lockfile::FILENAME)
+			.with_context(|| format!("Failed to read {}", lockfile::FILENAME))?;
+		Lockfile::parse(&content)
+			.with_context(|| format!("Failed to parse {}", lockfile::FILENAME))
+			.map(Some)
+	}
+
+	pub fn save(&self) -> Result<()> {
+		fs::write(lockfile::FILENAME, self.render())
+			.with_context(|| format!("Failed to write {}", lockfile::FILENAME))
+	}
+
+	pub fn resolve() -> Result<Self> {
+		let mut lock = Lockfile::default();
+		resolve_system(&mut lock)?;
+		match detect_package_manager()? {
+			Some(PackageManager::Conan | PackageManager::ConanRecipe) => resolve_conan(&mut lock)?,
+			Some(PackageManager::Vcpkg) => resolve_vcpkg(&mut lock)?,
+			None => {}
+		}
+		resolve_git(&mut lock)?;
+		Ok(lock)
+	}
+
+	pub fn diff(&self, current: &Lockfile) -> Vec<String> {
+		let mut divergences = Vec::new();
+		let sections: BTreeSet<&String> = self
+			.sections
+			.keys()
+			.chain(current.sections.keys())
+			.collect();
+
+		for section in sections {
+			let locked = self.sections.get(section);
+			let found = current.sections.get(section);
+			let names: BTreeSet<&String> = locked
+				.into_iter()
+				.chain(found)
+				.flat_map(BTreeMap::keys)
+				.collect();
+
+			for name in names {
+				let locked = locked.and_then(|entries| entries.get(name));
+				let found = found.and_then(|entries| entries.get(name));
+				match (locked, found) {
+					(Some(locked), Some(found)) if locked != found => divergences.push(format!(
+						"[{}] {}: locked at {} but found {}",
+						section, name, locked, found
+					)),
+					(Some(locked), None) => divergences.push(format!(
+						"[{}] {}: locked at {} but missing on this host",
+						section, name, locked
+					)),
+					(None, Some(found)) => divergences.push(format!(
+						"[{}] {}: {} is not recorded in {}",
+						section,
+						name,
+						found,
+						lockfile::FILENAME
+					)),
+					_ => {}
+				}
+			}
+		}
+		divergences
+	}
+}
+
+pub fn update_lockfile() -> Result<Lockfile> {
+	let lock = Lockfile::resolve()?;
+	if lock.is_empty() && Lockfile::load()?.is_none() {
+		return Ok(lock);
+	}
+	lock.save()?;
+	println!("🔒 Updated {}", lockfile::FILENAME);
+	Ok(lock)
+}
+
+pub fn install_dependencies(locked: bool) -> Result<()> {
+	let expected = if locked {
+		Some(Lockfile::load()?.with_context(|| {
+			format!(
+				"No {} found; run 'sticks install' without --locked to create it",
+				lockfile::FILENAME
+			)
+		})?)
+	} else {
+		None
+	};
+
+	install_system_dependencies()?;
+	if let Some(pm) = detect_package_manager()? {
+		install_package_manager_dependencies(pm, locked)?;
+	}
+	if Path::new(vendor::GITMODULES).exists() {
+		run(&["git", "submodule", "update", "--init"])?;
+	}
+
+	let expected = match expected {
+		Some(expected) => expected,
+		None => {
+			update_lockfile()?;
+			return Ok(());
+		}
+	};
+
+	let divergences = expected.diff(&Lockfile::resolve()?);
+	if !divergences.is_empty() {
+		anyhow::bail!(
+			"This host diverges from {}:\n  - {}\nRun 'sticks install' without --locked to accept the new versions",
+			lockfile::FILENAME,
+			divergences.join("\n  - ")
+		);
+	}
+	println!("✓ Dependencies match {}", lockfile::FILENAME);
+	Ok(())
+}
+
+fn install_system_dependencies() -> Result<()> {
+	let deps = declared_dependencies()?;
+	if deps.is_empty() {
+		return Ok(());
+	}
+	let missing = match HostPackageManager::detect() {
+		Some(pm) => deps.iter().any(|dep| {
+			translate_package(dep, pm)
+				.is_some_and(|package| pm.installed_version(&package).is_none())
+		}),
+		None => true,
+	};
+	if !missing {
+		println!("✓ System dependencies are already installed");
+		return Ok(());
+	}
+
+	let mut command = vec!["sh", scripts::INSTALL_DEPS];
+	command.extend(deps.iter().map(String::as_str));
+	run(&command)
+}
+
+fn install_package_manager_dependencies(pm: PackageManager, locked: bool) -> Result<()> {
+	let generator = get_package_manager_generator(pm);
+	let command: Vec<&str> = generator.install_command().split_whitespace().collect();
+	run(&command)?;
+
+	if matches!(pm, PackageManager::Conan | PackageManager::ConanRecipe)
+		&& !locked
+		&& !Path::new(lockfile::CONAN_LOCK).exists()
+	{
+		run(&["conan", "lock", "create", "."])?;
+	}
+	Ok(())
+}
+
+fn run(command: &[&str]) -> Result<()> {
+	let (program, args) = command.split_first().context("Empty command")?;
+	let status = Command::new(program)
+		.args(args)
+		.status()
+		.with_context(|| format!("Failed to run {}. Is it installed?", program))?;
+	if !status.success() {
+		anyhow::bail!("'{}' failed", command.join(" "));
+	}
+	Ok(())
+}
+
+fn resolve_system(lock: &mut Lockfile) -> Result<()> {
+	let deps = declared_dependencies()?;
+	if deps.is_empty() {
+		return Ok(());
+	}
+	let pm = match HostPackageManager::detect() {
+		Some(pm) => pm,
+		None => return Ok(()),
+	};
+
+	lock.set(lockfile::HOST, "package_manager", pm.as_str());
+	for dep in deps {
+		if let Some(version) = translate_package(&dep, pm).and_then(|p| pm.installed_version(&p)) {
+			lock.set(lockfile::SYSTEM, &dep, &version);
+		}
+	}
+	Ok(())
+}
+
+fn resolve_conan(lock: &mut Lockfile) -> Result<()> {
+	if Path::new(lockfile::CONAN_LOCK).exists() {
+		let content = fs::read_to_string(lockfile::CONAN_LOCK)
+			.with_context(|| format!("Failed to read {}", lockfile::CONAN_LOCK))?;
+		let root = JsonValue::parse(&content)
+			.with_context(|| format!("Failed to parse {}", lockfile::CONAN_LOCK))?;
+		for reference in root
+			.get("requires")
+			.and_then(JsonValue::as_array)
+			.unwrap_or_default()
+			.iter()
+			.filter_map(JsonValue::as_str)
+		{
+			let reference = reference.split('%').next().unwrap_or(reference);
+			if let Some((name, version)) = reference.split_once('/') {
+				lock.set(lockfile::CONAN, name, version);
+			}
+		}
+		return Ok(());
+	}
+
+	let pm = detect_package_manager()?.context("No Conan manifest found")?;
+	let generator = get_package_manager_generator(pm);
+	let manifest = fs::read_to_string(generator.extension())
+		.with_context(|| format!("Failed to read {}", generator.extension()))?;
+	for spec in generator.dependencies(&manifest)? {
+		if let Some(version) = spec.version {
+			lock.set(lockfile::CONAN, &spec.name, &version);
+		}
+	}
+	Ok(())
+}
+
+fn resolve_vcpkg(lock: &mut Lockfile) -> Result<()> {
+	if let Some(baseline) = vcpkg_baseline()? {
+		lock.set(lockfile::VCPKG, "baseline", &baseline);
+	}
+
+	let status = match lockfile::VCPKG_STATUS
+		.iter()
+		.find_map(|path| fs::read_to_string(path).ok())
+	{
+		Some(status) => status,
+		None => return Ok(()),
+	};
+	let manifest = fs::read_to_string(vcpkg::MANIFEST)
+		.with_context(|| format!("Failed to read {}", vcpkg::MANIFEST))?;
+	let declared: Vec<String> = get_package_manager_generator(PackageManager::Vcpkg)
+		.dependencies(&manifest)?
+		.into_iter()
+		.map(|spec| spec.name)
+		.collect();
+
+	for paragraph in status.split("\n\n") {
+		let field = |key: &str| {
+			paragraph
+				.lines()
+				.find_map(|line| line.strip_prefix(key))
+				.map(str::trim)
+		};
+		let (name, version) = match (field("Package:"), field("Version:")) {
+			(Some(name), Some(version)) => (name, version),
+			_ => continue,
+		};
+		let installed = field("Status:").is_some_and(|s| s.ends_with(" installed"));
+		if installed && field("Feature:").is_none() && declared.iter().any(|d| d == name) {
+			let version = match field("Port-Version:").filter(|v| *v != "0") {
+				Some(port) => format!("{}#{}", version, port),
+				None => version.to_string(),
+			};
+			lock.set(lockfile::VCPKG, name, &version);
+		}
+	}
+	Ok(())
+}
+
+fn vcpkg_baseline() -> Result<Option<String>> {
+	for (file, path) in [
+		(
+			vcpkg::CONFIGURATION,
+			["default-registry", "baseline"].as_slice(),
+		),
+		(vcpkg::MANIFEST, ["builtin-baseline"].as_slice()),
+	] {
+		let content = match fs::read_to_string(file) {
+			Ok(content) => content,
+			Err(_) => continue,
+		};
+		let root =
+			JsonValue::parse(&content).with_context(|| format!("Failed to parse {}", file))?;
+		let baseline = path
+			.iter()
+			.try_fold(&root, |value, key| value.get(key))
+			.and_then(JsonValue::as_str);
+		if let Some(baseline) = baseline {
+			return Ok(Some(baseline.to_string()));
+		}
+	}
+	Ok(None)
+}
+
+fn resolve_git(lock: &mut Lockfile) -> Result<()> {
+	if detect_build_system()? == Some(BuildSystem::CMake) {
+		let content = fs::read_to_string(cmake::FILENAME)
+			.with_context(|| format!("Failed to read {}", cmake::FILENAME))?;
+		for dependency in git_dependencies(&content) {
+			lock.set(lockfile::GIT, &dependency.name, &dependency.git_ref);
+		}
+	}
+
+	for dependency in vendored_dependencies()? {
+		if !Path::new(&dependency.path).join(".git").exists() {
+			continue;
+		}
+		let output = Command::new("git")
+			.args(["-C", &dependency.path, "rev-parse", "HEAD"])
+			.output()
+			.context("Failed to run git. Is it installed?")?;
+		if output.status.success() {
+			let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+			lock.set(lockfile::VENDOR, &dependency.path, &commit);
+		}
+	}
+	Ok(())
+}
@@ -1,7 +1,9 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 use std::env;
-use sticks::{add_dependencies, add_sources, remove_dependencies, update_project, Language};
+use sticks::{
+	add_dependencies, add_sources_with_visibility, remove_dependencies, update_project, Language,
+};
 
 #[derive(Parser)]
 #[command(name = "sticks")]
@@ -16,7 +18,7 @@ struct Cli {
 enum Commands {
 	#[command(about = "Create a new C project in a subdirectory")]
 	#[command(
-		after_help = "Examples:\n  sticks c myproject            # Create C project with Makefile\n  sticks c myproject --build cmake  # Create C project with CMake\n  sticks c myproject -p conan   # Create C project with Conan support"
+		after_help = "Examples:\n  sticks c myproject            # Create C project with Makefile\n  sticks c myproject --build cmake  # Create C project with CMake\n  sticks c myproject -p conan   # Create C project with Conan support\n  sticks c myproject --libc musl  # Create fully static C project linked against musl"
 	)]
 	C {
 		project_name: Vec<String>,
@@ -27,12 +29,23 @@ enum Commands {
 			help = "Build system: 'makefile' or 'cmake'"
 		)]
 		build: String,
-		#[arg(long, short = 'p', help = "Package manager: 'conan' or 'vcpkg'")]
+		#[arg(
+			long,
+			short = 'p',
+			help = "Package manager: 'conan', 'conan-py' (Conan 2 recipe) or 'vcpkg'"
+		)]
 		package_manager: Option<String>,
+		#[arg(long = "static", help = "Link the executable fully statically")]
+		static_link: bool,
+		#[arg(
+			long,
+			help = "C library to link against: 'glibc' or 'musl' (musl implies --static)"
+		)]
+		libc: Option<String>,
 	},
 	#[command(about = "Create a new C++ project in a subdirectory")]
 	#[command(
-		after_help = "Examples:\n  sticks cpp myproject          # Create C++ project with Makefile\n  sticks cpp myproject --build cmake  # Create C++ project with CMake\n  sticks cpp myproject -p vcpkg # Create C++ project with vcpkg support"
+		after_help = "Examples:\n  sticks cpp myproject          # Create C++ project with Makefile\n  sticks cpp myproject --build cmake  # Create C++ project with CMake\n  sticks cpp myproject -p vcpkg # Create C++ project with vcpkg support\n  sticks cpp myproject --static # Create C++ project linked fully statically"
 	)]
 	Cpp {
 		project_name: Vec<String>,
@@ -43,16 +56,27 @@ enum Commands {
 			help = "Build system: 'makefile' or 'cmake'"
 		)]
 		build: String,
-		#[arg(long, short = 'p', help = "Package manager: 'conan' or 'vcpkg'")]
+		#[arg(
+			long,
+			short = 'p',
+			help = "Package manager: 'conan', 'conan-py' (Conan 2 recipe) or 'vcpkg'"
+		)]
 		package_manager: Option<String>,
+		#[arg(long = "static", help = "Link the executable fully statically")]
+		static_link: bool,
+		#[arg(
+			long,
+			help = "C library to link against: 'glibc' or 'musl' (musl implies --static)"
+		)]
+		libc: Option<String>,
 	},
 	#[command(about = "Initialize a project in the current directory")]
 	#[command(
-		after_help = "Examples:\n  sticks init c                 # Initialize C project\n  sticks i cpp --build cmake    # Initialize C++ project with CMake\n  sticks i c -p conan           # Initialize C project with Conan support"
+		after_help = "Examples:\n  sticks init c                 # Initialize C project\n  sticks i cpp --build cmake    # Initialize C++ project with CMake\n  sticks i c -p conan           # Initialize C project with Conan support\n  sticks init mixed             # Initialize C/C++/assembly project\n  sticks init objc              # Initialize Objective-C (GNUstep) project\n  sticks init c-fortran         # Initialize C project calling Fortran"
 	)]
 	#[command(visible_alias = "i")]
 	Init {
-		#[arg(value_parser = ["c", "cpp"])]
+		#[arg(value_parser = ["c", "cpp", "mixed", "objc", "objcpp", "fortran", "c-fortran"])]
 		language: Option<String>,
 		#[arg(
 			long,
@@ -61,27 +85,152 @@ enum Commands {
 			help = "Build system: 'makefile' or 'cmake'"
 		)]
 		build: String,
-		#[arg(long, short = 'p', help = "Package manager: 'conan' or 'vcpkg'")]
+		#[arg(
+			long,
+			short = 'p',
+			help = "Package manager: 'conan', 'conan-py' (Conan 2 recipe) or 'vcpkg'"
+		)]
 		package_manager: Option<String>,
+		#[arg(long = "static", help = "Link the executable fully statically")]
+		static_link: bool,
+		#[arg(
+			long,
+			help = "C library to link against: 'glibc' or 'musl' (musl implies --static)"
+		)]
+		libc: Option<String>,
+	},
+	#[command(about = "Create a new project from a template directory or git repository")]
+	#[command(
+		after_help = "Examples:\n  sticks new myapp --template cli          # Template from ~/.config/sticks/templates/cli\n  sticks new myapp -t ./templates/service  # Template from a local directory\n  sticks new myapp -t https://github.com/user/template.git  # Template from git\n  sticks new myapp -t cli --set author=Ada # Answer a template prompt\n  sticks new myapp -t cli --no-hooks       # Skip post_create hooks\n  sticks new myapp -t https://github.com/user/template.git --run-hooks  # Run git template hooks without asking"
+	)]
+	New {
+		project_name: String,
+		#[arg(long, short, help = "Template name, directory path or git URL")]
+		template: String,
+		#[arg(
+			long,
+			short,
+			value_parser = ["c", "cpp", "mixed", "objc", "objcpp", "fortran", "c-fortran"],
+			help = "Project language (defaults to the template's language)"
+		)]
+		language: Option<String>,
+		#[arg(
+			long = "set",
+			value_name = "KEY=VALUE",
+			help = "Set a template placeholder, skipping its prompt"
+		)]
+		values: Vec<String>,
+		#[arg(long, help = "Do not run the template's post_create hooks")]
+		no_hooks: bool,
+		#[arg(
+			long,
+			conflicts_with = "no_hooks",
+			help = "Run post_create hooks from git templates without asking"
+		)]
+		run_hooks: bool,
 	},
-	#[command(about = "Add dependencies to your project's Makefile")]
+	#[command(about = "Add dependencies to your project's build file or package manifest")]
 	#[command(
-		after_help = "Examples:\n  sticks add libcurl            # Add single dependency\n  sticks a libcurl openssl      # Add multiple dependencies\n  sticks add sqlite3 pthread    # Add libraries for C project"
+		after_help = "Examples:\n  sticks add libcurl            # Add single dependency\n  sticks a libcurl openssl      # Add multiple dependencies\n  sticks add sqlite3 pthread    # Add libraries for C project\n  sticks add fmt/10.2.1         # Add a versioned Conan/vcpkg requirement\n  sticks add fmt --git https://github.com/fmtlib/fmt --tag 10.2.1  # FetchContent (CMake)"
 	)]
 	#[command(visible_alias = "a")]
-	Add { dependency_name: Vec<String> },
-	#[command(about = "Remove dependencies from your project's Makefile")]
+	Add {
+		dependency_name: Vec<String>,
+		#[arg(
+			long,
+			help = "Fetch the dependency from a git repository with CMake FetchContent"
+		)]
+		git: Option<String>,
+		#[arg(
+			long,
+			requires = "git",
+			help = "Tag, branch or commit to pin (defaults to the remote HEAD commit)"
+		)]
+		tag: Option<String>,
+		#[arg(
+			long,
+			requires = "git",
+			help = "CMake target to link (defaults to the dependency name)"
+		)]
+		link: Option<String>,
+	},
+	#[command(about = "Remove dependencies from your project's build file or package manifest")]
 	#[command(
 		after_help = "Examples:\n  sticks remove libcurl         # Remove single dependency\n  sticks r libcurl openssl      # Remove multiple dependencies\n  sticks remove sqlite3         # Remove library from project"
 	)]
 	#[command(visible_alias = "r")]
 	Remove { dependency_name: Vec<String> },
-	#[command(about = "Add new source files to your project")]
+	#[command(about = "Inspect the project's declared dependencies")]
+	#[command(
+		after_help = "Examples:\n  sticks deps check             # Report missing packages, pkg-config files and headers\n  sticks deps audit             # Report unused and undeclared dependencies"
+	)]
+	Deps {
+		#[command(subcommand)]
+		action: DepsAction,
+	},
+	#[command(about = "Search for packages to pass to 'sticks add'")]
+	#[command(
+		after_help = "Examples:\n  sticks search ssl             # Find OpenSSL packages on every source\n  sticks search fmt             # Also lists Conan cache entries and vcpkg ports\n\nSearches sticks' known packages, the host package database, pkg-config modules,\nthe local Conan cache and $VCPKG_ROOT/ports."
+	)]
+	Search { term: String },
+	#[command(about = "Install the project's dependencies and record them in sticks.lock")]
+	#[command(
+		after_help = "Examples:\n  sticks install                # Install dependencies and update sticks.lock\n  sticks install --locked       # Fail if the host diverges from sticks.lock"
+	)]
+	Install {
+		#[arg(
+			long,
+			help = "Do not update sticks.lock; fail if installed versions differ from it"
+		)]
+		locked: bool,
+	},
+	#[command(about = "Add, remove or move source files in your project")]
+	#[command(
+		after_help = "Examples:\n  sticks src utils              # Add src/utils.c and include/<project>/utils.h\n  sticks s math parser          # Add multiple source files\n  sticks src --private helpers  # Keep the header next to the source in src/\n  sticks src rm parser          # Remove src/parser.c and its header\n  sticks src mv utils net/util  # Move the pair and rewrite #include references"
+	)]
+	#[command(visible_alias = "s", args_conflicts_with_subcommands = true)]
+	Src {
+		#[command(subcommand)]
+		action: Option<SrcAction>,
+		source_names: Vec<String>,
+		#[arg(long, help = "Put headers in src/ instead of include/<project>/")]
+		private: bool,
+	},
+	#[command(about = "Generate a C++ class header and source")]
+	#[command(
+		after_help = "Examples:\n  sticks class Parser                       # include/<project>/Parser.hpp and src/Parser.cpp\n  sticks class Socket --namespace app::net --dir net\n  sticks class Buffer --rule-of-five move-only --pragma-once\n\nRule-of-five options: default, move-only, immovable, none."
+	)]
+	Class {
+		name: String,
+		#[arg(long, help = "Namespace to declare the class in, e.g. app::net")]
+		namespace: Option<String>,
+		#[arg(long, help = "Subdirectory under src/ and include/<project>/")]
+		dir: Option<String>,
+		#[arg(
+			long,
+			default_value = "default",
+			help = "Copy/move members to declare: default, move-only, immovable or none"
+		)]
+		rule_of_five: sticks::classes::SpecialMembers,
+		#[arg(long, help = "Use #pragma once instead of include guards")]
+		pragma_once: bool,
+		#[arg(long, help = "Put the header in src/ instead of include/<project>/")]
+		private: bool,
+	},
+	#[command(about = "Build the project with its configured build system")]
 	#[command(
-		after_help = "Examples:\n  sticks src utils              # Add utils.c/.cpp\n  sticks s math parser          # Add multiple source files\n  sticks src database           # Add database.c/.cpp"
+		after_help = "Examples:\n  sticks build                  # Run make or cmake for the current project\n  sticks b                      # Same, using the short alias\n\nStatic projects (--static / --libc musl) are checked for a dynamic interpreter after building."
 	)]
-	#[command(visible_alias = "s")]
-	Src { source_names: Vec<String> },
+	#[command(visible_alias = "b")]
+	Build,
+	#[command(about = "Manage vendored dependencies (git submodules)")]
+	#[command(
+		after_help = "Examples:\n  sticks vendor add https://github.com/nothings/stb   # Vendor into third_party/stb\n  sticks vendor add <url> --path libs/json            # Vendor into a custom path\n  sticks vendor add <url> --target fmt::fmt           # Link a CMake target\n  sticks vendor update                                # Update every vendored dependency\n  sticks vendor remove stb                            # Remove submodule and build entries"
+	)]
+	Vendor {
+		#[command(subcommand)]
+		action: VendorAction,
+	},
 	#[command(about = "Update sticks to the latest version")]
 	#[command(visible_alias = "u")]
 	Update,
@@ -96,6 +245,62 @@ enum Commands {
 	},
 }
 
+#[derive(Subcommand)]
+enum DepsAction {
+	#[command(about = "Check that declared dependencies are installed on this host")]
+	#[command(
+		after_help = "Exits with a non-zero status when a package, pkg-config file or header is missing,\nso it can gate CI jobs."
+	)]
+	Check,
+	#[command(
+		about = "Find declared dependencies that are unused and used ones that are undeclared"
+	)]
+	#[command(
+		after_help = "Scans #include directives under src/ and include/ and -l flags in the build file,\nmaps headers to packages with pkg-config and the host package database, and exits\nwith a non-zero status when something is unused or undeclared."
+	)]
+	Audit,
+}
+
+#[derive(Subcommand)]
+enum SrcAction {
+	#[command(about = "Remove source files and their headers")]
+	Rm {
+		#[arg(required = true)]
+		names: Vec<String>,
+	},
+	#[command(about = "Rename or move a source file and its header")]
+	Mv { old: String, new: String },
+}
+
+#[derive(Subcommand)]
+enum VendorAction {
+	#[command(about = "Add a git repository as a submodule and build it with the project")]
+	Add {
+		url: String,
+		#[arg(
+			long,
+			help = "Where to check out the submodule (default: third_party/<name>)"
+		)]
+		path: Option<String>,
+		#[arg(
+			long,
+			help = "CMake target to link when the dependency has its own CMakeLists.txt"
+		)]
+		target: Option<String>,
+	},
+	#[command(about = "Update vendored dependencies to their latest upstream commit")]
+	Update {
+		#[arg(help = "Name or path of the dependency (all when omitted)")]
+		name: Option<String>,
+	},
+	#[command(about = "Remove a vendored dependency from git and the build files")]
+	#[command(visible_alias = "rm")]
+	Remove {
+		#[arg(help = "Name or path of the dependency")]
+		name: String,
+	},
+}
+
 #[derive(Subcommand)]
 enum FeatureAction {
 	#[command(about = "List detected project features")]
@@ -116,7 +321,7 @@ enum FeatureAction {
 	)]
 	#[command(visible_alias = "add-pm")]
 	AddPackageManager {
-		#[arg(value_parser = ["conan", "vcpkg"])]
+		#[arg(value_parser = ["conan", "conan-py", "vcpkg"])]
 		package_manager: String,
 		#[arg(help = "Project name (auto-detected if not provided)")]
 		project_name: Option<String>,
@@ -127,7 +332,7 @@ enum FeatureAction {
 	)]
 	#[command(visible_alias = "rm-pm")]
 	RemovePackageManager {
-		#[arg(value_parser = ["conan", "vcpkg"])]
+		#[arg(value_parser = ["conan", "conan-py", "vcpkg"])]
 		package_manager: String,
 	},
 }
@@ -160,92 +365,179 @@ fn run() -> Result<()> {
 			project_name,
 			build,
 			package_manager,
+			static_link,
+			libc,
 		} => {
 			validate_project_names(&project_name)?;
 			let build_system = build.parse::<sticks::BuildSystem>()?;
+			let pm = parse_package_manager(package_manager.as_deref())?;
+			let profile = parse_build_profile(static_link, libc.as_deref())?;
+			let original_dir = env::current_dir()?;
 			for name in project_name {
-				match package_manager {
-					Some(ref pm_str) => {
-						let pm = pm_str.parse::<sticks::PackageManager>()?;
-						sticks::create_project_with_system_and_pm(
-							&name,
-							Language::C,
-							build_system,
-							pm,
-						)?;
-					}
-					None => {
-						sticks::new_project_with_system(&name, Language::C, build_system)?;
-					}
-				}
+				sticks::new_project_with_profile(&name, Language::C, build_system, pm, profile)?;
+				env::set_current_dir(&original_dir)?;
 			}
 		}
 		Commands::Cpp {
 			project_name,
 			build,
 			package_manager,
+			static_link,
+			libc,
 		} => {
 			validate_project_names(&project_name)?;
 			let build_system = build.parse::<sticks::BuildSystem>()?;
+			let pm = parse_package_manager(package_manager.as_deref())?;
+			let profile = parse_build_profile(static_link, libc.as_deref())?;
+			let original_dir = env::current_dir()?;
 			for name in project_name {
-				match package_manager {
-					Some(ref pm_str) => {
-						let pm = pm_str.parse::<sticks::PackageManager>()?;
-						sticks::create_project_with_system_and_pm(
-							&name,
-							Language::Cpp,
-							build_system,
-							pm,
-						)?;
-					}
-					None => {
-						sticks::new_project_with_system(&name, Language::Cpp, build_system)?;
-					}
-				}
+				sticks::new_project_with_profile(&name, Language::Cpp, build_system, pm, profile)?;
+				env::set_current_dir(&original_dir)?;
 			}
 		}
 		Commands::Init {
 			language,
 			build,
 			package_manager,
+			static_link,
+			libc,
 		} => {
 			let lang = match language {
 				Some(l) => l.parse::<Language>()?,
 				None => sticks::interactive::select_language(),
 			};
 			let build_system = build.parse::<sticks::BuildSystem>()?;
-			match package_manager {
-				Some(pm_str) => {
-					let pm = pm_str.parse::<sticks::PackageManager>()?;
-					sticks::init_project_with_system_and_pm(lang, build_system, pm)?;
-				}
-				None => {
-					sticks::init_project_with_system(lang, build_system)?;
-				}
-			}
+			let pm = parse_package_manager(package_manager.as_deref())?;
+			let profile = parse_build_profile(static_link, libc.as_deref())?;
+			sticks::init_project_with_profile(lang, build_system, pm, profile)?;
+		}
+		Commands::New {
+			project_name,
+			template,
+			language,
+			values,
+			no_hooks,
+			run_hooks,
+		} => {
+			validate_project_names(std::slice::from_ref(&project_name))?;
+			let language = language.map(|l| l.parse::<Language>()).transpose()?;
+			let values = values
+				.iter()
+				.map(|pair| match pair.split_once('=') {
+					Some((key, value)) if !key.trim().is_empty() => {
+						Ok((key.trim().to_string(), value.to_string()))
+					}
+					_ => Err(anyhow::anyhow!(
+						"Invalid --set value '{}'. Use KEY=VALUE",
+						pair
+					)),
+				})
+				.collect::<Result<Vec<_>>>()?;
+			let options = sticks::project_templates::TemplateOptions {
+				template,
+				language,
+				values,
+				run_hooks: match (run_hooks, no_hooks) {
+					(true, _) => Some(true),
+					(_, true) => Some(false),
+					_ => None,
+				},
+			};
+			sticks::project_templates::new_project_from_template(&project_name, &options)?;
 		}
-		Commands::Add { dependency_name } => {
+		Commands::Add {
+			dependency_name,
+			git,
+			tag,
+			link,
+		} => {
 			if dependency_name.is_empty() {
 				anyhow::bail!("Please specify at least one dependency to add");
 			}
-			add_dependencies(&dependency_name)?;
+			match git {
+				Some(url) => {
+					if dependency_name.len() != 1 {
+						anyhow::bail!("--git adds exactly one dependency at a time");
+					}
+					sticks::add_git_dependency(
+						&dependency_name[0],
+						&url,
+						tag.as_deref(),
+						link.as_deref(),
+					)?;
+				}
+				None => add_dependencies(&dependency_name)?,
+			}
+			sticks::lockfile::update_lockfile()?;
 		}
 		Commands::Remove { dependency_name } => {
 			if dependency_name.is_empty() {
 				anyhow::bail!("Please specify at least one dependency to remove");
 			}
 			remove_dependencies(&dependency_name)?;
+			sticks::lockfile::update_lockfile()?;
+		}
+		Commands::Deps { action } => match action {
+			DepsAction::Check => sticks::dependency_check::run_check()?,
+			DepsAction::Audit => sticks::dependency_check::run_audit()?,
+		},
+		Commands::Search { term } => {
+			sticks::search::search_packages(&term)?;
 		}
-		Commands::Src { source_names } => {
+		Commands::Install { locked } => {
+			sticks::lockfile::install_dependencies(locked)?;
+		}
+		Commands::Src {
+			action: Some(action),
+			..
+		} => match action {
+			SrcAction::Rm { names } => {
+				let names: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
+				sticks::sources::remove_sources(&names)?;
+			}
+			SrcAction::Mv { old, new } => sticks::sources::move_source(&old, &new)?,
+		},
+		Commands::Src {
+			action: None,
+			source_names,
+			private,
+		} => {
 			if source_names.is_empty() {
 				anyhow::bail!("Please specify at least one source file to add");
 			}
 			let sources: Vec<&str> = source_names.iter().map(|s| s.as_str()).collect();
-			add_sources(&sources)?;
+			add_sources_with_visibility(&sources, private)?;
+		}
+		Commands::Class {
+			name,
+			namespace,
+			dir,
+			rule_of_five,
+			pragma_once,
+			private,
+		} => {
+			sticks::classes::add_class(&sticks::classes::ClassOptions {
+				name,
+				namespace,
+				dir,
+				special_members: rule_of_five,
+				pragma_once,
+				private,
+			})?;
+		}
+		Commands::Build => {
+			sticks::build_project()?;
 		}
 		Commands::Update => {
 			update_project()?;
 		}
+		Commands::Vendor { action } => match action {
+			VendorAction::Add { url, path, target } => {
+				sticks::vendor::add_vendor(&url, path.as_deref(), target.as_deref())?
+			}
+			VendorAction::Update { name } => sticks::vendor::update_vendor(name.as_deref())?,
+			VendorAction::Remove { name } => sticks::vendor::remove_vendor(&name)?,
+		},
 		Commands::Feature { action } => {
 			handle_feature_action(action)?;
 		}
@@ -316,6 +608,7 @@ fn handle_shortcuts(args: Vec<String>) -> Vec<String> {
 		"a" => "add",
 		"r" => "remove",
 		"u" => "update",
+		"b" => "build",
 		_ => return args,
 	};
 
@@ -324,6 +617,20 @@ fn handle_shortcuts(args: Vec<String>) -> Vec<String> {
 	new_args
 }
 
+fn parse_package_manager(package_manager: Option<&str>) -> Result<Option<sticks::PackageManager>> {
+	package_manager
+		.map(|pm| pm.parse::<sticks::PackageManager>())
+		.transpose()
+}
+
+fn parse_build_profile(static_link: bool, libc: Option<&str>) -> Result<sticks::BuildProfile> {
+	let libc = match libc {
+		Some(l) => l.parse::<sticks::Libc>()?,
+		None => sticks::Libc::default(),
+	};
+	Ok(sticks::BuildProfile::new(libc, static_link))
+}
+
 fn validate_project_names(names: &[String]) -> Result<()> {
 	if names.is_empty() {
 		anyhow::bail!("Please specify at least one project name");
@@ -0,0 +1,583 @@
+use crate::cmake_editor::{REGION_BEGIN, REGION_END};
+
+const DIRECTIVES: &[&str] = &[
+	"include", "-include", "sinclude", "ifeq", "ifneq", "ifdef", "ifndef", "else", "endif",
+	"export", "unexport", "override", "vpath", "define", "undefine", "private",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+	Blank(String),
+	Comment(String),
+	Variable(Variable),
+	Rule(Rule),
+	Region(Region),
+	Directive(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variable {
+	pub name: String,
+	pub op: String,
+	pub value: String,
+	raw: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+	pub targets: Vec<String>,
+	pub prerequisites: Vec<String>,
+	pub recipe: Vec<String>,
+	double_colon: bool,
+	raw_header: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region {
+	pub name: String,
+	pub body: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Anchor<'a> {
+	AfterVariable(&'a str),
+	End,
+}
+
+impl Variable {
+	pub fn new(name: &str, op: &str, value: &str) -> Self {
+		Variable {
+			name: name.to_string(),
+			op: op.to_string(),
+			value: value.to_string(),
+			raw: None,
+		}
+	}
+
+	pub fn set_value(&mut self, value: &str) {
+		if self.value != value {
+			self.value = value.to_string();
+			self.raw = None;
+		}
+	}
+
+	fn lines(&self) -> Vec<String> {
+		match &self.raw {
+			Some(raw) => raw.clone(),
+			None if self.value.is_empty() => vec![format!("{} {}", self.name, self.op)],
+			None => vec![format!("{} {} {}", self.name, self.op, self.value)],
+		}
+	}
+}
+
+impl Rule {
+	pub fn new(target: &str, prerequisites: &[&str], recipe: &[String]) -> Self {
+		Rule {
+			targets: vec![target.to_string()],
+			prerequisites: prerequisites.iter().map(|p| p.to_string()).collect(),
+			recipe: recipe.iter().map(|line| format!("\t{}", line)).collect(),
+			double_colon: false,
+			raw_header: None,
+		}
+	}
+
+	pub fn is_target(&self, target: &str) -> bool {
+		self.targets.len() == 1 && self.targets[0] == target
+	}
+
+	fn lines(&self) -> Vec<String> {
+		let mut lines = match &self.raw_header {
+			Some(raw) => raw.clone(),
+			None => {
+				let separator = if self.double_colon { "::" } else { ":" };
+				let mut header = format!("{}{}", self.targets.join(" "), separator);
+				if !self.prerequisites.is_empty() {
+					header.push(' ');
+					header.push_str(&self.prerequisites.join(" "));
+				}
+				vec![header]
+			}
+		};
+		lines.extend(self.recipe.iter().cloned());
+		lines
+	}
+}
+
+impl Item {
+	pub fn lines(&self) -> Vec<String> {
+		match self {
+			Item::Blank(line) | Item::Comment(line) => vec![line.clone()],
+			Item::Variable(variable) => variable.lines(),
+			Item::Rule(rule) => rule.lines(),
+			Item::Region(region) => {
+				let mut lines = vec![format!("{} {}", REGION_BEGIN, region.name)];
+				lines.extend(region.body.iter().cloned());
+				lines.push(format!("{} {}", REGION_END, region.name));
+				lines
+			}
+			Item::Directive(lines) => lines.clone(),
+		}
+	}
+
+	fn is_blank(&self) -> bool {
+		matches!(self, Item::Blank(_))
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Makefile {
+	items: Vec<Item>,
+	trailing_newline: bool,
+}
+
+impl Default for Makefile {
+	fn default() -> Self {
+		Makefile {
+			items: Vec::new(),
+			trailing_newline: true,
+		}
+	}
+}
+
+impl Makefile {
+	pub fn parse(content: &str) -> Self {
+		Makefile {
+			items: parse_items(content),
+			trailing_newline: content.is_empty() || content.ends_with('\n'),
+		}
+	}
+
+	pub fn render(&self) -> String {
+		let mut output = self
+			.items
+			.iter()
+			.flat_map(Item::lines)
+			.collect::<Vec<_>>()
+			.join("\n");
+		if self.trailing_newline && !output.is_empty() {
+			output.push('\n');
+		}
+		output
+	}
+
+	pub fn items(&self) -> &[Item] {
+		&self.items
+	}
+
+	pub fn variable(&self, name: &str) -> Option<&Variable> {
+		self.items.iter().find_map(|item| match item {
+			Item::Variable(variable) if variable.name == name => Some(variable),
+			_ => None,
+		})
+	}
+
+	pub fn variables<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Variable> + 'a {
+		self.items.iter().filter_map(move |item| match item {
+			Item::Variable(variable) if variable.name == name => Some(variable),
+			_ => None,
+		})
+	}
+
+	pub fn variables_mut(&mut self) -> impl Iterator<Item = &mut Variable> {
+		self.items.iter_mut().filter_map(|item| match item {
+			Item::Variable(variable) => Some(variable),
+			_ => None,
+		})
+	}
+
+	pub fn rule(&self, target: &str) -> Option<&Rule> {
+		self.items.iter().find_map(|item| match item {
+			Item::Rule(rule) if rule.is_target(target) => Some(rule),
+			_ => None,
+		})
+	}
+
+	pub fn rule_mut(&mut self, target: &str) -> Option<&mut Rule> {
+		self.items.iter_mut().find_map(|item| match item {
+			Item::Rule(rule) if rule.is_target(target) => Some(rule),
+			_ => None,
+		})
+	}
+
+	pub fn position(&self, predicate: impl Fn(&Item) -> bool) -> Option<usize> {
+		self.items.iter().position(predicate)
+	}
+
+	pub fn anchor_position(&self, anchor: Anchor) -> usize {
+		let found = match anchor {
+			Anchor::AfterVariable(name) => self
+				.position(|item| matches!(item, Item::Variable(v) if v.name == name))
+				.map(|index| self.skip_regions(index + 1)),
+			Anchor::End => None,
+		};
+		found.unwrap_or(self.items.len())
+	}
+
+	pub fn insert(&mut self, index: usize, items: Vec<Item>) {
+		let index = index.min(self.items.len());
+		self.items.splice(index..index, items);
+	}
+
+	pub fn insert_lines(&mut self, index: usize, lines: &[String]) {
+		self.insert(index, parse_items(&lines.join("\n")));
+	}
+
+	pub fn insert_block(&mut self, index: usize, lines: &[String]) {
+		let mut block = parse_items(&lines.join("\n"));
+		if index == 0 {
+			block.push(Item::Blank(String::new()));
+		} else {
+			block.insert(0, Item::Blank(String::new()));
+		}
+		self.insert(index, block);
+	}
+
+	pub fn remove_where(&mut self, predicate: impl Fn(&Item) -> bool) -> Option<usize> {
+		let first = self.position(&predicate)?;
+		self.items.retain(|item| !predicate(item));
+		Some(first)
+	}
+
+	pub fn remove_block(&mut self, predicate: impl Fn(&Item) -> bool) -> bool {
+		let first = match self.remove_where(predicate) {
+			Some(first) => first,
+			None => return false,
+		};
+		if first > 0
+			&& self.items[first - 1].is_blank()
+			&& self.items.get(first).is_none_or(Item::is_blank)
+		{
+			self.items.remove(first - 1);
+		}
+		self.trim_trailing_blanks();
+		true
+	}
+
+	pub fn set_variable(&mut self, name: &str, value: &str) -> bool {
+		for item in self.items.iter_mut() {
+			if let Item::Variable(variable) = item {
+				if variable.name == name {
+					variable.set_value(value);
+					return true;
+				}
+			}
+		}
+		false
+	}
+
+	pub fn set_rule(&mut self, rule: Rule, comment: Option<&str>) {
+		let target = rule.targets[0].clone();
+		if let Some(existing) = self.items.iter_mut().find_map(|item| match item {
+			Item::Rule(existing) if existing.is_target(&target) => Some(existing),
+			_ => None,
+		}) {
+			*existing = rule;
+			return;
+		}
+
+		self.trim_trailing_blanks();
+		if !self.items.is_empty() {
+			self.items.push(Item::Blank(String::new()));
+		}
+		if let Some(comment) = comment {
+			self.items.push(Item::Comment(comment.to_string()));
+		}
+		self.items.push(Item::Rule(rule));
+	}
+
+	pub fn remove_rule(&mut self, target: &str) -> bool {
+		let index = match self.position(|item| matches!(item, Item::Rule(r) if r.is_target(target)))
+		{
+			Some(index) => index,
+			None => return false,
+		};
+		self.items.remove(index);
+		let mut start = index;
+		if start > 0 && matches!(self.items[start - 1], Item::Comment(_)) {
+			start -= 1;
+			self.items.remove(start);
+		}
+		if start > 0
+			&& self.items[start - 1].is_blank()
+			&& self.items.get(start).is_none_or(Item::is_blank)
+		{
+			self.items.remove(start - 1);
+		}
+		self.trim_trailing_blanks();
+		true
+	}
+
+	pub fn add_prerequisite(&mut self, target: &str, prerequisite: &str) -> bool {
+		self.edit_prerequisites(target, |prerequisites| {
+			if prerequisites.iter().any(|p| p == prerequisite) {
+				false
+			} else {
+				prerequisites.push(prerequisite.to_string());
+				true
+			}
+		})
+	}
+
+	pub fn remove_prerequisite(&mut self, target: &str, prerequisite: &str) -> bool {
+		self.edit_prerequisites(target, |prerequisites| {
+			let before = prerequisites.len();
+			prerequisites.retain(|p| p != prerequisite);
+			prerequisites.len() != before
+		})
+	}
+
+	pub fn add_phony(&mut self, target: &str) -> bool {
+		self.add_prerequisite(".PHONY", target)
+	}
+
+	pub fn remove_phony(&mut self, target: &str) -> bool {
+		self.remove_prerequisite(".PHONY", target)
+	}
+
+	pub fn region(&self, name: &str) -> Option<&Region> {
+		self.items.iter().find_map(|item| match item {
+			Item::Region(region) if region.name == name => Some(region),
+			_ => None,
+		})
+	}
+
+	pub fn set_region(&mut self, name: &str, body: &str, anchor: Anchor) {
+		let region = Region {
+			name: name.to_string(),
+			body: body.lines().map(String::from).collect(),
+		};
+		if let Some(existing) = self.items.iter_mut().find_map(|item| match item {
+			Item::Region(existing) if existing.name == name => Some(existing),
+			_ => None,
+		}) {
+			*existing = region;
+			return;
+		}
+
+		let index = self.anchor_position(anchor);
+		if index >= self.items.len() {
+			self.trim_trailing_blanks();
+			self.items.push(Item::Blank(String::new()));
+			self.items.push(Item::Region(region));
+		} else {
+			self.insert(
+				index,
+				vec![Item::Blank(String::new()), Item::Region(region)],
+			);
+		}
+	}
+
+	pub fn remove_region(&mut self, name: &str) -> bool {
+		let index = match self.position(|item| matches!(item, Item::Region(r) if r.name == name)) {
+			Some(index) => index,
+			None => return false,
+		};
+		self.items.remove(index);
+		if self.items.get(index).is_some_and(Item::is_blank) {
+			self.items.remove(index);
+		} else if index > 0 && self.items[index - 1].is_blank() {
+			self.items.remove(index - 1);
+		}
+		true
+	}
+
+	fn edit_prerequisites(
+		&mut self,
+		target: &str,
+		edit: impl FnOnce(&mut Vec<String>) -> bool,
+	) -> bool {
+		let rule = self.items.iter_mut().find_map(|item| match item {
+			Item::Rule(rule) if rule.is_target(target) => Some(rule),
+			_ => None,
+		});
+		match rule {
+			Some(rule) => {
+				let changed = edit(&mut rule.prerequisites);
+				if changed {
+					rule.raw_header = None;
+				}
+				changed
+			}
+			None => false,
+		}
+	}
+
+	fn skip_regions(&self, mut index: usize) -> usize {
+		loop {
+			let next = if self.items.get(index).is_some_and(Item::is_blank) {
+				index + 1
+			} else {
+				index
+			};
+			if !matches!(self.items.get(next), Some(Item::Region(_))) {
+				return index;
+			}
+			index = next + 1;
+		}
+	}
+
+	fn trim_trailing_blanks(&mut self) {
+		while self.items.last().is_some_and(Item::is_blank) {
+			self.items.pop();
+		}
+	}
+}
+
+fn parse_items(content: &str) -> Vec<Item> {
+	let lines: Vec<&str> = content.lines().collect();
+	let mut items: Vec<Item> = Vec::new();
+	let mut index = 0;
+
+	while index < lines.len() {
+		let line = lines[index];
+		let trimmed = line.trim();
+
+		if line.starts_with('\t') {
+			let end = logical_end(&lines, index);
+			let recipe: Vec<String> = lines[index..=end].iter().map(|l| l.to_string()).collect();
+			match items.last_mut() {
+				Some(Item::Rule(rule)) => rule.recipe.extend(recipe),
+				_ => items.push(Item::Directive(recipe)),
+			}
+			index = end + 1;
+			continue;
+		}
+
+		if trimmed.is_empty() {
+			items.push(Item::Blank(line.to_string()));
+			index += 1;
+			continue;
+		}
+
+		if let Some(name) = trimmed.strip_prefix(REGION_BEGIN) {
+			let name = name.trim();
+			let end_marker = format!("{} {}", REGION_END, name);
+			if let Some(offset) = lines[index + 1..]
+				.iter()
+				.position(|l| l.trim() == end_marker)
+			{
+				let end = index + 1 + offset;
+				items.push(Item::Region(Region {
+					name: name.to_string(),
+					body: lines[index + 1..end]
+						.iter()
+						.map(|l| l.to_string())
+						.collect(),
+				}));
+				index = end + 1;
+				continue;
+			}
+		}
+
+		if trimmed.starts_with('#') {
+			items.push(Item::Comment(line.to_string()));
+			index += 1;
+			continue;
+		}
+
+		let first_word = trimmed.split_whitespace().next().unwrap_or_default();
+		if first_word == "define"
+			|| (DIRECTIVES.contains(&first_word) && trimmed.contains("define "))
+		{
+			let end = lines[index..]
+				.iter()
+				.position(|l| l.trim_start().starts_with("endef"))
+				.map_or(lines.len() - 1, |offset| index + offset);
+			items.push(Item::Directive(
+				lines[index..=end].iter().map(|l| l.to_string()).collect(),
+			));
+			index = end + 1;
+			continue;
+		}
+
+		let end = logical_end(&lines, index);
+		let raw: Vec<String> = lines[index..=end].iter().map(|l| l.to_string()).collect();
+		let logical = lines[index..=end]
+			.iter()
+			.map(|l| l.trim_end().trim_end_matches('\\').trim())
+			.collect::<Vec<_>>()
+			.join(" ");
+		index = end + 1;
+
+		if DIRECTIVES.contains(&first_word) {
+			items.push(Item::Directive(raw));
+			continue;
+		}
+		items.push(parse_statement(&logical, raw));
+	}
+
+	items
+}
+
+fn logical_end(lines: &[&str], start: usize) -> usize {
+	let mut end = start;
+	while lines[end].trim_end().ends_with('\\') && end + 1 < lines.len() {
+		end += 1;
+	}
+	end
+}
+
+fn parse_statement(logical: &str, raw: Vec<String>) -> Item {
+	let chars: Vec<char> = logical.chars().collect();
+	let mut depth = 0;
+	for (i, &c) in chars.iter().enumerate() {
+		match c {
+			'(' | '{' => depth += 1,
+			')' | '}' => depth -= 1,
+			'=' if depth == 0 => {
+				let (name_end, op) = match i.checked_sub(1).map(|p| chars[p]) {
+					Some(prefix @ ('?' | '+' | '!')) => (i - 1, format!("{}=", prefix)),
+					_ => (i, "=".to_string()),
+				};
+				return variable(&chars, name_end, &op, i + 1, raw);
+			}
+			':' if depth == 0 => {
+				let rest: String = chars[i..].iter().take(3).collect();
+				if rest.starts_with("::=") {
+					return variable(&chars, i, "::=", i + 3, raw);
+				}
+				if rest.starts_with(":=") {
+					return variable(&chars, i, ":=", i + 2, raw);
+				}
+				let double_colon = rest.starts_with("::");
+				let targets: String = chars[..i].iter().collect();
+				let prerequisites: String = chars[i + if double_colon { 2 } else { 1 }..]
+					.iter()
+					.collect();
+				if targets.trim().is_empty() {
+					break;
+				}
+				return Item::Rule(Rule {
+					targets: targets.split_whitespace().map(String::from).collect(),
+					prerequisites: prerequisites.split_whitespace().map(String::from).collect(),
+					recipe: Vec::new(),
+					double_colon,
+					raw_header: Some(raw),
+				});
+			}
+			_ => {}
+		}
+	}
+	Item::Directive(raw)
+}
+
+fn variable(
+	chars: &[char],
+	name_end: usize,
+	op: &str,
+	value_start: usize,
+	raw: Vec<String>,
+) -> Item {
+	let name: String = chars[..name_end].iter().collect();
+	let name = name.trim();
+	if name.is_empty() || name.contains(char::is_whitespace) {
+		return Item::Directive(raw);
+	}
+	let value: String = chars[value_start.min(chars.len())..].iter().collect();
+	Item::Variable(Variable {
+		name: name.to_string(),
+		op: op.to_string(),
+		value: value.trim().to_string(),
+		raw: Some(raw),
+	})
+}
@@ -1,8 +1,16 @@
+use anyhow::{Context, Result};
 use std::str::FromStr;
 
+use crate::cmake_editor::{REGION_BEGIN, REGION_END};
+use crate::constants::conan as conan_constants;
+use crate::file_handler::join_lines;
+use crate::json::{detect_indent, JsonValue};
+use crate::{conan, vcpkg, BuildSystem};
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum PackageManager {
 	Conan,
+	ConanRecipe,
 	Vcpkg,
 }
 
@@ -10,6 +18,7 @@ impl std::fmt::Display for PackageManager {
 	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
 		match self {
 			PackageManager::Conan => write!(f, "Conan"),
+			PackageManager::ConanRecipe => write!(f, "Conan (conanfile.py)"),
 			PackageManager::Vcpkg => write!(f, "vcpkg"),
 		}
 	}
@@ -21,20 +30,62 @@ impl FromStr for PackageManager {
 	fn from_str(input: &str) -> Result<PackageManager, Self::Err> {
 		match input.to_lowercase().as_str() {
 			"conan" => Ok(PackageManager::Conan),
+			"conan-py" | "conanfile.py" => Ok(PackageManager::ConanRecipe),
 			"vcpkg" => Ok(PackageManager::Vcpkg),
 			_ => anyhow::bail!(
-				"Unsupported package manager: {}. Use 'conan' or 'vcpkg'",
+				"Unsupported package manager: {}. Use 'conan', 'conan-py' or 'vcpkg'",
 				input
 			),
 		}
 	}
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackageSpec {
+	pub name: String,
+	pub version: Option<String>,
+}
+
+impl std::fmt::Display for PackageSpec {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match &self.version {
+			Some(version) => write!(f, "{}/{}", self.name, version),
+			None => write!(f, "{}", self.name),
+		}
+	}
+}
+
+impl FromStr for PackageSpec {
+	type Err = anyhow::Error;
+
+	fn from_str(input: &str) -> Result<PackageSpec, Self::Err> {
+		let input = input.trim();
+		let (name, version) = match input.split_once('/') {
+			Some((name, version)) => (name, Some(version)),
+			None => (input, None),
+		};
+		if name.is_empty() || version.is_some_and(str::is_empty) {
+			anyhow::bail!(
+				"Invalid package spec '{}'. Use <name> or <name>/<version>",
+				input
+			);
+		}
+		Ok(PackageSpec {
+			name: name.to_string(),
+			version: version.map(String::from),
+		})
+	}
+}
+
 pub trait PackageManagerGenerator {
 	fn name(&self) -> &'static str;
 	fn generate_manifest(&self, project_name: &str) -> String;
 	fn extension(&self) -> &'static str;
 	fn generate_install_instructions(&self) -> String;
+	fn install_command(&self) -> &'static str;
+	fn dependencies(&self, manifest: &str) -> Result<Vec<PackageSpec>>;
+	fn add_dependencies(&self, manifest: &str, specs: &[PackageSpec]) -> Result<String>;
+	fn remove_dependencies(&self, manifest: &str, names: &[String]) -> Result<String>;
 }
 
 pub struct ConanGenerator;
@@ -66,10 +117,308 @@ impl PackageManagerGenerator for ConanGenerator {
 		1. Install Conan: pip install conan\n\
 		2. Add dependencies to conanfile.txt in [requires] section:\n\
 		   Example: libcurl/7.85.0\n\
+		3. Install dependencies: conan install . --output-folder=.conan --build=missing\n\
+		4. Build as usual: sticks wires .conan into CMakeLists.txt or the Makefile"
+			.to_string()
+	}
+
+	fn install_command(&self) -> &'static str {
+		"conan install . --output-folder=.conan --build=missing"
+	}
+
+	fn dependencies(&self, manifest: &str) -> Result<Vec<PackageSpec>> {
+		let lines: Vec<&str> = manifest.lines().collect();
+		let (start, end) = match conan_section(&lines, "requires") {
+			Some(range) => range,
+			None => return Ok(Vec::new()),
+		};
+		lines[start + 1..end]
+			.iter()
+			.map(|line| line.trim())
+			.filter(|line| !line.is_empty() && !line.starts_with('#'))
+			.map(|line| line.parse())
+			.collect()
+	}
+
+	fn add_dependencies(&self, manifest: &str, specs: &[PackageSpec]) -> Result<String> {
+		if let Some(spec) = specs.iter().find(|spec| spec.version.is_none()) {
+			anyhow::bail!(
+				"Conan requires a version for '{}'. Use {}/<version>, e.g. fmt/10.2.1",
+				spec.name,
+				spec.name
+			);
+		}
+
+		let mut lines: Vec<String> = manifest.lines().map(String::from).collect();
+		let borrowed: Vec<&str> = lines.iter().map(String::as_str).collect();
+		let (start, mut end) = match conan_section(&borrowed, "requires") {
+			Some(range) => range,
+			None => {
+				lines.splice(0..0, ["[requires]".to_string(), String::new()]);
+				(0, 1)
+			}
+		};
+
+		for spec in specs {
+			let existing = (start + 1..end).find(|&i| conan_reference_name(&lines[i]) == spec.name);
+			match existing {
+				Some(index) => lines[index] = spec.to_string(),
+				None => {
+					let mut insert_at = end;
+					while insert_at > start + 1 && lines[insert_at - 1].trim().is_empty() {
+						insert_at -= 1;
+					}
+					lines.insert(insert_at, spec.to_string());
+					end += 1;
+				}
+			}
+		}
+
+		Ok(join_lines(&lines))
+	}
+
+	fn remove_dependencies(&self, manifest: &str, names: &[String]) -> Result<String> {
+		let lines: Vec<&str> = manifest.lines().collect();
+		let (start, end) = match conan_section(&lines, "requires") {
+			Some(range) => range,
+			None => return Ok(manifest.to_string()),
+		};
+		let kept: Vec<String> = lines
+			.iter()
+			.enumerate()
+			.filter(|(i, line)| {
+				*i <= start || *i >= end || !names.iter().any(|n| conan_reference_name(line) == n)
+			})
+			.map(|(_, line)| line.to_string())
+			.collect();
+		Ok(join_lines(&kept))
+	}
+}
+
+fn conan_section(lines: &[&str], section: &str) -> Option<(usize, usize)> {
+	let header = format!("[{}]", section);
+	let start = lines.iter().position(|line| line.trim() == header)?;
+	let end = lines[start + 1..]
+		.iter()
+		.position(|line| line.trim_start().starts_with('['))
+		.map(|offset| start + 1 + offset)
+		.unwrap_or(lines.len());
+	Some((start, end))
+}
+
+fn conan_reference_name(line: &str) -> &str {
+	let line = line.trim();
+	line.split(['/', '@']).next().unwrap_or(line)
+}
+
+const RECIPE_INDENT: &str = "    ";
+
+pub struct ConanRecipeGenerator;
+
+impl ConanRecipeGenerator {
+	pub fn generate_recipe(
+		&self,
+		project_name: &str,
+		build_system: BuildSystem,
+		requirements: &[PackageSpec],
+	) -> String {
+		let (imports, exports, layout, generate, build, package) = match build_system {
+			BuildSystem::CMake => (
+				"from conan.tools.cmake import CMake, CMakeDeps, CMakeToolchain, cmake_layout\n",
+				"\"CMakeLists.txt\", \"src/*\", \"include/*\"",
+				"\t\tcmake_layout(self)\n",
+				"\t\tCMakeDeps(self).generate()\n\
+				\t\tCMakeToolchain(self).generate()\n",
+				"\t\tcmake = CMake(self)\n\
+				\t\tcmake.configure()\n\
+				\t\tcmake.build()\n",
+				"\t\tcmake = CMake(self)\n\
+				\t\tcmake.install()\n",
+			),
+			BuildSystem::Makefile => (
+				"from conan.tools.files import copy\n\
+				from conan.tools.gnu import PkgConfigDeps\n",
+				"\"Makefile\", \"src/*\", \"include/*\"",
+				"",
+				"\t\tPkgConfigDeps(self).generate()\n",
+				"\t\tself.run(\"make\", cwd=self.source_folder)\n",
+				"\t\tcopy(self, \"*\", src=os.path.join(self.source_folder, \"bin\"), dst=os.path.join(self.package_folder, \"bin\"))\n",
+			),
+		};
+
+		format!(
+			"{}from conan import ConanFile\n\
+			{}\
+			\n\
+			\n\
+			class {}Conan(ConanFile):\n\
+			\tname = \"{}\"\n\
+			\tversion = \"0.1.0\"\n\
+			\tpackage_type = \"application\"\n\
+			\tsettings = \"os\", \"compiler\", \"build_type\", \"arch\"\n\
+			\texports_sources = {}\n\
+			\n\
+			\tdef requirements(self):\n\
+			{}\
+			\n\
+			\tdef layout(self):\n\
+			{}\
+			\t\tself.folders.generators = \"{}\"\n\
+			\n\
+			\tdef generate(self):\n\
+			{}\
+			\n\
+			\tdef build(self):\n\
+			{}\
+			\n\
+			\tdef package(self):\n\
+			{}",
+			match build_system {
+				BuildSystem::CMake => "",
+				BuildSystem::Makefile => "import os\n\n",
+			},
+			imports,
+			recipe_class_name(project_name),
+			project_name.to_lowercase(),
+			exports,
+			requires_region(requirements, "\t\t"),
+			layout,
+			conan_constants::OUTPUT_DIR,
+			generate,
+			build,
+			package
+		)
+		.replace('\t', RECIPE_INDENT)
+	}
+}
+
+impl PackageManagerGenerator for ConanRecipeGenerator {
+	fn name(&self) -> &'static str {
+		"Conan"
+	}
+
+	fn extension(&self) -> &'static str {
+		"conanfile.py"
+	}
+
+	fn generate_manifest(&self, project_name: &str) -> String {
+		self.generate_recipe(project_name, BuildSystem::CMake, &[])
+	}
+
+	fn generate_install_instructions(&self) -> String {
+		"To use the Conan 2 recipe with this project:\n\n\
+		1. Install Conan 2: pip install conan\n\
+		2. Add requirements: sticks add fmt/10.2.1\n\
 		3. Install dependencies: conan install . --build=missing\n\
-		4. Use generated CMake toolchain in your CMakeLists.txt"
+		4. Package the project: conan create .\n\
+		5. Publish to a remote: conan upload <name>/0.1.0 -r <remote>"
 			.to_string()
 	}
+
+	fn install_command(&self) -> &'static str {
+		"conan install . --build=missing"
+	}
+
+	fn dependencies(&self, manifest: &str) -> Result<Vec<PackageSpec>> {
+		let lines: Vec<&str> = manifest.lines().collect();
+		let (start, end) = match recipe_region(&lines) {
+			Some(range) => range,
+			None => return Ok(Vec::new()),
+		};
+		lines[start + 1..end]
+			.iter()
+			.filter_map(|line| recipe_reference(line))
+			.map(str::parse)
+			.collect()
+	}
+
+	fn add_dependencies(&self, manifest: &str, specs: &[PackageSpec]) -> Result<String> {
+		if let Some(spec) = specs.iter().find(|spec| spec.version.is_none()) {
+			anyhow::bail!(
+				"Conan requires a version for '{}'. Use {}/<version>, e.g. fmt/10.2.1",
+				spec.name,
+				spec.name
+			);
+		}
+		let mut requirements = self.dependencies(manifest)?;
+		for spec in specs {
+			match requirements.iter_mut().find(|r| r.name == spec.name) {
+				Some(existing) => *existing = spec.clone(),
+				None => requirements.push(spec.clone()),
+			}
+		}
+		replace_recipe_region(manifest, &requirements)
+	}
+
+	fn remove_dependencies(&self, manifest: &str, names: &[String]) -> Result<String> {
+		let mut requirements = self.dependencies(manifest)?;
+		requirements.retain(|r| !names.contains(&r.name));
+		replace_recipe_region(manifest, &requirements)
+	}
+}
+
+fn recipe_class_name(project_name: &str) -> String {
+	let name: String = project_name
+		.split(|c: char| !c.is_ascii_alphanumeric())
+		.filter(|part| !part.is_empty())
+		.map(|part| {
+			let mut chars = part.chars();
+			match chars.next() {
+				Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+				None => String::new(),
+			}
+		})
+		.collect();
+	if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+		name
+	} else {
+		format!("Pkg{}", name)
+	}
+}
+
+fn requires_region(requirements: &[PackageSpec], indent: &str) -> String {
+	let mut region = format!("{}{} requires\n", indent, REGION_BEGIN);
+	if requirements.is_empty() {
+		region.push_str(&format!("{}pass\n", indent));
+	}
+	for requirement in requirements {
+		region.push_str(&format!("{}self.requires(\"{}\")\n", indent, requirement));
+	}
+	region.push_str(&format!("{}{} requires\n", indent, REGION_END));
+	region
+}
+
+fn recipe_region(lines: &[&str]) -> Option<(usize, usize)> {
+	let begin = format!("{} requires", REGION_BEGIN);
+	let end = format!("{} requires", REGION_END);
+	let start = lines.iter().position(|l| l.trim() == begin)?;
+	let stop = lines[start..].iter().position(|l| l.trim() == end)? + start;
+	Some((start, stop))
+}
+
+fn recipe_reference(line: &str) -> Option<&str> {
+	line.trim()
+		.strip_prefix("self.requires(")?
+		.strip_suffix(')')?
+		.trim_matches(|c| c == '"' || c == '\'')
+		.into()
+}
+
+fn replace_recipe_region(manifest: &str, requirements: &[PackageSpec]) -> Result<String> {
+	let lines: Vec<&str> = manifest.lines().collect();
+	let (start, end) = recipe_region(&lines)
+		.context("conanfile.py has no '# sticks:begin requires' block in requirements()")?;
+	let mut output = lines[..start].join("\n");
+	if start > 0 {
+		output.push('\n');
+	}
+	let indent = &lines[start][..lines[start].len() - lines[start].trim_start().len()];
+	output.push_str(&requires_region(requirements, indent));
+	for line in &lines[end + 1..] {
+		output.push_str(line);
+		output.push('\n');
+	}
+	Ok(output)
 }
 
 pub struct VcpkgGenerator;
@@ -107,11 +456,127 @@ impl PackageManagerGenerator for VcpkgGenerator {
 		   -DCMAKE_TOOLCHAIN_FILE=./vcpkg/scripts/buildsystems/vcpkg.cmake"
 			.to_string()
 	}
+
+	fn install_command(&self) -> &'static str {
+		"vcpkg install"
+	}
+
+	fn dependencies(&self, manifest: &str) -> Result<Vec<PackageSpec>> {
+		let root = JsonValue::parse(manifest).context("Failed to parse vcpkg.json")?;
+		let entries = match root.get("dependencies").and_then(JsonValue::as_array) {
+			Some(entries) => entries,
+			None => return Ok(Vec::new()),
+		};
+		Ok(entries
+			.iter()
+			.filter_map(|entry| {
+				let name = vcpkg_dependency_name(entry)?;
+				Some(PackageSpec {
+					name: name.to_string(),
+					version: entry
+						.get("version>=")
+						.and_then(JsonValue::as_str)
+						.map(String::from),
+				})
+			})
+			.collect())
+	}
+
+	fn add_dependencies(&self, manifest: &str, specs: &[PackageSpec]) -> Result<String> {
+		let mut root = JsonValue::parse(manifest).context("Failed to parse vcpkg.json")?;
+		if root
+			.get("dependencies")
+			.and_then(JsonValue::as_array)
+			.is_none()
+		{
+			root.set("dependencies", JsonValue::Array(Vec::new()));
+		}
+		let entries = root
+			.get_mut("dependencies")
+			.and_then(JsonValue::as_array_mut)
+			.context("vcpkg.json has no dependencies array")?;
+
+		for spec in specs {
+			let entry = match &spec.version {
+				Some(version) => {
+					let mut entry = JsonValue::object();
+					entry.set("name", JsonValue::string(&spec.name));
+					entry.set("version>=", JsonValue::string(version));
+					entry
+				}
+				None => JsonValue::string(&spec.name),
+			};
+			match entries
+				.iter_mut()
+				.find(|existing| vcpkg_dependency_name(existing) == Some(spec.name.as_str()))
+			{
+				Some(existing) => *existing = entry,
+				None => entries.push(entry),
+			}
+		}
+
+		Ok(root.render_with_indent(detect_indent(manifest)))
+	}
+
+	fn remove_dependencies(&self, manifest: &str, names: &[String]) -> Result<String> {
+		let mut root = JsonValue::parse(manifest).context("Failed to parse vcpkg.json")?;
+		if let Some(entries) = root
+			.get_mut("dependencies")
+			.and_then(JsonValue::as_array_mut)
+		{
+			entries.retain(|entry| {
+				!vcpkg_dependency_name(entry).is_some_and(|name| names.iter().any(|n| n == name))
+			});
+		}
+		Ok(root.render_with_indent(detect_indent(manifest)))
+	}
+}
+
+fn vcpkg_dependency_name(entry: &JsonValue) -> Option<&str> {
+	entry
+		.as_str()
+		.or_else(|| entry.get("name").and_then(JsonValue::as_str))
 }
 
 pub fn get_package_manager_generator(pm: PackageManager) -> Box<dyn PackageManagerGenerator> {
 	match pm {
 		PackageManager::Conan => Box::new(ConanGenerator),
+		PackageManager::ConanRecipe => Box::new(ConanRecipeGenerator),
 		PackageManager::Vcpkg => Box::new(VcpkgGenerator),
 	}
 }
+
+pub fn integrate_package_manager(pm: PackageManager, build_system: BuildSystem) -> Result<()> {
+	match (pm, build_system) {
+		(PackageManager::Conan | PackageManager::ConanRecipe, _) => {
+			conan::integrate(pm, build_system)
+		}
+		(PackageManager::Vcpkg, BuildSystem::CMake) => vcpkg::integrate(),
+		(PackageManager::Vcpkg, BuildSystem::Makefile) => {
+			println!(
+				"ℹ️  vcpkg manifest mode needs CMake; run 'sticks f convert cmake' to wire it into the build"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub fn remove_package_manager_integration(
+	pm: PackageManager,
+	build_system: BuildSystem,
+) -> Result<()> {
+	match (pm, build_system) {
+		(PackageManager::Conan | PackageManager::ConanRecipe, _) => {
+			conan::remove_integration(build_system)
+		}
+		(PackageManager::Vcpkg, BuildSystem::CMake) => vcpkg::remove_integration(),
+		(PackageManager::Vcpkg, BuildSystem::Makefile) => Ok(()),
+	}
+}
+
+pub fn sync_package_manager_dependencies(pm: PackageManager) -> Result<()> {
+	match pm {
+		PackageManager::Conan | PackageManager::ConanRecipe => conan::sync_cmake_dependencies(pm),
+		PackageManager::Vcpkg => vcpkg::sync_cmake_dependencies(),
+	}
+}
@@ -0,0 +1,446 @@
+use crate::config::{ConfigFile, ConfigValue, ProjectConfig};
+use crate::constants::{config, project_templates};
+use crate::file_handler::create_dir;
+use crate::languages::{Language, SourceCounts};
+use crate::BuildProfile;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub struct TemplateOptions {
+	pub template: String,
+	pub language: Option<Language>,
+	pub values: Vec<(String, String)>,
+	pub run_hooks: Option<bool>,
+}
+
+#[derive(Debug, Default)]
+pub struct TemplateManifest {
+	pub description: Option<String>,
+	pub language: Option<Language>,
+	pub prompts: Vec<TemplatePrompt>,
+	pub post_create: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TemplatePrompt {
+	pub key: String,
+	pub question: String,
+	pub default: Option<String>,
+}
+
+impl TemplateManifest {
+	pub fn parse(content: &str) -> Result<Self> {
+		let file = ConfigFile::parse(content)?;
+
+		let description = file
+			.get("template", "description")
+			.and_then(ConfigValue::as_str)
+			.map(String::from);
+		let language = file
+			.get("template", "language")
+			.and_then(ConfigValue::as_str)
+			.map(str::parse::<Language>)
+			.transpose()?;
+
+		let mut prompts = Vec::new();
+		for (key, value) in file.section("prompts").unwrap_or(&[]) {
+			let (question, default) = match value {
+				ConfigValue::String(question) => (question.clone(), None),
+				ConfigValue::Array(items) if !items.is_empty() && items.len() <= 2 => {
+					(items[0].clone(), items.get(1).cloned())
+				}
+				_ => anyhow::bail!(
+					"Prompt '{}' must be a question string or [\"question\", \"default\"]",
+					key
+				),
+			};
+			prompts.push(TemplatePrompt {
+				key: key.clone(),
+				question,
+				default,
+			});
+		}
+
+		let post_create = match file.get("hooks", "post_create") {
+			Some(ConfigValue::String(command)) => vec![command.clone()],
+			Some(ConfigValue::Array(commands)) => commands.clone(),
+			Some(_) => anyhow::bail!("hooks.post_create must be a command or a list of commands"),
+			None => Vec::new(),
+		};
+
+		Ok(TemplateManifest {
+			description,
+			language,
+			prompts,
+			post_create,
+		})
+	}
+
+	fn load(dir: &Path) -> Result<Self> {
+		let path = dir.join(project_templates::MANIFEST);
+		if !path.exists() {
+			return Ok(TemplateManifest::default());
+		}
+		let content = fs::read_to_string(&path)
+			.with_context(|| format!("Failed to read {}", path.display()))?;
+		TemplateManifest::parse(&content)
+			.with_context(|| format!("Failed to parse {}", path.display()))
+	}
+}
+
+enum TemplateSource {
+	Local(PathBuf),
+	Cloned(PathBuf),
+}
+
+impl TemplateSource {
+	fn path(&self) -> &Path {
+		match self {
+			TemplateSource::Local(path) | TemplateSource::Cloned(path) => path,
+		}
+	}
+}
+
+impl Drop for TemplateSource {
+	fn drop(&mut self) {
+		if let TemplateSource::Cloned(path) = self {
+			let _ = fs::remove_dir_all(path);
+		}
+	}
+}
+
+pub fn templates_dir() -> Option<PathBuf> {
+	dirs::config_dir().map(|dir| dir.join(project_templates::USER_DIR))
+}
+
+pub fn list_templates() -> Result<Vec<String>> {
+	let dir = match templates_dir() {
+		Some(dir) if dir.is_dir() => dir,
+		_ => return Ok(Vec::new()),
+	};
+
+	let mut names = Vec::new();
+	for entry in fs::read_dir(&dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+		let entry = entry?;
+		if entry.file_type()?.is_dir() {
+			if let Some(name) = entry.file_name().to_str() {
+				names.push(name.to_string());
+			}
+		}
+	}
+	names.sort();
+	Ok(names)
+}
+
+pub fn is_git_url(spec: &str) -> bool {
+	["https://", "http://", "ssh://", "git://", "git@", "file://"]
+		.iter()
+		.any(|prefix| spec.starts_with(prefix))
+		|| spec.ends_with(".git")
+}
+
+fn resolve_template(spec: &str) -> Result<TemplateSource> {
+	if is_git_url(spec) {
+		return clone_template(spec);
+	}
+
+	let expanded = match spec.strip_prefix("~/") {
+		Some(rest) => dirs::home_dir()
+			.context("Failed to determine home directory")?
+			.join(rest),
+		None => PathBuf::from(spec),
+	};
+	if expanded.is_dir() {
+		let path = expanded
+			.canonicalize()
+			.with_context(|| format!("Failed to resolve template path {}", spec))?;
+		return Ok(TemplateSource::Local(path));
+	}
+	if spec.contains('/') || spec.starts_with('.') || spec.starts_with('~') {
+		anyhow::bail!("Template directory not found: {}", spec);
+	}
+
+	if let Some(dir) = templates_dir() {
+		let path = dir.join(spec);
+		if path.is_dir() {
+			return Ok(TemplateSource::Local(path));
+		}
+	}
+
+	let available = list_templates()?;
+	if available.is_empty() {
+		anyhow::bail!(
+			"Template '{}' not found. Add templates to {}",
+			spec,
+			templates_dir()
+				.map(|dir| dir.display().to_string())
+				.unwrap_or_else(|| format!("~/.config/{}", project_templates::USER_DIR))
+		);
+	}
+	anyhow::bail!(
+		"Template '{}' not found. Available templates: {}",
+		spec,
+		available.join(", ")
+	)
+}
+
+fn clone_template(url: &str) -> Result<TemplateSource> {
+	let nanos = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|d| d.as_nanos())
+		.unwrap_or(0);
+	let dest = env::temp_dir().join(format!("sticks-template-{}-{}", std::process::id(), nanos));
+
+	println!("📥 Fetching template from {}", url);
+	let output = Command::new("git")
+		.args(["clone", "--depth", "1", "--quiet", url])
+		.arg(&dest)
+		.output()
+		.context("Failed to run git. Is it installed?")?;
+	let source = TemplateSource::Cloned(dest);
+	if !output.status.success() {
+		anyhow::bail!(
+			"Failed to clone template {}: {}",
+			url,
+			String::from_utf8_lossy(&output.stderr).trim()
+		);
+	}
+	Ok(source)
+}
+
+pub fn render_placeholders(content: &str, values: &HashMap<String, String>) -> String {
+	let mut rendered = String::with_capacity(content.len());
+	let mut rest = content;
+
+	while let Some(start) = rest.find("{{") {
+		rendered.push_str(&rest[..start]);
+		let after = &rest[start + 2..];
+		match after.find("}}") {
+			Some(end) => match values.get(after[..end].trim()) {
+				Some(value) => {
+					rendered.push_str(value);
+					rest = &after[end + 2..];
+				}
+				None => {
+					rendered.push_str("{{");
+					rest = after;
+				}
+			},
+			None => {
+				rendered.push_str(&rest[start..]);
+				rest = "";
+			}
+		}
+	}
+	rendered.push_str(rest);
+	rendered
+}
+
+pub fn current_year() -> i64 {
+	let days = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|d| d.as_secs() as i64 / 86_400)
+		.unwrap_or(0);
+
+	// Civil-from-days conversion (proleptic Gregorian calendar).
+	let z = days + 719_468;
+	let era = z.div_euclid(146_097);
+	let doe = z.rem_euclid(146_097);
+	let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+	let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+	let mp = (5 * doy + 2) / 153;
+	let month = if mp < 10 { mp + 3 } else { mp - 9 };
+	let year = yoe + era * 400;
+	if month <= 2 {
+		year + 1
+	} else {
+		year
+	}
+}
+
+fn prompt_value(prompt: &TemplatePrompt) -> Result<String> {
+	match &prompt.default {
+		Some(default) => print!("📝 {} [{}]: ", prompt.question, default),
+		None => print!("📝 {}: ", prompt.question),
+	}
+	io::stdout().flush()?;
+
+	let mut answer = String::new();
+	io::stdin().read_line(&mut answer)?;
+	let answer = answer.trim();
+	if answer.is_empty() {
+		return Ok(prompt.default.clone().unwrap_or_default());
+	}
+	Ok(answer.to_string())
+}
+
+fn copy_template(
+	src: &Path,
+	dest: &Path,
+	values: &HashMap<String, String>,
+	top_level: bool,
+) -> Result<()> {
+	let mut entries = fs::read_dir(src)
+		.with_context(|| format!("Failed to read {}", src.display()))?
+		.collect::<std::io::Result<Vec<_>>>()?;
+	entries.sort_by_key(|entry| entry.file_name());
+
+	for entry in entries {
+		let file_name = entry.file_name();
+		let name = file_name.to_string_lossy();
+		if name == ".git" || (top_level && name == project_templates::MANIFEST) {
+			continue;
+		}
+		let target = dest.join(render_placeholders(&name, values));
+		let file_type = entry.file_type()?;
+
+		if file_type.is_symlink() {
+			copy_symlink(&entry.path(), &target, values)?;
+			continue;
+		}
+		if file_type.is_dir() {
+			fs::create_dir_all(&target)
+				.with_context(|| format!("Failed to create {}", target.display()))?;
+			copy_template(&entry.path(), &target, values, false)?;
+			continue;
+		}
+		if !file_type.is_file() {
+			anyhow::bail!(
+				"Unsupported file type in template: {}",
+				entry.path().display()
+			);
+		}
+
+		let bytes = fs::read(entry.path()).with_context(|| format!("Failed to read {}", name))?;
+		match String::from_utf8(bytes) {
+			Ok(text) => fs::write(&target, render_placeholders(&text, values)),
+			Err(err) => fs::write(&target, err.into_bytes()),
+		}
+		.with_context(|| format!("Failed to write {}", target.display()))?;
+
+		let permissions = fs::metadata(entry.path())?.permissions();
+		fs::set_permissions(&target, permissions)
+			.with_context(|| format!("Failed to set permissions on {}", target.display()))?;
+	}
+	Ok(())
+}
+
+fn copy_symlink(link: &Path, target: &Path, values: &HashMap<String, String>) -> Result<()> {
+	let destination =
+		fs::read_link(link).with_context(|| format!("Failed to read link {}", link.display()))?;
+	let destination = PathBuf::from(render_placeholders(&destination.to_string_lossy(), values));
+	let escapes = destination.is_absolute()
+		|| destination
+			.components()
+			.any(|component| matches!(component, Component::ParentDir));
+	if escapes {
+		anyhow::bail!(
+			"Template link {} points outside the template ({}). Only relative links inside the template are copied",
+			link.display(),
+			destination.display()
+		);
+	}
+	std::os::unix::fs::symlink(&destination, target)
+		.with_context(|| format!("Failed to create link {}", target.display()))
+}
+
+fn confirm_hooks(commands: &[String]) -> Result<bool> {
+	println!("⚠️ This template wants to run the following commands:");
+	for command in commands {
+		println!("   {}", command);
+	}
+	print!("Run them? [y/N]: ");
+	io::stdout().flush()?;
+
+	let mut answer = String::new();
+	io::stdin().read_line(&mut answer)?;
+	Ok(matches!(answer.trim(), "y" | "Y" | "yes" | "Yes"))
+}
+
+fn run_hooks(commands: &[String]) -> Result<()> {
+	for command in commands {
+		println!("🔧 Running hook: {}", command);
+		let status = Command::new("sh")
+			.args(["-c", command])
+			.status()
+			.with_context(|| format!("Failed to run hook: {}", command))?;
+		if !status.success() {
+			anyhow::bail!("Hook failed ({}): {}", status, command);
+		}
+	}
+	Ok(())
+}
+
+pub fn new_project_from_template(project_name: &str, options: &TemplateOptions) -> Result<()> {
+	let source = resolve_template(&options.template)?;
+	let template_dir = source.path();
+	let manifest = TemplateManifest::load(template_dir)?;
+
+	let language = match options.language.or(manifest.language) {
+		Some(language) => language,
+		None => SourceCounts::scan(&template_dir.join("src"))?
+			.primary()
+			.unwrap_or(Language::C),
+	};
+
+	let mut values = HashMap::new();
+	values.insert("project_name".to_string(), project_name.to_string());
+	values.insert("language".to_string(), language.as_str().to_string());
+	values.insert("year".to_string(), current_year().to_string());
+	for (key, value) in &options.values {
+		values.insert(key.clone(), value.clone());
+	}
+	for prompt in &manifest.prompts {
+		if !values.contains_key(&prompt.key) {
+			let answer = prompt_value(prompt)?;
+			values.insert(prompt.key.clone(), answer);
+		}
+	}
+
+	if let Some(description) = &manifest.description {
+		println!("📦 {}", description);
+	}
+
+	create_dir(project_name)?;
+	let project_dir = env::current_dir().context("Failed to get current directory")?;
+	if let Err(err) = copy_template(template_dir, &project_dir, &values, true) {
+		if let Some(parent) = project_dir.parent() {
+			env::set_current_dir(parent).ok();
+			fs::remove_dir_all(&project_dir).ok();
+		}
+		return Err(err);
+	}
+
+	if !Path::new(config::FILENAME).exists() {
+		ProjectConfig::new(project_name, language, BuildProfile::default()).save()?;
+	}
+
+	let hooks = manifest
+		.post_create
+		.iter()
+		.map(|command| render_placeholders(command, &values))
+		.collect::<Vec<_>>();
+	let remote = matches!(source, TemplateSource::Cloned(_));
+	let run = match options.run_hooks {
+		Some(run) => run,
+		None if remote && !hooks.is_empty() => confirm_hooks(&hooks)?,
+		None => true,
+	};
+	if run {
+		run_hooks(&hooks)?;
+	} else if !hooks.is_empty() {
+		println!("⚠️ Skipped {} post_create hook(s)", hooks.len());
+	}
+
+	println!(
+		"✓ Created project {} from template {}",
+		project_name, options.template
+	);
+	Ok(())
+}
@@ -0,0 +1,348 @@
+use anyhow::{Context, Result};
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::constants::{search, vcpkg};
+use crate::json::JsonValue;
+use crate::system_packages::{executable_in_path, HostPackageManager, KNOWN_PACKAGES};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchSource {
+	Known,
+	Host(HostPackageManager),
+	PkgConfig,
+	Conan,
+	Vcpkg,
+}
+
+impl std::fmt::Display for SearchSource {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			SearchSource::Known => write!(f, "sticks (known packages, any host)"),
+			SearchSource::Host(pm) => write!(f, "{} (system package)", pm),
+			SearchSource::PkgConfig => write!(f, "pkg-config (installed modules)"),
+			SearchSource::Conan => write!(f, "Conan cache (Conan projects)"),
+			SearchSource::Vcpkg => write!(f, "vcpkg ports (vcpkg projects)"),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+	pub source: SearchSource,
+	pub package: String,
+	pub add_name: String,
+	pub description: String,
+}
+
+pub fn search_packages(term: &str) -> Result<()> {
+	let results = search(term)?;
+	if results.is_empty() {
+		println!("No packages matching '{}' were found.", term);
+		return Ok(());
+	}
+
+	println!("🔎 Packages matching '{}':", term);
+	let mut sources: Vec<SearchSource> = Vec::new();
+	for result in &results {
+		if !sources.contains(&result.source) {
+			sources.push(result.source);
+		}
+	}
+
+	for source in sources {
+		let matches: Vec<&SearchResult> = results.iter().filter(|r| r.source == source).collect();
+		println!("\n{}:", source);
+		for result in matches.iter().take(search::MAX_RESULTS) {
+			let provides = if result.package == result.add_name {
+				String::new()
+			} else {
+				format!(" [{}]", result.package)
+			};
+			println!(
+				"  sticks add {:<28} {}{}",
+				result.add_name, result.description, provides
+			);
+		}
+		if matches.len() > search::MAX_RESULTS {
+			println!("  ... and {} more", matches.len() - search::MAX_RESULTS);
+		}
+	}
+	Ok(())
+}
+
+pub fn search(term: &str) -> Result<Vec<SearchResult>> {
+	let term = term.trim();
+	if term.is_empty() {
+		anyhow::bail!("Please specify a search term");
+	}
+
+	let mut results = search_known(term);
+	if let Some(pm) = HostPackageManager::detect() {
+		results.extend(search_host(pm, term));
+	}
+	results.extend(search_pkg_config(term));
+	results.extend(search_conan(term));
+	results.extend(search_vcpkg(term)?);
+	Ok(results)
+}
+
+pub fn search_known(term: &str) -> Vec<SearchResult> {
+	let pm = HostPackageManager::detect();
+	KNOWN_PACKAGES
+		.iter()
+		.filter(|known| {
+			matches_term(known.name, term) || known.aliases.iter().any(|a| matches_term(a, term))
+		})
+		.map(|known| {
+			let package = pm
+				.map(|pm| known.package_for(pm))
+				.filter(|p| !p.is_empty())
+				.unwrap_or(known.name);
+			let description = match pm {
+				Some(pm) if known.package_for(pm).is_empty() => {
+					"provided by the C library".to_string()
+				}
+				Some(pm) => format!("installs {} with {}", package, pm),
+				None => "translated for each host package manager".to_string(),
+			};
+			SearchResult {
+				source: SearchSource::Known,
+				package: package.to_string(),
+				add_name: known.name.to_string(),
+				description,
+			}
+		})
+		.collect()
+}
+
+pub fn search_host(pm: HostPackageManager, term: &str) -> Vec<SearchResult> {
+	let args: &[&str] = match pm {
+		HostPackageManager::Apt => &["apt-cache", "search", "--names-only"],
+		HostPackageManager::Dnf => &["dnf", "search", "-q"],
+		HostPackageManager::Pacman => &["pacman", "-Ss"],
+		HostPackageManager::Zypper => &["zypper", "--quiet", "search"],
+		HostPackageManager::Apk => &["apk", "search", "-v"],
+		HostPackageManager::Brew => &["brew", "search"],
+	};
+	let output = match Command::new(args[0]).args(&args[1..]).arg(term).output() {
+		Ok(output) if output.status.success() => output,
+		_ => return Vec::new(),
+	};
+
+	let mut results: Vec<SearchResult> =
+		parse_host_search(pm, &String::from_utf8_lossy(&output.stdout))
+			.into_iter()
+			.filter(|(package, _)| matches_term(package, term))
+			.map(|(package, description)| SearchResult {
+				source: SearchSource::Host(pm),
+				add_name: known_name_for(pm, &package).unwrap_or_else(|| package.clone()),
+				package,
+				description,
+			})
+			.collect();
+	results.sort_by_key(|r| {
+		let is_dev = r.package.ends_with("-dev") || r.package.ends_with("-devel");
+		(r.add_name == r.package, !is_dev, r.package.clone())
+	});
+	results
+}
+
+pub fn parse_host_search(pm: HostPackageManager, output: &str) -> Vec<(String, String)> {
+	let mut packages = Vec::new();
+	match pm {
+		HostPackageManager::Apt => {
+			for line in output.lines() {
+				if let Some((name, description)) = line.split_once(" - ") {
+					packages.push((name.trim().to_string(), description.trim().to_string()));
+				}
+			}
+		}
+		HostPackageManager::Dnf => {
+			for line in output.lines().filter(|l| !l.starts_with('=')) {
+				if let Some((name, description)) = line.split_once(" : ") {
+					let name = name.trim();
+					let name = name.rsplit_once('.').map_or(name, |(n, _)| n);
+					packages.push((name.to_string(), description.trim().to_string()));
+				}
+			}
+		}
+		HostPackageManager::Pacman => {
+			let mut lines = output.lines().peekable();
+			while let Some(line) = lines.next() {
+				if line.starts_with(' ') {
+					continue;
+				}
+				let name = line
+					.split_whitespace()
+					.next()
+					.and_then(|n| n.rsplit('/').next())
+					.unwrap_or_default();
+				let description = match lines.peek() {
+					Some(next) if next.starts_with(' ') => next.trim().to_string(),
+					_ => String::new(),
+				};
+				if !name.is_empty() {
+					packages.push((name.to_string(), description));
+				}
+			}
+		}
+		HostPackageManager::Zypper => {
+			for line in output.lines() {
+				let columns: Vec<&str> = line.split('|').map(str::trim).collect();
+				if columns.len() >= 3 && columns[1] != "Name" && !columns[1].is_empty() {
+					packages.push((columns[1].to_string(), columns[2].to_string()));
+				}
+			}
+		}
+		HostPackageManager::Apk => {
+			for line in output.lines() {
+				let (versioned, description) = line.split_once(" - ").unwrap_or((line, ""));
+				let mut parts = versioned.trim().rsplitn(3, '-');
+				let name = match (parts.next(), parts.next(), parts.next()) {
+					(Some(_release), Some(_version), Some(name)) => name,
+					_ => continue,
+				};
+				packages.push((name.to_string(), description.trim().to_string()));
+			}
+		}
+		HostPackageManager::Brew => {
+			for line in output.lines() {
+				let name = line.trim();
+				if !name.is_empty() && !name.starts_with("==>") {
+					packages.push((name.to_string(), String::new()));
+				}
+			}
+		}
+	}
+	packages
+}
+
+pub fn search_pkg_config(term: &str) -> Vec<SearchResult> {
+	let output = match Command::new("pkg-config").arg("--list-all").output() {
+		Ok(output) if output.status.success() => output,
+		_ => return Vec::new(),
+	};
+
+	let mut results: Vec<SearchResult> = String::from_utf8_lossy(&output.stdout)
+		.lines()
+		.filter_map(|line| {
+			let (module, description) = line.split_once(char::is_whitespace)?;
+			Some((module.to_string(), description.trim().to_string()))
+		})
+		.filter(|(module, _)| matches_term(module, term))
+		.map(|(module, description)| SearchResult {
+			source: SearchSource::PkgConfig,
+			add_name: KNOWN_PACKAGES
+				.iter()
+				.find(|known| known.pkg_config == module)
+				.map(|known| known.name.to_string())
+				.unwrap_or_else(|| module.clone()),
+			package: module,
+			description,
+		})
+		.collect();
+	results.sort_by(|a, b| a.package.cmp(&b.package));
+	results
+}
+
+pub fn search_conan(term: &str) -> Vec<SearchResult> {
+	if !executable_in_path("conan") {
+		return Vec::new();
+	}
+	let output = match Command::new("conan")
+		.args(["list", &format!("*{}*", term), "--format=json"])
+		.output()
+	{
+		Ok(output) if output.status.success() => output,
+		_ => return Vec::new(),
+	};
+	let root = match JsonValue::parse(&String::from_utf8_lossy(&output.stdout)) {
+		Ok(root) => root,
+		Err(_) => return Vec::new(),
+	};
+
+	let mut results = Vec::new();
+	if let JsonValue::Object(remotes) = root {
+		for (_, recipes) in remotes {
+			if let JsonValue::Object(recipes) = recipes {
+				for (reference, _) in recipes {
+					results.push(SearchResult {
+						source: SearchSource::Conan,
+						package: reference.clone(),
+						add_name: reference,
+						description: "in the local Conan cache".to_string(),
+					});
+				}
+			}
+		}
+	}
+	results
+}
+
+pub fn search_vcpkg(term: &str) -> Result<Vec<SearchResult>> {
+	let root = match env::var(vcpkg::ROOT_ENV).ok().filter(|r| !r.is_empty()) {
+		Some(root) => root,
+		None => return Ok(Vec::new()),
+	};
+	let ports = Path::new(&root).join("ports");
+	if !ports.is_dir() {
+		return Ok(Vec::new());
+	}
+
+	let mut results = Vec::new();
+	for entry in fs::read_dir(&ports)
+		.with_context(|| format!("Failed to read {}", ports.display()))?
+		.flatten()
+	{
+		let name = entry.file_name().to_string_lossy().to_string();
+		if !matches_term(&name, term) {
+			continue;
+		}
+		let manifest = fs::read_to_string(entry.path().join(vcpkg::MANIFEST))
+			.ok()
+			.and_then(|content| JsonValue::parse(&content).ok());
+		let version = manifest.as_ref().and_then(|m| {
+			[
+				"version",
+				"version-semver",
+				"version-date",
+				"version-string",
+			]
+			.iter()
+			.find_map(|key| m.get(key).and_then(JsonValue::as_str))
+		});
+		let description = manifest
+			.as_ref()
+			.and_then(|m| m.get("description"))
+			.and_then(|d| match d {
+				JsonValue::Array(lines) => lines.first().and_then(JsonValue::as_str),
+				other => other.as_str(),
+			})
+			.unwrap_or_default();
+		results.push(SearchResult {
+			source: SearchSource::Vcpkg,
+			package: match version {
+				Some(version) => format!("{} {}", name, version),
+				None => name.clone(),
+			},
+			add_name: name,
+			description: description.to_string(),
+		});
+	}
+	results.sort_by(|a, b| a.add_name.cmp(&b.add_name));
+	Ok(results)
+}
+
+fn known_name_for(pm: HostPackageManager, package: &str) -> Option<String> {
+	KNOWN_PACKAGES
+		.iter()
+		.find(|known| known.package_for(pm) == package)
+		.map(|known| known.name.to_string())
+}
+
+fn matches_term(name: &str, term: &str) -> bool {
+	name.to_lowercase().contains(&term.to_lowercase())
+}
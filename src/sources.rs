@@ -1,69 +1,878 @@
 use anyhow::{Context, Result};
 use std::fs;
-use std::path::Path;
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+
+use crate::cmake_editor::{self, ArgumentKind, Placement};
+use crate::config::{project_name, ProjectConfig};
+use crate::constants::{audit, cmake, makefile, project};
+use crate::dependency_check::source_files;
+use crate::file_handler::join_lines;
+use crate::languages::{Language, LanguageConsts, SourceCounts};
+use crate::makefile_editor::{Anchor, Makefile};
+
+const SOURCE_EXTENSIONS: &[&str] = &["c", "cpp", "cc", "cxx", "S", "s", "m", "mm", "f90"];
+const HEADER_EXTENSIONS: &[&str] = &["h", "hpp", "hh", "hxx"];
+const SOURCE_LIST_COMMANDS: &[&str] = &[
+	"add_executable",
+	"add_library",
+	"target_sources",
+	"set",
+	"list",
+];
+const PATH_PREFIXES: &[&str] = &[
+	"",
+	"${CMAKE_CURRENT_SOURCE_DIR}/",
+	"${CMAKE_SOURCE_DIR}/",
+	"${PROJECT_SOURCE_DIR}/",
+];
 
 pub fn add_sources(source_names: &[&str]) -> Result<()> {
-	if !Path::new("src").exists() {
+	add_sources_with_visibility(source_names, false)
+}
+
+pub fn add_sources_with_visibility(source_names: &[&str], private: bool) -> Result<()> {
+	if !Path::new(project::SRC_DIR).exists() {
 		anyhow::bail!(
 			"src directory not found. Cannot add sources and headers.\n\
 			Maybe try creating a new project or initializing a new project in the current directory"
 		);
 	}
 
-	let src_path = Path::new("src");
-
-	let extension = determine_extension(src_path)?;
+	let (extension, header_extension) = project_extensions("h")?;
+	let language = Language::detect()?;
 
+	let mut added_public = false;
+	let mut added_nested = false;
+	let mut added_c = false;
 	for &source_name in source_names {
-		let source_file = format!("{}.{}", source_name, extension);
-		let source_path = src_path.join(&source_file);
+		let source_extension = explicit_source_extension(source_name, language, extension)?;
+		let source_name =
+			normalize_source_name(source_name, &[source_extension, &header_extension])?;
+		let layout = source_layout(&source_name, source_extension, &header_extension, private);
+		if layout.source.exists() {
+			println!(
+				"Source file {} already exists. Skipping.",
+				layout.source.display()
+			);
+			continue;
+		}
+		if Language::is_assembly(source_extension) || source_extension == "f90" {
+			write_file(
+				&layout.source,
+				&standalone_source_content(&source_name, source_extension),
+			)?;
+			added_nested |= source_name.contains('/');
+			println!("Added source: {}", layout.source.display());
+			continue;
+		}
 
-		if source_path.exists() {
-			println!("Source file {} already exists. Skipping.", source_file);
+		write_file(&layout.source, &source_content(&layout.include))?;
+		if layout.header.exists() {
+			println!(
+				"Header {} already exists. Keeping it.",
+				layout.header.display()
+			);
 		} else {
-			fs::write(&source_path, "")
-				.with_context(|| format!("Failed to create source file {}", source_file))?;
-
-			let header_file = format!("{}.h", source_name);
-			let header_path = src_path.join(&header_file);
-			fs::write(
-				&header_path,
-				format!(
-					"#ifndef {}_H\n#define {}_H\n#endif /* {}_H */",
-					source_name.to_uppercase(),
-					source_name.to_uppercase(),
-					source_name.to_uppercase()
-				),
+			let c_linkage = needs_c_linkage(language, source_extension);
+			write_file(&layout.header, &header_content(&layout.guard, c_linkage))?;
+		}
+		added_public |= !private;
+		added_nested |= source_name.contains('/');
+		added_c |= language == Some(Language::Cpp) && source_extension == "c";
+
+		println!(
+			"Added source: {} (header: {})",
+			layout.source.display(),
+			layout.header.display()
+		);
+	}
+
+	if added_public {
+		ensure_makefile_include_dir()?;
+	}
+	if added_nested {
+		ensure_recursive_makefile_sources(extension)?;
+	}
+	if added_c {
+		ensure_mixed_sources()?;
+	}
+	Ok(())
+}
+
+fn explicit_source_extension(
+	name: &str,
+	language: Option<Language>,
+	project_extension: &'static str,
+) -> Result<&'static str> {
+	let explicit = name.trim().rsplit_once('.').and_then(|(_, extension)| {
+		SOURCE_EXTENSIONS
+			.iter()
+			.find(|known| **known == extension)
+			.copied()
+	});
+	match (language, explicit) {
+		(_, None) => Ok(project_extension),
+		(Some(Language::Mixed), Some(extension)) => Ok(extension),
+		(_, Some(extension)) if Language::is_assembly(extension) => anyhow::bail!(
+			"Cannot add assembly source {} outside a mixed-language project. Set language = \"mixed\" in sticks.toml",
+			name.trim()
+		),
+		(Some(Language::Cpp), Some("c")) => Ok("c"),
+		(Some(Language::CFortran), Some(extension @ ("c" | "f90"))) => Ok(extension),
+		(Some(Language::C | Language::Fortran), Some(extension @ ("c" | "f90")))
+			if extension != project_extension =>
+		{
+			anyhow::bail!(
+				"Cannot add {} to a {} project. Set language = \"c-fortran\" in sticks.toml to build C and Fortran together",
+				name.trim(),
+				language.map(|l| l.to_string()).unwrap_or_default()
+			)
+		}
+		(Some(Language::C), Some(extension))
+			if Language::from_extension(extension) == Some(Language::Cpp) =>
+		{
+			anyhow::bail!(
+			"Cannot add C++ source {} to a C project. Set language = \"cpp\" or \"mixed\" in sticks.toml to build both",
+			name.trim()
 			)
-			.with_context(|| format!("Failed to create header file {}", header_file))?;
+		}
+		_ => Ok(project_extension),
+	}
+}
 
-			println!("Added source: {}", source_file);
+pub struct SourcePair {
+	pub name: String,
+	pub source: Option<PathBuf>,
+	pub header: Option<PathBuf>,
+}
+
+pub fn find_source_pair(name: &str) -> Result<SourcePair> {
+	let name = normalize_source_name(name, audit::SOURCE_EXTENSIONS)?;
+	let src_path = Path::new(project::SRC_DIR);
+	let public_dir = Path::new(project::INCLUDE_DIR).join(project_name());
+	let existing = |dir: &Path, extensions: &[&str]| {
+		extensions
+			.iter()
+			.map(|ext| dir.join(format!("{}.{}", name, ext)))
+			.find(|path| path.is_file())
+	};
+	let source = existing(src_path, SOURCE_EXTENSIONS);
+	let header =
+		existing(&public_dir, HEADER_EXTENSIONS).or_else(|| existing(src_path, HEADER_EXTENSIONS));
+	if source.is_none() && header.is_none() {
+		anyhow::bail!("No source or header named '{}' found", name);
+	}
+	Ok(SourcePair {
+		name,
+		source,
+		header,
+	})
+}
+
+pub fn remove_sources(source_names: &[&str]) -> Result<()> {
+	let pairs = source_names
+		.iter()
+		.map(|name| find_source_pair(name))
+		.collect::<Result<Vec<_>>>()?;
+
+	for pair in pairs {
+		let files: Vec<&PathBuf> = pair.source.iter().chain(pair.header.iter()).collect();
+		for file in &files {
+			fs::remove_file(file)
+				.with_context(|| format!("Failed to remove {}", file.display()))?;
+			remove_empty_dirs(file);
+			update_build_file_lists(file, None)?;
+		}
+		println!(
+			"✓ Removed {}",
+			files
+				.iter()
+				.map(|f| f.display().to_string())
+				.collect::<Vec<_>>()
+				.join(", ")
+		);
+
+		if let Some(header) = &pair.header {
+			for file in project_files()? {
+				let content = fs::read_to_string(&file)
+					.with_context(|| format!("Failed to read {}", file.display()))?;
+				if content
+					.lines()
+					.filter_map(quoted_include)
+					.any(|include| resolve_include(&file, include, header.as_path()).is_some())
+				{
+					println!("⚠️  {} still includes {}", file.display(), header.display());
+				}
+			}
+		}
+	}
+	Ok(())
+}
+
+pub fn move_source(old: &str, new: &str) -> Result<()> {
+	let pair = find_source_pair(old)?;
+	let new_name = normalize_source_name(new, audit::SOURCE_EXTENSIONS)?;
+	if new_name == pair.name {
+		anyhow::bail!("'{}' and '{}' are the same source", old, new);
+	}
+
+	let project = project_name();
+	let src_path = Path::new(project::SRC_DIR);
+	let renamed = |path: &Path| -> PathBuf {
+		let ext = path
+			.extension()
+			.and_then(|e| e.to_str())
+			.unwrap_or_default();
+		let dir = if path.starts_with(src_path) {
+			src_path.to_path_buf()
+		} else {
+			Path::new(project::INCLUDE_DIR).join(&project)
+		};
+		dir.join(format!("{}.{}", new_name, ext))
+	};
+	let moves: Vec<(PathBuf, PathBuf)> = pair
+		.source
+		.iter()
+		.chain(pair.header.iter())
+		.map(|path| (path.clone(), renamed(path)))
+		.collect();
+	for (_, target) in &moves {
+		if target.exists() {
+			anyhow::bail!("{} already exists", target.display());
 		}
 	}
 
+	let originals = project_files()?;
+	for (from, to) in &moves {
+		if let Some(parent) = to.parent() {
+			fs::create_dir_all(parent)
+				.with_context(|| format!("Failed to create {}", parent.display()))?;
+		}
+		fs::rename(from, to)
+			.with_context(|| format!("Failed to move {} to {}", from.display(), to.display()))?;
+	}
+
+	let guards = pair.header.as_ref().map(|header| {
+		let ext = header.extension().and_then(|e| e.to_str()).unwrap_or("h");
+		(
+			header_guard(&format!("{}/{}", project, pair.name), ext),
+			header_guard(&format!("{}/{}", project, new_name), ext),
+		)
+	});
+	for original in originals {
+		let current = moves
+			.iter()
+			.find(|(from, _)| *from == original)
+			.map(|(_, to)| to.clone())
+			.unwrap_or_else(|| original.clone());
+		let content = fs::read_to_string(&current)
+			.with_context(|| format!("Failed to read {}", current.display()))?;
+		let mut updated = rewrite_includes(&content, &original, &current, &moves);
+		if let Some((old_guard, new_guard)) = &guards {
+			updated = replace_word(&updated, old_guard, new_guard);
+		}
+		if updated != content {
+			fs::write(&current, updated)
+				.with_context(|| format!("Failed to write {}", current.display()))?;
+		}
+	}
+
+	for (from, to) in &moves {
+		remove_empty_dirs(from);
+		update_build_file_lists(from, Some(to))?;
+		println!("✓ Moved {} to {}", from.display(), to.display());
+	}
 	Ok(())
 }
 
-fn determine_extension(src_path: &Path) -> Result<&'static str> {
-	let source_file = fs::read_dir(src_path)?
-		.filter_map(|entry| {
-			let entry = entry.ok()?;
-			let path = entry.path();
-			if path.is_file() {
-				path.extension()
-					.map(|ext| ext.to_string_lossy().to_string())
+pub struct SourceLayout {
+	pub source: PathBuf,
+	pub header: PathBuf,
+	pub include: String,
+	pub guard: String,
+}
+
+pub fn source_layout(
+	name: &str,
+	extension: &str,
+	header_extension: &str,
+	private: bool,
+) -> SourceLayout {
+	let src_path = Path::new(project::SRC_DIR);
+	let header_file = format!("{}.{}", name, header_extension);
+	let project = project_name();
+	let (header, include) = if private {
+		let file_name = header_file.rsplit('/').next().unwrap_or(&header_file);
+		(src_path.join(&header_file), file_name.to_string())
+	} else {
+		let include = format!("{}/{}", project, header_file);
+		(Path::new(project::INCLUDE_DIR).join(&include), include)
+	};
+	SourceLayout {
+		source: src_path.join(format!("{}.{}", name, extension)),
+		header,
+		include,
+		guard: header_guard(&format!("{}/{}", project, name), header_extension),
+	}
+}
+
+pub fn normalize_source_name(name: &str, extensions: &[&str]) -> Result<String> {
+	let trimmed = name.trim().trim_start_matches("./");
+	let trimmed = trimmed
+		.strip_prefix(&format!("{}/", project::SRC_DIR))
+		.unwrap_or(trimmed);
+	let trimmed = extensions
+		.iter()
+		.find_map(|ext| trimmed.strip_suffix(&format!(".{}", ext)))
+		.unwrap_or(trimmed);
+
+	let segments: Vec<&str> = trimmed.split('/').collect();
+	let valid = !name.trim().starts_with('/')
+		&& segments.iter().all(|segment| {
+			!segment.is_empty()
+				&& *segment != "."
+				&& *segment != ".."
+				&& segment
+					.chars()
+					.all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+		});
+	if !valid {
+		anyhow::bail!(
+			"Invalid source name: {}. Use a relative path such as 'net/socket'",
+			name
+		);
+	}
+	Ok(segments.join("/"))
+}
+
+pub fn project_extensions(default_header: &str) -> Result<(&'static str, String)> {
+	let extension = match source_extension()? {
+		Some(extension) => extension,
+		None => {
+			eprintln!("No existing source files found in src/. Defaulting to .c extension.");
+			"c"
+		}
+	};
+	Ok((extension, header_extension(default_header)?))
+}
+
+pub fn source_extension() -> Result<Option<&'static str>> {
+	Ok(Language::detect()?.map(|language| language.extension()))
+}
+
+pub fn header_extension(default: &str) -> Result<String> {
+	Ok(ProjectConfig::load()?
+		.and_then(|c| c.header_extension)
+		.unwrap_or_else(|| default.to_string()))
+}
+
+pub fn write_file(path: &Path, content: &str) -> Result<()> {
+	if let Some(parent) = path.parent() {
+		fs::create_dir_all(parent)
+			.with_context(|| format!("Failed to create {}", parent.display()))?;
+	}
+	fs::write(path, content).with_context(|| format!("Failed to create {}", path.display()))
+}
+
+fn standalone_source_content(name: &str, extension: &str) -> String {
+	if extension != "f90" {
+		return "\t.text\n\n\t.section .note.GNU-stack,\"\",%progbits\n".to_string();
+	}
+	let file_name = name.rsplit('/').next().unwrap_or(name);
+	let mut module: String = file_name
+		.chars()
+		.map(|c| {
+			if c.is_ascii_alphanumeric() {
+				c.to_ascii_lowercase()
 			} else {
-				None
+				'_'
 			}
 		})
-		.next();
+		.collect();
+	if !module.starts_with(|c: char| c.is_ascii_alphabetic()) {
+		module.insert_str(0, "m_");
+	}
+	format!(
+		"module {module}\n    implicit none\n    private\ncontains\nend module {module}\n",
+		module = module
+	)
+}
 
-	match source_file.as_deref() {
-		Some("c") => Ok("c"),
-		Some("cpp") => Ok("cpp"),
-		_ => {
-			eprintln!("No existing source files found in src/. Defaulting to .c extension.");
-			Ok("c")
+fn source_content(include: &str) -> String {
+	format!("#include \"{}\"\n", include)
+}
+
+fn needs_c_linkage(language: Option<Language>, source_extension: &str) -> bool {
+	matches!(language, Some(Language::Cpp) | Some(Language::Mixed)) && source_extension == "c"
+}
+
+fn header_content(guard: &str, c_linkage: bool) -> String {
+	if c_linkage {
+		return format!(
+			"#ifndef {}\n#define {}\n\n#ifdef __cplusplus\nextern \"C\" {{\n#endif\n\n#ifdef __cplusplus\n}}\n#endif\n\n#endif /* {} */\n",
+			guard, guard, guard
+		);
+	}
+	format!(
+		"#ifndef {}\n#define {}\n\n#endif /* {} */\n",
+		guard, guard, guard
+	)
+}
+
+pub fn header_guard(path: &str, extension: &str) -> String {
+	let mut guard: String = path
+		.chars()
+		.map(|c| {
+			if c.is_ascii_alphanumeric() {
+				c.to_ascii_uppercase()
+			} else {
+				'_'
+			}
+		})
+		.collect();
+	if guard.starts_with(|c: char| c.is_ascii_digit()) {
+		guard.insert(0, '_');
+	}
+	guard.push('_');
+	guard.push_str(&extension.to_uppercase());
+	guard
+}
+
+pub fn ensure_makefile_include_dir() -> Result<()> {
+	if !Path::new(makefile::FILENAME).exists() {
+		return Ok(());
+	}
+	let content = fs::read_to_string(makefile::FILENAME)
+		.with_context(|| format!("Failed to read {}", makefile::FILENAME))?;
+	let mut editor = Makefile::parse(&content);
+	let flag = format!("-I{}", project::INCLUDE_DIR);
+	let value = match editor.variable("CFLAGS") {
+		Some(cflags) if !cflags.value.split_whitespace().any(|f| f == flag) => {
+			format!("{} {}", cflags.value, flag).trim().to_string()
+		}
+		_ => return Ok(()),
+	};
+	editor.set_variable("CFLAGS", &value);
+	fs::write(makefile::FILENAME, editor.render())
+		.with_context(|| format!("Failed to write updated {}", makefile::FILENAME))?;
+	println!("✓ Added {} to CFLAGS in {}", flag, makefile::FILENAME);
+	Ok(())
+}
+
+pub fn ensure_recursive_makefile_sources(extension: &str) -> Result<()> {
+	if !Path::new(makefile::FILENAME).exists() {
+		return Ok(());
+	}
+	let content = fs::read_to_string(makefile::FILENAME)
+		.with_context(|| format!("Failed to read {}", makefile::FILENAME))?;
+	let mut editor = Makefile::parse(&content);
+
+	let flat = format!("$(wildcard $(SRC_DIR)/*.{})", extension);
+	let mut changed = false;
+	if editor
+		.variable("SRCS")
+		.is_some_and(|srcs| srcs.value == flat)
+	{
+		let recursive = format!("$(shell find $(SRC_DIR) -name '*.{}')", extension);
+		changed |= editor.set_variable("SRCS", &recursive);
+	}
+	if let Some(rule) = editor.rule_mut(makefile::OBJECT_TARGET) {
+		for line in rule.recipe.iter_mut() {
+			if line.trim() == "@mkdir -p $(BUILD_DIR)" {
+				*line = format!("\t{}", makefile::OBJECT_MKDIR);
+				changed = true;
+			}
+		}
+	}
+
+	if changed {
+		fs::write(makefile::FILENAME, editor.render())
+			.with_context(|| format!("Failed to write updated {}", makefile::FILENAME))?;
+		println!(
+			"✓ Updated {} to build sources in subdirectories of {}/",
+			makefile::FILENAME,
+			project::SRC_DIR
+		);
+	}
+	Ok(())
+}
+
+pub fn ensure_mixed_sources() -> Result<()> {
+	if Language::detect()? != Some(Language::Cpp)
+		|| SourceCounts::scan(Path::new(project::SRC_DIR))?.c == 0
+	{
+		return Ok(());
+	}
+	if Path::new(makefile::FILENAME).exists() {
+		ensure_makefile_c_sources()?;
+	}
+	if Path::new(cmake::FILENAME).exists() {
+		ensure_cmake_c_sources()?;
+	}
+	Ok(())
+}
+
+fn ensure_makefile_c_sources() -> Result<()> {
+	let content = fs::read_to_string(makefile::FILENAME)
+		.with_context(|| format!("Failed to read {}", makefile::FILENAME))?;
+	let mut editor = Makefile::parse(&content);
+	if editor.region(makefile::C_SOURCES_REGION).is_some() {
+		return Ok(());
+	}
+	let profile = ProjectConfig::load()?
+		.map(|config| config.profile)
+		.unwrap_or_default();
+	let body = format!(
+		"C_COMPILER = {}\n\
+		C_SRCS = $(shell find $(SRC_DIR) -name '*.c')\n\
+		OBJS += $(C_SRCS:$(SRC_DIR)/%.c=$(BUILD_DIR)/%.o)\n\
+		\n\
+		$(BUILD_DIR)/%.o: $(SRC_DIR)/%.c\n\
+		\t{}\n\
+		\t$(C_COMPILER) $(CFLAGS) -c $< -o $@",
+		profile.compiler(Language::C).command_line(),
+		makefile::OBJECT_MKDIR
+	);
+	editor.set_region(
+		makefile::C_SOURCES_REGION,
+		&body,
+		Anchor::AfterVariable("OBJS"),
+	);
+	fs::write(makefile::FILENAME, editor.render())
+		.with_context(|| format!("Failed to write updated {}", makefile::FILENAME))?;
+	println!("✓ Added C sources to {}", makefile::FILENAME);
+	Ok(())
+}
+
+fn ensure_cmake_c_sources() -> Result<()> {
+	let content = fs::read_to_string(cmake::FILENAME)
+		.with_context(|| format!("Failed to read {}", cmake::FILENAME))?;
+	let listfile = cmake_editor::parse(&content);
+	let mut lines: Vec<String> = content.lines().map(String::from).collect();
+	let mut changed = false;
+
+	if let Some(command) = listfile.command("project") {
+		let values = command.values();
+		if values.contains(&"CXX") && !values.contains(&"C") {
+			for line in lines[command.start..=command.end].iter_mut() {
+				let updated = replace_word(line, "CXX", "C CXX");
+				if updated != *line {
+					*line = updated;
+					changed = true;
+					break;
+				}
+			}
+		}
+	}
+
+	let glob = listfile.user_commands().find(|command| {
+		let values = command.values();
+		command.is("file")
+			&& matches!(values.first(), Some(&"GLOB") | Some(&"GLOB_RECURSE"))
+			&& values.get(1) == Some(&"SOURCES")
+	});
+	let content = match glob {
+		Some(command) if command.values().contains(&cmake::C_SOURCES_GLOB) => join_lines(&lines),
+		Some(command) => {
+			let line = &mut lines[command.end];
+			if let Some(close) = line.rfind(')') {
+				let separator = if line[..close].trim().is_empty() {
+					""
+				} else {
+					" "
+				};
+				line.insert_str(
+					close,
+					&format!("{}\"{}\"", separator, cmake::C_SOURCES_GLOB),
+				);
+				changed = true;
+			}
+			join_lines(&lines)
+		}
+		None if listfile.region(cmake::C_SOURCES_REGION).is_none() => {
+			changed = true;
+			cmake_editor::set_region(
+				&join_lines(&lines),
+				cmake::C_SOURCES_REGION,
+				&format!(
+					"file(GLOB_RECURSE C_SOURCES CONFIGURE_DEPENDS \"{}\")\n\
+					target_sources(${{PROJECT_NAME}} PRIVATE ${{C_SOURCES}})",
+					cmake::C_SOURCES_GLOB
+				),
+				Placement::AfterTarget,
+			)
+		}
+		None => join_lines(&lines),
+	};
+
+	if changed {
+		fs::write(cmake::FILENAME, content)
+			.with_context(|| format!("Failed to write updated {}", cmake::FILENAME))?;
+		println!("✓ Added C sources to {}", cmake::FILENAME);
+	}
+	Ok(())
+}
+
+fn project_files() -> Result<Vec<PathBuf>> {
+	let mut files = Vec::new();
+	for dir in [project::SRC_DIR, project::INCLUDE_DIR, "tests"] {
+		files.extend(source_files(Path::new(dir))?);
+	}
+	Ok(files)
+}
+
+fn quoted_include(line: &str) -> Option<&str> {
+	let rest = line.trim_start().strip_prefix('#')?.trim_start();
+	let rest = rest.strip_prefix("include")?.trim_start();
+	rest.strip_prefix('"')?.split('"').next()
+}
+
+fn resolve_include(file: &Path, include: &str, target: &Path) -> Option<bool> {
+	let relative = normalize_path(&file.parent()?.join(include));
+	if relative == target {
+		return Some(false);
+	}
+	(normalize_path(&Path::new(project::INCLUDE_DIR).join(include)) == target).then_some(true)
+}
+
+fn rewrite_includes(
+	content: &str,
+	original: &Path,
+	current: &Path,
+	moves: &[(PathBuf, PathBuf)],
+) -> String {
+	let original_dir = original.parent().unwrap_or(Path::new(""));
+	let current_dir = current.parent().unwrap_or(Path::new(""));
+	let include_dir = Path::new(project::INCLUDE_DIR);
+	let existed = |path: &Path| {
+		moves.iter().any(|(from, _)| from == path)
+			|| (path.is_file() && !moves.iter().any(|(_, to)| to == path))
+	};
+
+	let mut lines: Vec<String> = Vec::new();
+	for line in content.split('\n') {
+		let include = match quoted_include(line) {
+			Some(include) => include,
+			None => {
+				lines.push(line.to_string());
+				continue;
+			}
+		};
+		let relative = normalize_path(&original_dir.join(include));
+		let (resolved, via_include_dir) = if existed(&relative) {
+			(relative, false)
+		} else {
+			(normalize_path(&include_dir.join(include)), true)
+		};
+		let moved = moves.iter().find(|(from, _)| *from == resolved);
+		if moved.is_none() && original == current {
+			lines.push(line.to_string());
+			continue;
+		}
+		let target = moved.map(|(_, to)| to.clone()).unwrap_or(resolved);
+		let spelled = match target.strip_prefix(include_dir) {
+			Ok(inside) if via_include_dir => path_string(inside),
+			_ => relative_path(current_dir, &target),
+		};
+		lines.push(line.replacen(&format!("\"{}\"", include), &format!("\"{}\"", spelled), 1));
+	}
+	lines.join("\n")
+}
+
+fn normalize_path(path: &Path) -> PathBuf {
+	let mut normalized = PathBuf::new();
+	for component in path.components() {
+		match component {
+			Component::CurDir => {}
+			Component::ParentDir => {
+				normalized.pop();
+			}
+			other => normalized.push(other),
+		}
+	}
+	normalized
+}
+
+fn relative_path(from_dir: &Path, to: &Path) -> String {
+	let from: Vec<Component> = from_dir.components().collect();
+	let to_components: Vec<Component> = to.components().collect();
+	let common = from
+		.iter()
+		.zip(to_components.iter())
+		.take_while(|(a, b)| a == b)
+		.count();
+	let mut parts: Vec<String> = vec!["..".to_string(); from.len() - common];
+	parts.extend(
+		to_components[common..]
+			.iter()
+			.map(|c| c.as_os_str().to_string_lossy().to_string()),
+	);
+	parts.join("/")
+}
+
+fn path_string(path: &Path) -> String {
+	path.components()
+		.map(|c| c.as_os_str().to_string_lossy().to_string())
+		.collect::<Vec<_>>()
+		.join("/")
+}
+
+fn replace_word(content: &str, old: &str, new: &str) -> String {
+	let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+	let mut output = String::new();
+	let mut rest = content;
+	while let Some(index) = rest.find(old) {
+		let before = rest[..index].chars().last().or(output.chars().last());
+		let after = rest[index + old.len()..].chars().next();
+		output.push_str(&rest[..index]);
+		if before.is_some_and(is_word) || after.is_some_and(is_word) {
+			output.push_str(old);
+		} else {
+			output.push_str(new);
+		}
+		rest = &rest[index + old.len()..];
+	}
+	output.push_str(rest);
+	output
+}
+
+fn remove_empty_dirs(file: &Path) {
+	let roots = [
+		Path::new(project::SRC_DIR).to_path_buf(),
+		Path::new(project::INCLUDE_DIR).join(project_name()),
+	];
+	let mut dir = file.parent();
+	while let Some(current) = dir {
+		if roots.iter().any(|root| current == root) || fs::remove_dir(current).is_err() {
+			break;
+		}
+		dir = current.parent();
+	}
+}
+
+fn update_build_file_lists(old: &Path, new: Option<&Path>) -> Result<()> {
+	let spellings = path_spellings(&path_string(old), new.map(path_string));
+	for file in [makefile::FILENAME, cmake::FILENAME] {
+		if !Path::new(file).exists() {
+			continue;
+		}
+		let content =
+			fs::read_to_string(file).with_context(|| format!("Failed to read {}", file))?;
+		let updated = if file == makefile::FILENAME {
+			update_makefile_lists(&content, &spellings)
+		} else {
+			update_cmake_lists(&content, &spellings)
+		};
+		if updated != content {
+			fs::write(file, updated)
+				.with_context(|| format!("Failed to write updated {}", file))?;
+			println!("✓ Updated source list in {}", file);
+		}
+	}
+	Ok(())
+}
+
+fn path_spellings(old: &str, new: Option<String>) -> Vec<(String, Option<String>)> {
+	let mut spellings: Vec<(String, Option<String>)> = PATH_PREFIXES
+		.iter()
+		.map(|prefix| {
+			(
+				format!("{}{}", prefix, old),
+				new.as_ref().map(|n| format!("{}{}", prefix, n)),
+			)
+		})
+		.collect();
+	if let Some(rest) = old.strip_prefix(&format!("{}/", project::SRC_DIR)) {
+		let replacement =
+			new.as_ref().map(
+				|n| match n.strip_prefix(&format!("{}/", project::SRC_DIR)) {
+					Some(rest) => format!("$(SRC_DIR)/{}", rest),
+					None => n.clone(),
+				},
+			);
+		spellings.push((format!("$(SRC_DIR)/{}", rest), replacement));
+	}
+	spellings
+}
+
+fn update_makefile_lists(content: &str, spellings: &[(String, Option<String>)]) -> String {
+	let mut editor = Makefile::parse(content);
+	for variable in editor.variables_mut() {
+		let mut changed = false;
+		let mut tokens = Vec::new();
+		for token in variable.value.split_whitespace() {
+			match spellings.iter().find(|(spelled, _)| token == spelled) {
+				Some((_, Some(replacement))) => tokens.push(replacement.as_str()),
+				Some((_, None)) => {}
+				None => {
+					tokens.push(token);
+					continue;
+				}
+			}
+			changed = true;
+		}
+		if changed {
+			let value = tokens.join(" ");
+			variable.set_value(&value);
+		}
+	}
+	editor.render()
+}
+
+fn update_cmake_lists(content: &str, spellings: &[(String, Option<String>)]) -> String {
+	let mut edits: Vec<(Range<usize>, Option<String>)> = Vec::new();
+	for command in cmake_editor::parse(content)
+		.commands
+		.iter()
+		.filter(|command| SOURCE_LIST_COMMANDS.iter().any(|name| command.is(name)))
+	{
+		for argument in &command.arguments {
+			if argument.kind == ArgumentKind::Bracket {
+				continue;
+			}
+			if let Some((_, replacement)) = spellings
+				.iter()
+				.find(|(spelled, _)| argument.value == *spelled)
+			{
+				let replacement = replacement.as_ref().map(|r| match argument.kind {
+					ArgumentKind::Quoted => format!("\"{}\"", r),
+					_ => r.clone(),
+				});
+				edits.push((argument.span.clone(), replacement));
+			}
+		}
+	}
+
+	let mut output = content.to_string();
+	for (span, replacement) in edits.into_iter().rev() {
+		match replacement {
+			Some(replacement) => output.replace_range(span, &replacement),
+			None => {
+				let line_start = output[..span.start].rfind('\n').map_or(0, |i| i + 1);
+				let line_end = output[span.end..]
+					.find('\n')
+					.map_or(output.len(), |i| span.end + i);
+				let own_line = output[line_start..span.start].trim().is_empty()
+					&& output[span.end..line_end].trim().is_empty();
+				if own_line {
+					let end = (line_end + 1).min(output.len());
+					output.replace_range(line_start..end, "");
+				} else {
+					let start = output[..span.start].trim_end_matches([' ', '\t']).len();
+					let range = if start == line_start {
+						let trailing = output[span.end..line_end].len()
+							- output[span.end..line_end]
+								.trim_start_matches([' ', '\t'])
+								.len();
+						span.start..span.end + trailing
+					} else {
+						start..span.end
+					};
+					output.replace_range(range, "");
+				}
+			}
 		}
 	}
+	output
 }
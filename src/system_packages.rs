@@ -0,0 +1,603 @@
+use std::path::Path;
+use std::process::Command;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HostPackageManager {
+	Apt,
+	Dnf,
+	Pacman,
+	Zypper,
+	Apk,
+	Brew,
+}
+
+pub const ALL_HOST_PACKAGE_MANAGERS: [HostPackageManager; 6] = [
+	HostPackageManager::Apt,
+	HostPackageManager::Dnf,
+	HostPackageManager::Pacman,
+	HostPackageManager::Zypper,
+	HostPackageManager::Apk,
+	HostPackageManager::Brew,
+];
+
+impl std::fmt::Display for HostPackageManager {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.as_str())
+	}
+}
+
+impl FromStr for HostPackageManager {
+	type Err = anyhow::Error;
+
+	fn from_str(input: &str) -> Result<HostPackageManager, Self::Err> {
+		match input.to_lowercase().as_str() {
+			"apt" | "apt-get" | "dpkg" => Ok(HostPackageManager::Apt),
+			"dnf" | "yum" | "rpm" => Ok(HostPackageManager::Dnf),
+			"pacman" => Ok(HostPackageManager::Pacman),
+			"zypper" => Ok(HostPackageManager::Zypper),
+			"apk" => Ok(HostPackageManager::Apk),
+			"brew" => Ok(HostPackageManager::Brew),
+			_ => anyhow::bail!(
+				"Unsupported package manager: {}. Use apt, dnf, pacman, zypper, apk or brew",
+				input
+			),
+		}
+	}
+}
+
+impl HostPackageManager {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			HostPackageManager::Apt => "apt",
+			HostPackageManager::Dnf => "dnf",
+			HostPackageManager::Pacman => "pacman",
+			HostPackageManager::Zypper => "zypper",
+			HostPackageManager::Apk => "apk",
+			HostPackageManager::Brew => "brew",
+		}
+	}
+
+	pub fn executable(&self) -> &'static str {
+		match self {
+			HostPackageManager::Apt => "apt-get",
+			other => other.as_str(),
+		}
+	}
+
+	pub fn install_command(&self) -> &'static str {
+		match self {
+			HostPackageManager::Apt => "apt-get install -y",
+			HostPackageManager::Dnf => "dnf install -y",
+			HostPackageManager::Pacman => "pacman -S --needed --noconfirm",
+			HostPackageManager::Zypper => "zypper install -y",
+			HostPackageManager::Apk => "apk add",
+			HostPackageManager::Brew => "brew install",
+		}
+	}
+
+	pub fn needs_root(&self) -> bool {
+		!matches!(self, HostPackageManager::Brew)
+	}
+
+	pub fn installed_version(&self, package: &str) -> Option<String> {
+		let (program, args): (&str, Vec<&str>) = match self {
+			HostPackageManager::Apt => ("dpkg-query", vec!["-W", "-f=${Status}|${Version}"]),
+			HostPackageManager::Dnf | HostPackageManager::Zypper => {
+				("rpm", vec!["-q", "--qf", "installed|%{VERSION}-%{RELEASE}"])
+			}
+			HostPackageManager::Pacman => ("pacman", vec!["-Q"]),
+			HostPackageManager::Apk => ("apk", vec!["list", "--installed"]),
+			HostPackageManager::Brew => ("brew", vec!["list", "--versions"]),
+		};
+		let output = Command::new(program)
+			.args(args)
+			.arg(package)
+			.output()
+			.ok()?;
+		if !output.status.success() {
+			return None;
+		}
+		let stdout = String::from_utf8_lossy(&output.stdout);
+		let line = stdout.lines().next()?.trim();
+
+		let version = match self {
+			HostPackageManager::Apt | HostPackageManager::Dnf | HostPackageManager::Zypper => {
+				let (status, version) = line.split_once('|')?;
+				if !status.ends_with("installed") || status.contains("not-installed") {
+					return None;
+				}
+				version.to_string()
+			}
+			HostPackageManager::Pacman | HostPackageManager::Brew => {
+				line.split_whitespace().last()?.to_string()
+			}
+			HostPackageManager::Apk => line
+				.split_whitespace()
+				.next()?
+				.strip_prefix(package)?
+				.strip_prefix('-')?
+				.to_string(),
+		};
+		if version.is_empty() {
+			None
+		} else {
+			Some(version)
+		}
+	}
+
+	pub fn owning_package(&self, path: &Path) -> Option<String> {
+		if let HostPackageManager::Brew = self {
+			let mut components = path.components().map(|c| c.as_os_str().to_string_lossy());
+			components.find(|c| c == "Cellar")?;
+			return components.next().map(|c| c.to_string());
+		}
+
+		let (program, args): (&str, &[&str]) = match self {
+			HostPackageManager::Apt => ("dpkg", &["-S"]),
+			HostPackageManager::Dnf | HostPackageManager::Zypper => {
+				("rpm", &["-qf", "--qf", "%{NAME}\\n"])
+			}
+			HostPackageManager::Pacman => ("pacman", &["-Qqo"]),
+			HostPackageManager::Apk => ("apk", &["info", "-q", "--who-owns"]),
+			HostPackageManager::Brew => return None,
+		};
+		let output = Command::new(program).args(args).arg(path).output().ok()?;
+		if !output.status.success() {
+			return None;
+		}
+		let stdout = String::from_utf8_lossy(&output.stdout);
+		let line = stdout.lines().next()?.trim();
+
+		let package = match self {
+			HostPackageManager::Apt => line.split(": ").next()?.split(':').next()?,
+			HostPackageManager::Apk => {
+				let versioned = line.rsplit(' ').next()?;
+				let mut parts = versioned.rsplitn(3, '-');
+				parts.next()?;
+				parts.next()?;
+				parts.next()?
+			}
+			_ => line,
+		};
+		if package.is_empty() {
+			None
+		} else {
+			Some(package.to_string())
+		}
+	}
+
+	pub fn detect() -> Option<HostPackageManager> {
+		ALL_HOST_PACKAGE_MANAGERS
+			.into_iter()
+			.find(|pm| executable_in_path(pm.executable()))
+	}
+}
+
+pub struct KnownPackage {
+	pub name: &'static str,
+	pub aliases: &'static [&'static str],
+	pub apt: &'static str,
+	pub dnf: &'static str,
+	pub pacman: &'static str,
+	pub zypper: &'static str,
+	pub apk: &'static str,
+	pub brew: &'static str,
+	pub pkg_config: &'static str,
+	pub header: &'static str,
+	pub cmake_package: &'static str,
+	pub cmake_targets: &'static [&'static str],
+}
+
+impl KnownPackage {
+	pub fn package_for(&self, pm: HostPackageManager) -> &'static str {
+		match pm {
+			HostPackageManager::Apt => self.apt,
+			HostPackageManager::Dnf => self.dnf,
+			HostPackageManager::Pacman => self.pacman,
+			HostPackageManager::Zypper => self.zypper,
+			HostPackageManager::Apk => self.apk,
+			HostPackageManager::Brew => self.brew,
+		}
+	}
+}
+
+pub const KNOWN_PACKAGES: &[KnownPackage] = &[
+	KnownPackage {
+		name: "libcurl",
+		aliases: &["curl"],
+		apt: "libcurl4-openssl-dev",
+		dnf: "libcurl-devel",
+		pacman: "curl",
+		zypper: "libcurl-devel",
+		apk: "curl-dev",
+		brew: "curl",
+		pkg_config: "libcurl",
+		header: "curl/curl.h",
+		cmake_package: "CURL",
+		cmake_targets: &["CURL::libcurl"],
+	},
+	KnownPackage {
+		name: "openssl",
+		aliases: &["ssl", "libssl"],
+		apt: "libssl-dev",
+		dnf: "openssl-devel",
+		pacman: "openssl",
+		zypper: "libopenssl-devel",
+		apk: "openssl-dev",
+		brew: "openssl@3",
+		pkg_config: "openssl",
+		header: "openssl/ssl.h",
+		cmake_package: "OpenSSL",
+		cmake_targets: &["OpenSSL::SSL", "OpenSSL::Crypto"],
+	},
+	KnownPackage {
+		name: "zlib",
+		aliases: &["libz"],
+		apt: "zlib1g-dev",
+		dnf: "zlib-devel",
+		pacman: "zlib",
+		zypper: "zlib-devel",
+		apk: "zlib-dev",
+		brew: "zlib",
+		pkg_config: "zlib",
+		header: "zlib.h",
+		cmake_package: "ZLIB",
+		cmake_targets: &["ZLIB::ZLIB"],
+	},
+	KnownPackage {
+		name: "sqlite3",
+		aliases: &["sqlite", "libsqlite3"],
+		apt: "libsqlite3-dev",
+		dnf: "sqlite-devel",
+		pacman: "sqlite",
+		zypper: "sqlite3-devel",
+		apk: "sqlite-dev",
+		brew: "sqlite",
+		pkg_config: "sqlite3",
+		header: "sqlite3.h",
+		cmake_package: "SQLite3",
+		cmake_targets: &["SQLite::SQLite3"],
+	},
+	KnownPackage {
+		name: "pthread",
+		aliases: &["pthreads", "threads"],
+		apt: "",
+		dnf: "",
+		pacman: "",
+		zypper: "",
+		apk: "",
+		brew: "",
+		pkg_config: "",
+		header: "pthread.h",
+		cmake_package: "Threads",
+		cmake_targets: &["Threads::Threads"],
+	},
+	KnownPackage {
+		name: "ncurses",
+		aliases: &["curses"],
+		apt: "libncurses-dev",
+		dnf: "ncurses-devel",
+		pacman: "ncurses",
+		zypper: "ncurses-devel",
+		apk: "ncurses-dev",
+		brew: "ncurses",
+		pkg_config: "ncurses",
+		header: "ncurses.h",
+		cmake_package: "",
+		cmake_targets: &[],
+	},
+	KnownPackage {
+		name: "readline",
+		aliases: &["libreadline"],
+		apt: "libreadline-dev",
+		dnf: "readline-devel",
+		pacman: "readline",
+		zypper: "readline-devel",
+		apk: "readline-dev",
+		brew: "readline",
+		pkg_config: "readline",
+		header: "readline/readline.h",
+		cmake_package: "",
+		cmake_targets: &[],
+	},
+	KnownPackage {
+		name: "sdl2",
+		aliases: &["SDL2", "libsdl2"],
+		apt: "libsdl2-dev",
+		dnf: "SDL2-devel",
+		pacman: "sdl2",
+		zypper: "libSDL2-devel",
+		apk: "sdl2-dev",
+		brew: "sdl2",
+		pkg_config: "sdl2",
+		header: "SDL.h",
+		cmake_package: "SDL2",
+		cmake_targets: &["SDL2::SDL2"],
+	},
+	KnownPackage {
+		name: "glfw",
+		aliases: &["glfw3", "libglfw"],
+		apt: "libglfw3-dev",
+		dnf: "glfw-devel",
+		pacman: "glfw",
+		zypper: "libglfw-devel",
+		apk: "glfw-dev",
+		brew: "glfw",
+		pkg_config: "glfw3",
+		header: "GLFW/glfw3.h",
+		cmake_package: "glfw3",
+		cmake_targets: &["glfw"],
+	},
+	KnownPackage {
+		name: "libpng",
+		aliases: &["png"],
+		apt: "libpng-dev",
+		dnf: "libpng-devel",
+		pacman: "libpng",
+		zypper: "libpng16-devel",
+		apk: "libpng-dev",
+		brew: "libpng",
+		pkg_config: "libpng",
+		header: "png.h",
+		cmake_package: "PNG",
+		cmake_targets: &["PNG::PNG"],
+	},
+	KnownPackage {
+		name: "libxml2",
+		aliases: &["xml2"],
+		apt: "libxml2-dev",
+		dnf: "libxml2-devel",
+		pacman: "libxml2",
+		zypper: "libxml2-devel",
+		apk: "libxml2-dev",
+		brew: "libxml2",
+		pkg_config: "libxml-2.0",
+		header: "libxml/parser.h",
+		cmake_package: "LibXml2",
+		cmake_targets: &["LibXml2::LibXml2"],
+	},
+	KnownPackage {
+		name: "libuv",
+		aliases: &["uv"],
+		apt: "libuv1-dev",
+		dnf: "libuv-devel",
+		pacman: "libuv",
+		zypper: "libuv-devel",
+		apk: "libuv-dev",
+		brew: "libuv",
+		pkg_config: "libuv",
+		header: "uv.h",
+		cmake_package: "",
+		cmake_targets: &[],
+	},
+	KnownPackage {
+		name: "libpq",
+		aliases: &["postgresql", "pq"],
+		apt: "libpq-dev",
+		dnf: "libpq-devel",
+		pacman: "postgresql-libs",
+		zypper: "postgresql-devel",
+		apk: "libpq-dev",
+		brew: "libpq",
+		pkg_config: "libpq",
+		header: "libpq-fe.h",
+		cmake_package: "PostgreSQL",
+		cmake_targets: &["PostgreSQL::PostgreSQL"],
+	},
+	KnownPackage {
+		name: "jansson",
+		aliases: &["libjansson"],
+		apt: "libjansson-dev",
+		dnf: "jansson-devel",
+		pacman: "jansson",
+		zypper: "libjansson-devel",
+		apk: "jansson-dev",
+		brew: "jansson",
+		pkg_config: "jansson",
+		header: "jansson.h",
+		cmake_package: "",
+		cmake_targets: &[],
+	},
+	KnownPackage {
+		name: "fmt",
+		aliases: &["libfmt"],
+		apt: "libfmt-dev",
+		dnf: "fmt-devel",
+		pacman: "fmt",
+		zypper: "fmt-devel",
+		apk: "fmt-dev",
+		brew: "fmt",
+		pkg_config: "fmt",
+		header: "fmt/core.h",
+		cmake_package: "fmt",
+		cmake_targets: &["fmt::fmt"],
+	},
+	KnownPackage {
+		name: "boost",
+		aliases: &["libboost"],
+		apt: "libboost-all-dev",
+		dnf: "boost-devel",
+		pacman: "boost",
+		zypper: "boost-devel",
+		apk: "boost-dev",
+		brew: "boost",
+		pkg_config: "",
+		header: "boost/version.hpp",
+		cmake_package: "Boost",
+		cmake_targets: &["Boost::boost"],
+	},
+	KnownPackage {
+		name: "gtest",
+		aliases: &["googletest"],
+		apt: "libgtest-dev",
+		dnf: "gtest-devel",
+		pacman: "gtest",
+		zypper: "gtest",
+		apk: "gtest-dev",
+		brew: "googletest",
+		pkg_config: "gtest",
+		header: "gtest/gtest.h",
+		cmake_package: "GTest",
+		cmake_targets: &["GTest::gtest", "GTest::gtest_main"],
+	},
+];
+
+pub fn find_known_package(name: &str) -> Option<&'static KnownPackage> {
+	KNOWN_PACKAGES
+		.iter()
+		.find(|p| p.name == name || p.aliases.contains(&name))
+}
+
+pub fn cmake_package(name: &str) -> Option<(String, Vec<String>)> {
+	match find_known_package(name) {
+		Some(known) if !known.cmake_package.is_empty() => Some((
+			known.cmake_package.to_string(),
+			known.cmake_targets.iter().map(|t| t.to_string()).collect(),
+		)),
+		_ => None,
+	}
+}
+
+pub fn cmake_link_block(names: &[String]) -> Option<String> {
+	let mut body = String::new();
+	let mut targets = Vec::new();
+	for name in names {
+		match cmake_package(name) {
+			Some((package, package_targets)) => {
+				body.push_str(&format!("find_package({} REQUIRED)\n", package));
+				targets.extend(package_targets);
+			}
+			None => println!(
+				"⚠️  No known CMake package for '{}'. Add its find_package() and \
+				target_link_libraries() to CMakeLists.txt yourself",
+				name
+			),
+		}
+	}
+	if targets.is_empty() {
+		return None;
+	}
+	body.push_str(&format!(
+		"target_link_libraries(${{PROJECT_NAME}} PRIVATE {})\n",
+		targets.join(" ")
+	));
+	Some(body)
+}
+
+pub fn translate_package(name: &str, pm: HostPackageManager) -> Option<String> {
+	match find_known_package(name) {
+		Some(known) => {
+			let package = known.package_for(pm);
+			if package.is_empty() {
+				None
+			} else {
+				Some(package.to_string())
+			}
+		}
+		None => Some(name.to_string()),
+	}
+}
+
+pub fn pkg_config_module(name: &str) -> Option<String> {
+	match find_known_package(name) {
+		Some(known) if known.pkg_config.is_empty() => None,
+		Some(known) => Some(known.pkg_config.to_string()),
+		None if pkg_config_exists(name) => Some(name.to_string()),
+		None => None,
+	}
+}
+
+pub fn pkg_config_exists(module: &str) -> bool {
+	Command::new("pkg-config")
+		.args(["--exists", module])
+		.status()
+		.map(|s| s.success())
+		.unwrap_or(false)
+}
+
+pub fn generate_install_script(dependencies: &[String]) -> String {
+	let mut translations = String::new();
+	for dep in dependencies {
+		if let Some(known) = find_known_package(dep) {
+			for pm in ALL_HOST_PACKAGE_MANAGERS {
+				translations.push_str(&format!(
+					"\t\t{}:{}) echo \"{}\" ;;\n",
+					pm,
+					dep,
+					known.package_for(pm)
+				));
+			}
+		}
+	}
+
+	let mut detection = String::new();
+	for (i, pm) in ALL_HOST_PACKAGE_MANAGERS.iter().enumerate() {
+		detection.push_str(&format!(
+			"{} command -v {} >/dev/null 2>&1; then\n\tpm={}\n",
+			if i == 0 { "if" } else { "elif" },
+			pm.executable(),
+			pm
+		));
+	}
+
+	let mut installs = String::new();
+	for pm in ALL_HOST_PACKAGE_MANAGERS {
+		installs.push_str(&format!(
+			"\t{}) {}{} $packages ;;\n",
+			pm,
+			if pm.needs_root() { "$SUDO " } else { "" },
+			pm.install_command()
+		));
+	}
+
+	format!(
+		"#!/bin/sh\n\
+		# Generated by sticks: installs the system dependencies declared in the build file.\n\
+		# Usage: sh scripts/install-deps.sh <dependency>...\n\
+		\n\
+		set -e\n\
+		\n\
+		{}else\n\
+		\techo \"No supported package manager found (apt, dnf, pacman, zypper, apk, brew)\" >&2\n\
+		\texit 1\n\
+		fi\n\
+		\n\
+		translate() {{\n\
+		\tcase \"$pm:$1\" in\n\
+		{}\t\t*) echo \"$1\" ;;\n\
+		\tesac\n\
+		}}\n\
+		\n\
+		packages=\"\"\n\
+		for dep in \"$@\"; do\n\
+		\tpkg=$(translate \"$dep\")\n\
+		\tif [ -n \"$pkg\" ]; then\n\
+		\t\tpackages=\"$packages $pkg\"\n\
+		\tfi\n\
+		done\n\
+		\n\
+		if [ -z \"$packages\" ]; then\n\
+		\techo \"Nothing to install\"\n\
+		\texit 0\n\
+		fi\n\
+		\n\
+		SUDO=\"\"\n\
+		if [ \"$(id -u)\" -ne 0 ]; then\n\
+		\tSUDO=\"sudo\"\n\
+		fi\n\
+		\n\
+		echo \"Installing with $pm:$packages\"\n\
+		case \"$pm\" in\n\
+		{}esac\n",
+		detection, translations, installs
+	)
+}
+
+pub fn executable_in_path(program: &str) -> bool {
+	std::env::var_os("PATH")
+		.map(|paths| {
+			std::env::split_paths(&paths).any(|dir| Path::new(&dir).join(program).is_file())
+		})
+		.unwrap_or(false)
+}
@@ -2,7 +2,13 @@ use crate::languages::Language;
 
 pub fn generate_gitignore(language: Language) -> String {
 	match language {
-		Language::C | Language::Cpp => "# Build artifacts\n\
+		Language::C
+		| Language::Cpp
+		| Language::Mixed
+		| Language::ObjC
+		| Language::ObjCpp
+		| Language::Fortran
+		| Language::CFortran => "# Build artifacts\n\
 			build/\n\
 			cmake-build-*/\n\
 			*.o\n\
@@ -63,7 +69,7 @@ pub fn generate_editorconfig() -> String {
 
 pub fn generate_clang_format_config(language: Language) -> String {
 	match language {
-		Language::C => "---\n\
+		Language::C | Language::Fortran | Language::CFortran => "---\n\
 			Language: C\n\
 			Standard: C11\n\
 			IndentWidth: 4\n\
@@ -75,7 +81,7 @@ pub fn generate_clang_format_config(language: Language) -> String {
 			BreakBeforeBraces: Linux\n\
 			SpaceAfterCStyleCast: true\n"
 			.to_string(),
-		Language::Cpp => "---\n\
+		Language::Cpp | Language::Mixed => "---\n\
 			Language: Cpp\n\
 			Standard: C++17\n\
 			IndentWidth: 4\n\
@@ -88,13 +94,31 @@ pub fn generate_clang_format_config(language: Language) -> String {
 			SpaceAfterCStyleCast: true\n\
 			Cpp11BracedListStyle: true\n"
 			.to_string(),
+		Language::ObjC | Language::ObjCpp => "---\n\
+			Language: ObjC\n\
+			IndentWidth: 4\n\
+			UseTab: ForContinuationAndIndentation\n\
+			TabWidth: 4\n\
+			ColumnLimit: 100\n\
+			AllowShortFunctionsOnASingleLine: Empty\n\
+			AllowShortIfStatementsOnASingleLine: Never\n\
+			BreakBeforeBraces: Linux\n\
+			SpaceAfterCStyleCast: true\n\
+			ObjCBlockIndentWidth: 4\n\
+			ObjCSpaceAfterProperty: true\n\
+			ObjCSpaceBeforeProtocolList: true\n"
+			.to_string(),
 	}
 }
 
 pub fn generate_vscode_settings(language: Language) -> String {
 	let extension = match language {
-		Language::C => "c",
+		Language::C | Language::Mixed => "c",
 		Language::Cpp => "cpp",
+		Language::ObjC => "objective-c",
+		Language::ObjCpp => "objective-cpp",
+		Language::Fortran => "fortran",
+		Language::CFortran => "c",
 	};
 
 	format!(
@@ -171,6 +195,11 @@ pub fn generate_readme(project_name: &str, language: Language) -> String {
 	let lang_name = match language {
 		Language::C => "C",
 		Language::Cpp => "C++",
+		Language::Mixed => "C, C++ and assembly",
+		Language::ObjC => "Objective-C (GNUstep)",
+		Language::ObjCpp => "Objective-C++ (GNUstep)",
+		Language::Fortran => "Fortran",
+		Language::CFortran => "C and Fortran",
 	};
 
 	format!(
@@ -0,0 +1,229 @@
+use anyhow::{Context, Result};
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::cmake_editor::{self, Placement};
+use crate::constants::{cmake, project, vcpkg};
+use crate::file_handler::{read, write};
+use crate::json::{detect_indent, JsonValue};
+use crate::package_managers::{PackageManagerGenerator, VcpkgGenerator};
+use crate::system_packages::cmake_link_block;
+
+pub fn integrate() -> Result<()> {
+	let content = read(cmake::FILENAME)?;
+	let content = cmake_editor::set_region(
+		&content,
+		vcpkg::TOOLCHAIN_REGION,
+		&cmake_toolchain(),
+		Placement::BeforeCommand("project"),
+	);
+	write(cmake::FILENAME, &content)?;
+
+	let presets = if Path::new(vcpkg::PRESETS).exists() {
+		read(vcpkg::PRESETS)?
+	} else {
+		"{}".to_string()
+	};
+	write(vcpkg::PRESETS, &add_presets(&presets)?)?;
+	println!(
+		"✓ Added '{}' preset to {}",
+		vcpkg::PRESET_NAME,
+		vcpkg::PRESETS
+	);
+
+	match current_baseline() {
+		Some(_) if Path::new(vcpkg::CONFIGURATION).exists() && !is_generated_configuration()? => {
+			println!(
+				"⚠️  Keeping existing {}; it was not created by sticks",
+				vcpkg::CONFIGURATION
+			)
+		}
+		Some(baseline) => {
+			write(vcpkg::CONFIGURATION, &configuration(&baseline))?;
+			println!(
+				"✓ Pinned vcpkg baseline {} in {}",
+				baseline,
+				vcpkg::CONFIGURATION
+			);
+		}
+		None => println!(
+			"⚠️  Could not read the vcpkg baseline: set {} to a vcpkg checkout, or run \
+			'vcpkg x-update-baseline --add-initial-baseline' to pin it",
+			vcpkg::ROOT_ENV
+		),
+	}
+
+	sync_cmake_dependencies()
+}
+
+pub fn remove_integration() -> Result<()> {
+	let content = read(cmake::FILENAME)?;
+	let content = cmake_editor::remove_region(&content, vcpkg::TOOLCHAIN_REGION);
+	let content = cmake_editor::remove_region(&content, vcpkg::DEPENDENCIES_REGION);
+	write(cmake::FILENAME, &content)?;
+
+	if Path::new(vcpkg::PRESETS).exists() {
+		match remove_presets(&read(vcpkg::PRESETS)?)? {
+			Some(presets) => write(vcpkg::PRESETS, &presets)?,
+			None => fs::remove_file(vcpkg::PRESETS)
+				.with_context(|| format!("Failed to remove {}", vcpkg::PRESETS))?,
+		}
+	}
+	if Path::new(vcpkg::CONFIGURATION).exists() && is_generated_configuration()? {
+		fs::remove_file(vcpkg::CONFIGURATION)
+			.with_context(|| format!("Failed to remove {}", vcpkg::CONFIGURATION))?;
+	}
+	Ok(())
+}
+
+fn is_generated_configuration() -> Result<bool> {
+	let content = read(vcpkg::CONFIGURATION)?;
+	let parsed = match JsonValue::parse(&content) {
+		Ok(parsed) => parsed,
+		Err(_) => return Ok(false),
+	};
+	let baseline = parsed
+		.get("default-registry")
+		.and_then(|registry| registry.get("baseline"))
+		.and_then(JsonValue::as_str);
+	Ok(match baseline {
+		Some(baseline) => JsonValue::parse(&configuration(baseline))? == parsed,
+		None => false,
+	})
+}
+
+pub fn sync_cmake_dependencies() -> Result<()> {
+	if !Path::new(cmake::FILENAME).exists() || !Path::new(vcpkg::MANIFEST).exists() {
+		return Ok(());
+	}
+	let manifest = read(vcpkg::MANIFEST)?;
+	let names: Vec<String> = VcpkgGenerator
+		.dependencies(&manifest)?
+		.into_iter()
+		.map(|spec| spec.name)
+		.collect();
+	let content = read(cmake::FILENAME)?;
+
+	let updated = match cmake_link_block(&names) {
+		Some(block) => cmake_editor::set_region(
+			&content,
+			vcpkg::DEPENDENCIES_REGION,
+			&block,
+			Placement::AfterTarget,
+		),
+		None => cmake_editor::remove_region(&content, vcpkg::DEPENDENCIES_REGION),
+	};
+
+	write(cmake::FILENAME, &updated)
+}
+
+pub fn current_baseline() -> Option<String> {
+	let root = env::var(vcpkg::ROOT_ENV).ok().filter(|r| !r.is_empty())?;
+	let output = Command::new("git")
+		.args(["-C", &root, "rev-parse", "HEAD"])
+		.output()
+		.ok()?;
+	if !output.status.success() {
+		return None;
+	}
+	let baseline = String::from_utf8_lossy(&output.stdout).trim().to_string();
+	if baseline.len() == 40 && baseline.chars().all(|c| c.is_ascii_hexdigit()) {
+		Some(baseline)
+	} else {
+		None
+	}
+}
+
+pub fn configuration(baseline: &str) -> String {
+	let mut registry = JsonValue::object();
+	registry.set("kind", JsonValue::string("git"));
+	registry.set("repository", JsonValue::string(vcpkg::REGISTRY_URL));
+	registry.set("baseline", JsonValue::string(baseline));
+
+	let mut root = JsonValue::object();
+	root.set("default-registry", registry);
+	root.render()
+}
+
+pub fn add_presets(presets: &str) -> Result<String> {
+	let mut root =
+		JsonValue::parse(presets).with_context(|| format!("Failed to parse {}", vcpkg::PRESETS))?;
+	if root.get("version").is_none() {
+		root.set("version", JsonValue::Number("3".to_string()));
+	}
+
+	let mut cache = JsonValue::object();
+	cache.set(
+		"CMAKE_TOOLCHAIN_FILE",
+		JsonValue::string(&format!(
+			"$env{{{}}}/scripts/buildsystems/vcpkg.cmake",
+			vcpkg::ROOT_ENV
+		)),
+	);
+	cache.set("CMAKE_BUILD_TYPE", JsonValue::string("Release"));
+
+	let mut configure = JsonValue::object();
+	configure.set("name", JsonValue::string(vcpkg::PRESET_NAME));
+	configure.set(
+		"binaryDir",
+		JsonValue::string(&format!("${{sourceDir}}/{}", project::BUILD_DIR)),
+	);
+	configure.set("cacheVariables", cache);
+
+	let mut build = JsonValue::object();
+	build.set("name", JsonValue::string(vcpkg::PRESET_NAME));
+	build.set("configurePreset", JsonValue::string(vcpkg::PRESET_NAME));
+
+	upsert_preset(&mut root, "configurePresets", configure);
+	upsert_preset(&mut root, "buildPresets", build);
+	Ok(root.render_with_indent(detect_indent(presets)))
+}
+
+pub fn remove_presets(presets: &str) -> Result<Option<String>> {
+	let mut root =
+		JsonValue::parse(presets).with_context(|| format!("Failed to parse {}", vcpkg::PRESETS))?;
+	for key in ["configurePresets", "buildPresets"] {
+		if let Some(list) = root.get_mut(key).and_then(JsonValue::as_array_mut) {
+			list.retain(|preset| !is_vcpkg_preset(preset));
+			if list.is_empty() {
+				root.remove(key);
+			}
+		}
+	}
+	let only_version = match &root {
+		JsonValue::Object(entries) => entries.iter().all(|(key, _)| key == "version"),
+		_ => false,
+	};
+	Ok(if only_version {
+		None
+	} else {
+		Some(root.render_with_indent(detect_indent(presets)))
+	})
+}
+
+fn upsert_preset(root: &mut JsonValue, key: &str, preset: JsonValue) {
+	if root.get(key).and_then(JsonValue::as_array).is_none() {
+		root.set(key, JsonValue::Array(Vec::new()));
+	}
+	if let Some(list) = root.get_mut(key).and_then(JsonValue::as_array_mut) {
+		match list.iter_mut().find(|existing| is_vcpkg_preset(existing)) {
+			Some(existing) => *existing = preset,
+			None => list.push(preset),
+		}
+	}
+}
+
+fn is_vcpkg_preset(preset: &JsonValue) -> bool {
+	preset.get("name").and_then(JsonValue::as_str) == Some(vcpkg::PRESET_NAME)
+}
+
+fn cmake_toolchain() -> String {
+	format!(
+		"if(NOT DEFINED CMAKE_TOOLCHAIN_FILE AND DEFINED ENV{{{root}}})\n\
+		\tset(CMAKE_TOOLCHAIN_FILE \"$ENV{{{root}}}/scripts/buildsystems/vcpkg.cmake\")\n\
+		endif()\n",
+		root = vcpkg::ROOT_ENV
+	)
+}
@@ -0,0 +1,325 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::cmake_editor::{self, Placement};
+use crate::constants::{cmake, makefile, vendor};
+use crate::features::detect_build_system;
+use crate::languages::CPP_EXTENSIONS;
+use crate::makefile_editor::{Anchor, Makefile};
+use crate::BuildSystem;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VendoredDependency {
+	pub name: String,
+	pub path: String,
+}
+
+pub fn add_vendor(url: &str, path: Option<&str>, target: Option<&str>) -> Result<()> {
+	let build_system = detect_build_system()?
+		.context("No Makefile or CMakeLists.txt found in the current directory")?;
+	let name = repository_name(url)?;
+	let path = path
+		.map(|p| p.trim_end_matches('/').to_string())
+		.unwrap_or_else(|| format!("{}/{}", vendor::DIR, name));
+	let name = dependency_name(&path);
+
+	if vendored_dependencies()?.iter().any(|dep| dep.path == path) {
+		anyhow::bail!("{} is already vendored at {}", name, path);
+	}
+
+	git(&["submodule", "add", url, &path])?;
+	update_build_file(build_system, &VendoredDependency { name, path }, target)
+}
+
+pub fn update_vendor(name: Option<&str>) -> Result<()> {
+	let dependencies = match name {
+		Some(name) => vec![find_vendored(name)?],
+		None => vendored_dependencies()?,
+	};
+	if dependencies.is_empty() {
+		println!("No vendored dependencies to update.");
+		return Ok(());
+	}
+
+	for dependency in dependencies {
+		git(&[
+			"submodule",
+			"update",
+			"--init",
+			"--remote",
+			"--",
+			&dependency.path,
+		])?;
+		let commit = git_output(&["-C", &dependency.path, "rev-parse", "--short", "HEAD"])?;
+		println!(
+			"✓ Updated {} ({}) to {}",
+			dependency.name, dependency.path, commit
+		);
+	}
+	Ok(())
+}
+
+pub fn remove_vendor(name: &str) -> Result<()> {
+	let dependency = find_vendored(name)?;
+
+	git(&["submodule", "deinit", "-f", "--", &dependency.path])?;
+	git(&["rm", "-f", "--", &dependency.path])?;
+	let modules = Path::new(&git_output(&["rev-parse", "--git-dir"])?).join("modules");
+	fs::remove_dir_all(modules.join(&dependency.path)).ok();
+	if let Some(parent) = Path::new(&dependency.path).parent() {
+		fs::remove_dir(parent).ok();
+		fs::remove_dir(modules.join(parent)).ok();
+	}
+	fs::remove_dir(&modules).ok();
+	if fs::read_to_string(vendor::GITMODULES).is_ok_and(|c| c.trim().is_empty()) {
+		git(&["rm", "-q", "-f", "--", vendor::GITMODULES])?;
+	}
+
+	if let Some(build_system) = detect_build_system()? {
+		let file = build_file(build_system);
+		let content =
+			fs::read_to_string(file).with_context(|| format!("Failed to read {}", file))?;
+		let region = region_name(&dependency.name);
+		let updated = match build_system {
+			BuildSystem::CMake => cmake_editor::remove_region(&content, &region),
+			BuildSystem::Makefile => {
+				let mut editor = Makefile::parse(&content);
+				editor.remove_region(&region);
+				editor.render()
+			}
+		};
+		fs::write(file, updated).with_context(|| format!("Failed to write updated {}", file))?;
+	}
+
+	println!(
+		"✓ Removed vendored {} ({})",
+		dependency.name, dependency.path
+	);
+	Ok(())
+}
+
+pub fn vendored_dependencies() -> Result<Vec<VendoredDependency>> {
+	if !Path::new(vendor::GITMODULES).exists() {
+		return Ok(Vec::new());
+	}
+	let output = Command::new("git")
+		.args([
+			"config",
+			"-f",
+			vendor::GITMODULES,
+			"--get-regexp",
+			r"^submodule\..*\.path$",
+		])
+		.output()
+		.context("Failed to run git. Is it installed?")?;
+
+	Ok(String::from_utf8_lossy(&output.stdout)
+		.lines()
+		.filter_map(|line| line.split_whitespace().nth(1))
+		.map(|path| VendoredDependency {
+			name: dependency_name(path),
+			path: path.to_string(),
+		})
+		.collect())
+}
+
+pub fn cmake_block(
+	dependency: &VendoredDependency,
+	has_cmake: bool,
+	target: Option<&str>,
+) -> String {
+	if has_cmake {
+		let link = match target {
+			Some(target) => format!(
+				"target_link_libraries(${{PROJECT_NAME}} PRIVATE {})\n",
+				target
+			),
+			None => format!(
+				"# Link the library target exported by {}:\n\
+				# target_link_libraries(${{PROJECT_NAME}} PRIVATE <target>)\n",
+				dependency.path
+			),
+		};
+		format!(
+			"add_subdirectory({} EXCLUDE_FROM_ALL)\n{}",
+			dependency.path, link
+		)
+	} else {
+		let var = variable_name(&dependency.name);
+		let (sources, includes) = layout(&dependency.path);
+		format!(
+			"file(GLOB_RECURSE VENDOR_{var}_SOURCES \"{sources}/*.c\" \"{sources}/*.cpp\")\n\
+			target_sources(${{PROJECT_NAME}} PRIVATE ${{VENDOR_{var}_SOURCES}})\n\
+			target_include_directories(${{PROJECT_NAME}} PRIVATE \"{includes}\")\n",
+			var = var,
+			sources = sources,
+			includes = includes
+		)
+	}
+}
+
+pub fn makefile_block(dependency: &VendoredDependency) -> String {
+	let var = format!("VENDOR_{}", variable_name(&dependency.name));
+	let (sources, includes) = layout(&dependency.path);
+	let extensions: Vec<(&str, &str)> = std::iter::once(("c", "CC"))
+		.chain(CPP_EXTENSIONS.iter().map(|ext| (*ext, "CXX")))
+		.collect();
+	let wildcards = extensions
+		.iter()
+		.map(|(ext, _)| format!("{}/*.{}", sources, ext))
+		.collect::<Vec<_>>()
+		.join(" ");
+	let mut block = format!(
+		"{var}_FLAGS = -O2 -g -I{includes}\n\
+		{var}_SRCS = $(wildcard {wildcards})\n\
+		OBJS += $(patsubst %,$(BUILD_DIR)/%.o,$({var}_SRCS))\n\
+		CFLAGS += -I{includes}\n",
+		var = var,
+		includes = includes,
+		wildcards = wildcards
+	);
+	for (extension, compiler) in extensions {
+		block.push_str(&format!(
+			"\n\
+			$(BUILD_DIR)/{path}/%.{ext}.o: {path}/%.{ext}\n\
+			\t@mkdir -p $(dir $@)\n\
+			\t$({compiler}) $({var}_FLAGS) -c $< -o $@\n",
+			path = dependency.path,
+			ext = extension,
+			compiler = compiler,
+			var = var
+		));
+	}
+	block
+}
+
+fn update_build_file(
+	build_system: BuildSystem,
+	dependency: &VendoredDependency,
+	target: Option<&str>,
+) -> Result<()> {
+	let file = build_file(build_system);
+	let content = fs::read_to_string(file).with_context(|| format!("Failed to read {}", file))?;
+	let region = region_name(&dependency.name);
+	let updated = match build_system {
+		BuildSystem::CMake => {
+			let has_cmake = Path::new(&dependency.path).join(cmake::FILENAME).exists();
+			if has_cmake && target.is_none() {
+				println!(
+					"⚠️  {} has its own CMakeLists.txt. Link its library target in {} yourself, \
+					or pass --target",
+					dependency.name,
+					cmake::FILENAME
+				);
+			}
+			let body = cmake_block(dependency, has_cmake, target);
+			cmake_editor::set_region(&content, &region, &body, Placement::AfterTarget)
+		}
+		BuildSystem::Makefile => {
+			let mut editor = Makefile::parse(&content);
+			editor.set_region(
+				&region,
+				&makefile_block(dependency),
+				Anchor::AfterVariable("OBJS"),
+			);
+			editor.render()
+		}
+	};
+	fs::write(file, updated).with_context(|| format!("Failed to write updated {}", file))?;
+
+	println!(
+		"✓ Vendored {} at {} and added it to {}",
+		dependency.name, dependency.path, file
+	);
+	Ok(())
+}
+
+fn find_vendored(name: &str) -> Result<VendoredDependency> {
+	let name = name.trim_end_matches('/');
+	vendored_dependencies()?
+		.into_iter()
+		.find(|dep| dep.name == name || dep.path == name)
+		.with_context(|| format!("No vendored dependency named {}", name))
+}
+
+fn layout(path: &str) -> (String, String) {
+	let sources = if Path::new(path).join("src").is_dir() {
+		format!("{}/src", path)
+	} else {
+		path.to_string()
+	};
+	let includes = if Path::new(path).join("include").is_dir() {
+		format!("{}/include", path)
+	} else {
+		path.to_string()
+	};
+	(sources, includes)
+}
+
+fn repository_name(url: &str) -> Result<String> {
+	let name = url
+		.trim_end_matches('/')
+		.rsplit(['/', ':'])
+		.next()
+		.unwrap_or_default()
+		.trim_end_matches(".git");
+	if name.is_empty() {
+		anyhow::bail!("Could not derive a dependency name from {}", url);
+	}
+	Ok(name.to_string())
+}
+
+fn dependency_name(path: &str) -> String {
+	path.rsplit('/').next().unwrap_or(path).to_string()
+}
+
+fn variable_name(name: &str) -> String {
+	name.chars()
+		.map(|c| {
+			if c.is_ascii_alphanumeric() {
+				c.to_ascii_uppercase()
+			} else {
+				'_'
+			}
+		})
+		.collect()
+}
+
+fn region_name(name: &str) -> String {
+	format!("{}{}", vendor::REGION_PREFIX, name)
+}
+
+fn build_file(build_system: BuildSystem) -> &'static str {
+	match build_system {
+		BuildSystem::Makefile => makefile::FILENAME,
+		BuildSystem::CMake => cmake::FILENAME,
+	}
+}
+
+fn git(args: &[&str]) -> Result<()> {
+	let output = Command::new("git")
+		.args(args)
+		.output()
+		.context("Failed to run git. Is it installed?")?;
+	if !output.status.success() {
+		anyhow::bail!(
+			"git {} failed: {}",
+			args.join(" "),
+			String::from_utf8_lossy(&output.stderr).trim()
+		);
+	}
+	Ok(())
+}
+
+fn git_output(args: &[&str]) -> Result<String> {
+	let output = Command::new("git")
+		.args(args)
+		.output()
+		.context("Failed to run git. Is it installed?")?;
+	if !output.status.success() {
+		anyhow::bail!("git {} failed", args.join(" "));
+	}
+	Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -1,4 +1,8 @@
-use sticks::{BuildSystem, BuildSystemGenerator, CMakeGenerator, Language, MakefileGenerator};
+use std::process::Command;
+use sticks::{
+	BuildProfile, BuildSystem, BuildSystemGenerator, CMakeGenerator, Language, Libc,
+	MakefileGenerator,
+};
 
 #[test]
 fn test_build_system_display() {
@@ -83,7 +87,74 @@ fn test_cmake_generator_content() {
 
 	let cmake_cpp = generator.generate_build_file(Language::Cpp, "my_cpp_app");
 	assert!(cmake_cpp.contains("project(my_cpp_app CXX)"));
-	assert!(cmake_cpp.contains("src/*.cpp"));
+	assert!(cmake_cpp.contains("\"src/*.cpp\" \"src/*.cc\" \"src/*.cxx\" \"src/*.C\""));
+}
+
+#[test]
+fn test_objc_build_files_use_gnustep() {
+	let makefile = MakefileGenerator.generate_build_file(Language::ObjC, "tool");
+	assert!(makefile.contains("CC = gcc\n"));
+	assert!(makefile.contains("$(shell gnustep-config --objc-flags)\n"));
+	assert!(makefile.contains("LDFLAGS = $(shell gnustep-config --base-libs)\n"));
+	assert!(makefile.contains("$(BUILD_DIR)/%.o: $(SRC_DIR)/%.m\n"));
+
+	let makefile = MakefileGenerator.generate_build_file(Language::ObjCpp, "tool");
+	assert!(makefile.contains("CC = g++\n"));
+	assert!(makefile.contains("-name '*.mm'"));
+
+	let cmake = CMakeGenerator.generate_build_file(Language::ObjC, "tool");
+	assert!(cmake.starts_with("cmake_minimum_required(VERSION 3.16)\nproject(tool OBJC)\n"));
+	assert!(
+		cmake.contains("COMMAND gnustep-config --objc-flags OUTPUT_VARIABLE GNUSTEP_OBJC_FLAGS")
+	);
+	assert!(cmake.contains(
+		"set(CMAKE_OBJC_FLAGS \"${CMAKE_OBJC_FLAGS} ${GNUSTEP_OBJC_FLAGS} -Wall -Wextra\")\n"
+	));
+	assert!(cmake.contains("CONFIGURE_DEPENDS \"src/*.m\")"));
+	assert!(cmake.contains("target_link_libraries(${PROJECT_NAME} PRIVATE ${GNUSTEP_BASE_LIBS})\n"));
+
+	let cmake = CMakeGenerator.generate_build_file(Language::ObjCpp, "tool");
+	assert!(cmake.contains("project(tool OBJCXX)\n"));
+	assert!(cmake.contains("set(CMAKE_OBJCXX_STANDARD 17)\n"));
+	assert!(!CMakeGenerator
+		.generate_build_file(Language::C, "tool")
+		.contains("gnustep"));
+}
+
+#[test]
+fn test_fortran_build_files() {
+	let makefile = MakefileGenerator.generate_build_file(Language::Fortran, "solver");
+	assert!(makefile.contains("FC = gfortran\n"));
+	assert!(makefile.contains("FFLAGS = -Wall -Wextra -Werror -O2 -g -J$(MOD_DIR)\n"));
+	assert!(makefile.contains("MOD_DIR = $(BUILD_DIR)/modules\n"));
+	assert!(makefile.contains("$(filter-out $(MOD_OBJS),$(OBJS)): | $(MOD_OBJS)\n"));
+	assert!(makefile.contains("\t@mkdir -p $(dir $@) $(MOD_DIR)\n"));
+	assert!(!makefile.contains("CC ="));
+
+	let makefile = MakefileGenerator.generate_build_file(Language::CFortran, "solver");
+	assert!(makefile.contains("CC = gcc\n"));
+	assert!(makefile.contains("$(BUILD_DIR)/%.c.o: $(SRC_DIR)/%.c\n"));
+	assert!(makefile.contains("\t$(FC) $(FFLAGS) -o $@ $^ $(LDFLAGS)\n"));
+
+	let cmake = CMakeGenerator.generate_build_file(Language::CFortran, "solver");
+	assert!(cmake.contains("project(solver C Fortran)\n"));
+	assert!(cmake.contains("set(CMAKE_C_STANDARD 11)\n"));
+	assert!(!cmake.contains("CMAKE_Fortran_STANDARD"));
+	assert!(cmake.contains("set(CMAKE_Fortran_MODULE_DIRECTORY ${CMAKE_BINARY_DIR}/modules)\n"));
+	assert!(cmake.contains("CONFIGURE_DEPENDS \"src/*.c\" \"src/*.f90\")"));
+	assert!(CMakeGenerator
+		.generate_build_file(Language::Fortran, "solver")
+		.contains("project(solver Fortran)\n"));
+}
+
+#[test]
+fn test_cmake_generator_mixed() {
+	let cmake = CMakeGenerator.generate_build_file(Language::Mixed, "firmware");
+	assert!(cmake.contains("project(firmware C CXX ASM)\n"));
+	assert!(cmake.contains("set(CMAKE_C_STANDARD 11)\n"));
+	assert!(cmake.contains("set(CMAKE_CXX_FLAGS \"${CMAKE_CXX_FLAGS} -Wall -Wextra -Werror\")\n"));
+	assert!(cmake.contains("CONFIGURE_DEPENDS \"src/*.c\" \"src/*.cpp\""));
+	assert!(cmake.contains("\"src/*.S\" \"src/*.s\")\n"));
 }
 
 #[test]
@@ -92,3 +163,64 @@ fn test_build_system_equality() {
 	assert_eq!(BuildSystem::CMake, BuildSystem::CMake);
 	assert_ne!(BuildSystem::Makefile, BuildSystem::CMake);
 }
+
+#[test]
+fn test_libc_from_str() {
+	assert!(matches!("musl".parse::<Libc>(), Ok(Libc::Musl)));
+	assert!(matches!("GLIBC".parse::<Libc>(), Ok(Libc::Glibc)));
+	assert!("uclibc".parse::<Libc>().is_err());
+}
+
+#[test]
+fn test_static_profile_build_files() {
+	let profile = BuildProfile::new(Libc::Glibc, true);
+
+	let makefile =
+		MakefileGenerator.generate_build_file_with_profile(Language::C, "static_app", &profile);
+	assert!(makefile.contains("CC = gcc"));
+	assert!(makefile.contains("LDFLAGS = -static"));
+
+	let cmake =
+		CMakeGenerator.generate_build_file_with_profile(Language::C, "static_app", &profile);
+	assert!(cmake.contains("-static"));
+	assert!(cmake.contains("CMAKE_FIND_LIBRARY_SUFFIXES \".a\""));
+	assert!(!cmake.contains("CMAKE_C_COMPILER"));
+
+	let dynamic = MakefileGenerator.generate_build_file(Language::C, "app");
+	assert!(dynamic.contains("LDFLAGS =\n"));
+}
+
+#[test]
+fn test_musl_profile_build_files() {
+	let profile = BuildProfile::new(Libc::Musl, false);
+	assert!(profile.is_static());
+
+	let makefile =
+		MakefileGenerator.generate_build_file_with_profile(Language::C, "musl_app", &profile);
+	assert!(makefile.contains("musl"));
+	assert!(makefile.contains("LDFLAGS = -static"));
+
+	let cmake =
+		CMakeGenerator.generate_build_file_with_profile(Language::Cpp, "musl_app", &profile);
+	let compiler_pos = cmake.find("set(CMAKE_CXX_COMPILER").unwrap();
+	let project_pos = cmake.find("project(musl_app CXX)").unwrap();
+	assert!(compiler_pos < project_pos);
+	assert!(cmake.contains("-static"));
+}
+
+#[test]
+fn test_musl_fortran_requires_toolchain() {
+	let musl = BuildProfile::new(Libc::Musl, false);
+	assert!(musl.check_toolchain(Language::C).is_ok());
+	assert!(BuildProfile::default()
+		.check_toolchain(Language::Fortran)
+		.is_ok());
+
+	let gfortran = format!("{}-linux-musl-gfortran", std::env::consts::ARCH);
+	if Command::new(&gfortran).arg("--version").output().is_ok() {
+		return;
+	}
+	let err = musl.check_toolchain(Language::CFortran).unwrap_err();
+	assert!(err.to_string().contains("No musl Fortran toolchain found"));
+	assert!(err.to_string().contains(&gfortran));
+}
@@ -0,0 +1,60 @@
+use std::path::Path;
+use sticks::builder::{elf_has_interpreter, has_dynamic_interpreter};
+
+fn elf64_with_program_headers(types: &[u32]) -> Vec<u8> {
+	let phoff = 64usize;
+	let phentsize = 56usize;
+	let mut bytes = vec![0u8; phoff + phentsize * types.len()];
+	bytes[..4].copy_from_slice(b"\x7fELF");
+	bytes[4] = 2;
+	bytes[5] = 1;
+	bytes[0x20..0x28].copy_from_slice(&(phoff as u64).to_le_bytes());
+	bytes[0x36..0x38].copy_from_slice(&(phentsize as u16).to_le_bytes());
+	bytes[0x38..0x3a].copy_from_slice(&(types.len() as u16).to_le_bytes());
+	for (i, p_type) in types.iter().enumerate() {
+		let offset = phoff + i * phentsize;
+		bytes[offset..offset + 4].copy_from_slice(&p_type.to_le_bytes());
+	}
+	bytes
+}
+
+#[test]
+fn test_elf_without_interpreter_is_static() {
+	let elf = elf64_with_program_headers(&[1, 1, 4]);
+	assert!(!elf_has_interpreter(&elf).unwrap());
+}
+
+#[test]
+fn test_elf_with_interpreter_is_dynamic() {
+	let elf = elf64_with_program_headers(&[6, 3, 1, 2]);
+	assert!(elf_has_interpreter(&elf).unwrap());
+}
+
+#[test]
+fn test_elf_rejects_non_elf_input() {
+	assert!(elf_has_interpreter(b"#!/bin/sh\necho not an elf file at all, just text\n").is_err());
+	assert!(elf_has_interpreter(b"\x7fELF").is_err());
+}
+
+#[test]
+fn test_elf_rejects_out_of_range_program_headers() {
+	let mut elf = elf64_with_program_headers(&[1]);
+	elf[0x20..0x28].copy_from_slice(&u64::MAX.to_le_bytes());
+	let err = elf_has_interpreter(&elf).unwrap_err();
+	assert!(err.to_string().contains("Truncated ELF header"));
+
+	let mut elf = elf64_with_program_headers(&[1]);
+	elf[0x36..0x38].copy_from_slice(&u16::MAX.to_le_bytes());
+	elf[0x38..0x3a].copy_from_slice(&u16::MAX.to_le_bytes());
+	assert!(elf_has_interpreter(&elf).is_err());
+}
+
+#[test]
+fn test_system_shell_is_dynamically_linked() {
+	let shell = Path::new("/bin/sh");
+	if let Ok(canonical) = shell.canonicalize() {
+		if let Ok(dynamic) = has_dynamic_interpreter(&canonical) {
+			assert!(dynamic, "/bin/sh is expected to use a dynamic loader");
+		}
+	}
+}
@@ -0,0 +1,132 @@
+use serial_test::serial;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use sticks::classes::{add_class, class_header, ClassOptions, SpecialMembers};
+
+#[test]
+fn test_class_header_special_members() {
+	let options = ClassOptions {
+		name: "Buffer".to_string(),
+		special_members: SpecialMembers::MoveOnly,
+		..Default::default()
+	};
+	let header = class_header(&options, &[], None);
+	assert!(header.starts_with("#pragma once\n\nclass Buffer {\npublic:\n"));
+	assert!(header.contains("\tBuffer(const Buffer&) = delete;\n"));
+	assert!(header.contains("\tBuffer(Buffer&&) noexcept = default;\n"));
+	assert!(!header.contains("namespace"));
+
+	let options = ClassOptions {
+		special_members: SpecialMembers::None,
+		..options
+	};
+	let header = class_header(&options, &["app".to_string()], Some("APP_BUFFER_HPP"));
+	assert!(header.starts_with("#ifndef APP_BUFFER_HPP\n#define APP_BUFFER_HPP\n"));
+	assert!(!header.contains("operator="));
+	assert!(header.contains("namespace app {\n"));
+	assert!(header.ends_with("} // namespace app\n\n#endif /* APP_BUFFER_HPP */\n"));
+	assert!("immovable".parse::<SpecialMembers>().is_ok());
+	assert!("copyable".parse::<SpecialMembers>().is_err());
+}
+
+#[test]
+#[serial]
+fn test_add_class_in_namespace_and_subdirectory() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_class_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(temp_dir.join("src")).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+	fs::write(
+		"sticks.toml",
+		"[project]\nname = \"demo\"\nlanguage = \"cpp\"\n",
+	)
+	.unwrap();
+
+	add_class(&ClassOptions {
+		name: "Socket".to_string(),
+		namespace: Some("app::net".to_string()),
+		dir: Some("net".to_string()),
+		..Default::default()
+	})
+	.unwrap();
+
+	let header = fs::read_to_string("include/demo/net/Socket.hpp").unwrap();
+	assert!(header.starts_with("#ifndef DEMO_NET_SOCKET_HPP\n"));
+	assert!(header.contains("namespace app::net {\n\nclass Socket {"));
+	assert!(header.contains("\tSocket& operator=(Socket&&) noexcept = default;\n"));
+	let source = fs::read_to_string("src/net/Socket.cpp").unwrap();
+	assert!(source.starts_with("#include \"demo/net/Socket.hpp\"\n"));
+	assert!(source.contains("Socket::Socket() {}\n\nSocket::~Socket() {}\n"));
+
+	let again = add_class(&ClassOptions {
+		name: "Socket".to_string(),
+		dir: Some("net".to_string()),
+		..Default::default()
+	});
+	assert!(again.unwrap_err().to_string().contains("already exists"));
+	assert!(add_class(&ClassOptions {
+		name: "net::Socket".to_string(),
+		..Default::default()
+	})
+	.is_err());
+
+	if Command::new("g++").arg("--version").output().is_ok() {
+		let status = Command::new("g++")
+			.args([
+				"-std=c++17",
+				"-Wall",
+				"-Werror",
+				"-Iinclude",
+				"-c",
+				"src/net/Socket.cpp",
+				"-o",
+			])
+			.arg(temp_dir.join("Socket.o"))
+			.status()
+			.unwrap();
+		assert!(status.success());
+	}
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_add_class_rejects_c_projects() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_class_c_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(temp_dir.join("src")).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+	fs::write("src/main.c", "int main(void) { return 0; }\n").unwrap();
+
+	let result = add_class(&ClassOptions {
+		name: "Foo".to_string(),
+		..Default::default()
+	});
+	assert!(result.unwrap_err().to_string().contains("C project"));
+	assert!(!Path::new("include").exists());
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
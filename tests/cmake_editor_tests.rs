@@ -0,0 +1,144 @@
+use sticks::cmake_editor::{
+	parse, read_region, remove_region, set_region, ArgumentKind, Placement,
+};
+
+const LISTFILE: &str = "cmake_minimum_required(VERSION 3.15)\n\
+	project(demo C)\n\
+	\n\
+	add_executable(demo\n\
+	\tsrc/main.c\n\
+	)\n\
+	target_include_directories(demo PRIVATE include)\n\
+	\n\
+	install(TARGETS demo DESTINATION bin)\n";
+
+#[test]
+fn test_set_region_after_target() {
+	let updated = set_region(
+		LISTFILE,
+		"deps",
+		"find_package(ZLIB)\n",
+		Placement::AfterTarget,
+	);
+	let region_pos = updated.find("# sticks:begin deps").unwrap();
+	assert!(updated.find("target_include_directories").unwrap() < region_pos);
+	assert!(region_pos < updated.find("install(").unwrap());
+	assert_eq!(
+		read_region(&updated, "deps").as_deref(),
+		Some("find_package(ZLIB)")
+	);
+}
+
+#[test]
+fn test_set_region_replaces_existing_region() {
+	let once = set_region(LISTFILE, "deps", "a()\n", Placement::End);
+	let twice = set_region(&once, "deps", "b()\n", Placement::End);
+	assert_eq!(twice.matches("# sticks:begin deps").count(), 1);
+	assert_eq!(read_region(&twice, "deps").as_deref(), Some("b()"));
+}
+
+#[test]
+fn test_set_region_before_command() {
+	let updated = set_region(
+		LISTFILE,
+		"toolchain",
+		"set(CMAKE_TOOLCHAIN_FILE x)\n",
+		Placement::BeforeCommand("project"),
+	);
+	assert!(updated.find("# sticks:end toolchain").unwrap() < updated.find("project(").unwrap());
+}
+
+#[test]
+fn test_remove_region_round_trip() {
+	for placement in [
+		Placement::AfterTarget,
+		Placement::BeforeCommand("project"),
+		Placement::AfterCommand("project"),
+	] {
+		let updated = set_region(LISTFILE, "deps", "x()\n", placement);
+		assert_eq!(remove_region(&updated, "deps"), LISTFILE);
+	}
+	assert!(read_region(LISTFILE, "deps").is_none());
+}
+
+#[test]
+fn test_after_target_keeps_managed_blocks_in_order() {
+	let first = set_region(LISTFILE, "first", "a()\n", Placement::AfterTarget);
+	let second = set_region(&first, "second", "b()\n", Placement::AfterTarget);
+	let first_pos = second.find("# sticks:begin first").unwrap();
+	let second_pos = second.find("# sticks:begin second").unwrap();
+	assert!(first_pos < second_pos);
+	assert!(second_pos < second.find("install(").unwrap());
+	assert_eq!(
+		remove_region(&remove_region(&second, "first"), "second"),
+		LISTFILE
+	);
+}
+
+#[test]
+fn test_parse_commands_arguments_and_comments() {
+	let listfile = parse(
+		"# add_executable(commented)\n\
+		set(MESSAGE \"a (quoted) \\\"value\\\"\" [=[bracket ) text\n\
+		# sticks:begin not_a_region\n\
+		]=])\n\
+		#[[ add_library(bracket_comment)\n\
+		]]\n\
+		add_executable(app # trailing comment )\n\
+		\tsrc/main.c)\n",
+	);
+	assert_eq!(listfile.commands.len(), 2);
+	let set = &listfile.commands[0];
+	assert!(set.is("SET"));
+	assert_eq!(set.arguments[1].value, "a (quoted) \"value\"");
+	assert_eq!(set.arguments[1].kind, ArgumentKind::Quoted);
+	assert_eq!(set.arguments[2].kind, ArgumentKind::Bracket);
+	assert!(set.arguments[2]
+		.value
+		.contains("# sticks:begin not_a_region"));
+	assert_eq!((set.start, set.end), (1, 3));
+	assert!(listfile.regions.is_empty());
+
+	let content = "add_executable(app \"src/main.c\" src/é.c)\n";
+	let command = &parse(content).commands[0];
+	assert_eq!(
+		&content[command.arguments[1].span.clone()],
+		"\"src/main.c\""
+	);
+	assert_eq!(&content[command.arguments[2].span.clone()], "src/é.c");
+
+	let target = listfile.command("add_executable").unwrap();
+	assert_eq!(target.values(), vec!["app", "src/main.c"]);
+	assert_eq!((target.start, target.end), (6, 7));
+}
+
+#[test]
+fn test_regions_ignore_markers_in_strings_and_user_code() {
+	let listfile = "project(demo C)\n\
+		add_executable(demo\n\
+		\t\"src/main.c\" # target_link_libraries(demo m)\n\
+		\t[[)]]\n\
+		)\n\
+		message(STATUS \"# sticks:begin deps\")\n\
+		install(TARGETS demo DESTINATION bin)\n";
+	let updated = set_region(
+		listfile,
+		"deps",
+		"find_package(ZLIB)\n",
+		Placement::AfterTarget,
+	);
+	assert!(updated.contains("\t[[)]]\n)\n\n# sticks:begin deps\n"));
+	assert_eq!(
+		read_region(&updated, "deps").as_deref(),
+		Some("find_package(ZLIB)")
+	);
+	let user: Vec<String> = parse(&updated)
+		.user_commands()
+		.map(|c| c.name.clone())
+		.collect();
+	assert_eq!(
+		user,
+		vec!["project", "add_executable", "message", "install"]
+	);
+	assert_eq!(remove_region(&updated, "deps"), listfile);
+}
@@ -0,0 +1,109 @@
+use serial_test::serial;
+use std::env;
+use std::fs;
+use sticks::conan::{integrate, remove_integration, set_generators};
+use sticks::package_managers::ConanGenerator;
+use sticks::{add_dependencies, BuildSystem, BuildSystemGenerator, LanguageConsts};
+use sticks::{Language, PackageManager, PackageManagerGenerator};
+
+#[test]
+fn test_set_generators() {
+	let manifest = ConanGenerator.generate_manifest("app");
+	let makefile = set_generators(&manifest, BuildSystem::Makefile);
+	assert!(makefile.contains("[generators]\nPkgConfigDeps\n\n[options]"));
+	assert!(!makefile.contains("CMakeDeps"));
+	assert_eq!(set_generators(&makefile, BuildSystem::CMake), manifest);
+
+	let bare = set_generators("[requires]\nfmt/10.2.1\n", BuildSystem::CMake);
+	assert_eq!(
+		bare,
+		"[requires]\nfmt/10.2.1\n\n[generators]\nCMakeDeps\nCMakeToolchain\n"
+	);
+}
+
+#[test]
+#[serial]
+fn test_integrate_makefile_round_trip() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_conan_make_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	let makefile = Language::C.generate_makefile_content("conan_app");
+	fs::write("Makefile", &makefile).unwrap();
+	fs::write(
+		"conanfile.txt",
+		ConanGenerator.generate_manifest("conan_app"),
+	)
+	.unwrap();
+
+	integrate(PackageManager::Conan, BuildSystem::Makefile).unwrap();
+	integrate(PackageManager::Conan, BuildSystem::Makefile).unwrap();
+	let updated = fs::read_to_string("Makefile").unwrap();
+	assert_eq!(updated.matches("CONAN_DIR = .conan").count(), 1);
+	assert!(updated.contains("pkg-config --libs $(CONAN_PC)"));
+	assert!(updated.contains("conan-install:\n\tconan install . --output-folder=$(CONAN_DIR)"));
+	assert!(updated.contains(".PHONY: all clean run rebuild conan-install\n"));
+	assert!(fs::read_to_string("conanfile.txt")
+		.unwrap()
+		.contains("[generators]\nPkgConfigDeps\n"));
+
+	remove_integration(BuildSystem::Makefile).unwrap();
+	assert_eq!(fs::read_to_string("Makefile").unwrap(), makefile);
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_integrate_cmake_tracks_requirements() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_conan_cmake_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	let listfile = sticks::CMakeGenerator.generate_build_file(Language::Cpp, "conan_app");
+	fs::write("CMakeLists.txt", &listfile).unwrap();
+	fs::write(
+		"conanfile.txt",
+		ConanGenerator.generate_manifest("conan_app"),
+	)
+	.unwrap();
+
+	integrate(PackageManager::Conan, BuildSystem::CMake).unwrap();
+	let updated = fs::read_to_string("CMakeLists.txt").unwrap();
+	assert!(updated.find("conan_toolchain.cmake").unwrap() < updated.find("project(").unwrap());
+	assert!(!updated.contains("conan-dependencies"));
+
+	add_dependencies(&["fmt/10.2.1".to_string(), "openssl/3.2.1".to_string()]).unwrap();
+	let updated = fs::read_to_string("CMakeLists.txt").unwrap();
+	assert!(updated.contains("find_package(fmt REQUIRED)\nfind_package(OpenSSL REQUIRED)\n"));
+	assert!(updated.contains(
+		"target_link_libraries(${PROJECT_NAME} PRIVATE fmt::fmt OpenSSL::SSL OpenSSL::Crypto)"
+	));
+
+	remove_integration(BuildSystem::CMake).unwrap();
+	assert_eq!(fs::read_to_string("CMakeLists.txt").unwrap(), listfile);
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
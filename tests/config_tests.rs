@@ -0,0 +1,86 @@
+use serial_test::serial;
+use std::env;
+use std::fs;
+use sticks::config::{ConfigFile, ConfigValue};
+use sticks::{BuildProfile, Language, Libc, ProjectConfig};
+
+#[test]
+fn test_config_file_parse() {
+	let content = "# project settings\n\
+		[project]\n\
+		name = \"demo\" # trailing comment\n\
+		\n\
+		[build]\n\
+		static = true\n\
+		flags = [\"-O2\", \"-g\"]\n";
+	let file = ConfigFile::parse(content).unwrap();
+
+	assert_eq!(
+		file.get("project", "name").and_then(ConfigValue::as_str),
+		Some("demo")
+	);
+	assert_eq!(
+		file.get("build", "static").and_then(ConfigValue::as_bool),
+		Some(true)
+	);
+	assert_eq!(
+		file.get("build", "flags").and_then(ConfigValue::as_array),
+		Some(&["-O2".to_string(), "-g".to_string()][..])
+	);
+	assert!(file.get("build", "missing").is_none());
+}
+
+#[test]
+fn test_config_file_round_trip() {
+	let mut file = ConfigFile::default();
+	file.set(
+		"project",
+		"name",
+		ConfigValue::String("a \"b\"".to_string()),
+	);
+	file.set("build", "static", ConfigValue::Bool(false));
+
+	let rendered = file.render();
+	assert!(rendered.starts_with("[project]\n"));
+	assert_eq!(ConfigFile::parse(&rendered).unwrap(), file);
+}
+
+#[test]
+fn test_config_file_rejects_invalid_lines() {
+	assert!(ConfigFile::parse("[project]\nname\n").is_err());
+	assert!(ConfigFile::parse("[project]\nname = unquoted\n").is_err());
+}
+
+#[test]
+#[serial]
+fn test_project_config_save_and_load() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_config_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	assert!(ProjectConfig::load().unwrap().is_none());
+
+	let profile = BuildProfile::new(Libc::Musl, false);
+	ProjectConfig::new("static_app", Language::Cpp, profile)
+		.save()
+		.unwrap();
+
+	let loaded = ProjectConfig::load().unwrap().unwrap();
+	assert_eq!(loaded.name, "static_app");
+	assert!(matches!(loaded.language, Some(Language::Cpp)));
+	assert_eq!(loaded.profile.libc, Libc::Musl);
+	assert!(loaded.profile.is_static());
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
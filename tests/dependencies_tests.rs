@@ -1,7 +1,11 @@
 use serial_test::serial;
 use std::env;
 use std::fs;
-use sticks::{add_dependencies, remove_dependencies};
+use std::path::Path;
+use sticks::{
+	add_dependencies, remove_dependencies, BuildSystemGenerator, LanguageConsts,
+	PackageManagerGenerator,
+};
 
 #[test]
 #[serial]
@@ -25,7 +29,7 @@ fn test_add_dependencies_no_makefile() {
 	assert!(result
 		.unwrap_err()
 		.to_string()
-		.contains("Makefile not found"));
+		.contains("No Makefile or CMakeLists.txt found"));
 
 	env::set_current_dir(&original_dir).unwrap();
 	fs::remove_dir_all(&temp_dir).ok();
@@ -64,9 +68,13 @@ fn test_add_dependencies_success() {
 	);
 
 	let updated = fs::read_to_string("Makefile").unwrap();
-	assert!(updated.contains("sudo apt install"));
-	assert!(updated.contains("libcurl"));
-	assert!(updated.contains("openssl"));
+	assert!(updated.contains("DEPS = libcurl openssl"));
+	assert!(updated.contains("install-deps:\n\t@sh scripts/install-deps.sh $(DEPS)"));
+	assert!(!updated.contains("sudo apt install"));
+
+	let script = fs::read_to_string("scripts/install-deps.sh").unwrap();
+	assert!(script.contains("apt:libcurl) echo \"libcurl4-openssl-dev\""));
+	assert!(script.contains("dnf:openssl) echo \"openssl-devel\""));
 
 	env::set_current_dir(&original_dir).unwrap();
 	fs::remove_dir_all(&temp_dir).ok();
@@ -139,3 +147,265 @@ fn test_remove_dependencies_success() {
 	env::set_current_dir(&original_dir).unwrap();
 	fs::remove_dir_all(&temp_dir).ok();
 }
+
+#[test]
+#[serial]
+fn test_add_dependencies_migrates_legacy_install_line() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_legacy_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	let makefile_content = "CC = gcc\nLDFLAGS =\n\nall: app\n\ninstall-deps:\n\tsudo apt install -y zlib\n\n.PHONY: all\n";
+	fs::write("Makefile", makefile_content).unwrap();
+
+	add_dependencies(&["sqlite3".to_string()]).unwrap();
+
+	let updated = fs::read_to_string("Makefile").unwrap();
+	assert!(updated.contains("DEPS = sqlite3 zlib"));
+	assert!(!updated.contains("sudo apt install"));
+	assert_eq!(updated.matches("install-deps:").count(), 1);
+	assert!(updated.contains(".PHONY: all install-deps"));
+
+	remove_dependencies(&["sqlite3".to_string(), "zlib".to_string()]).unwrap();
+	let cleaned = fs::read_to_string("Makefile").unwrap();
+	assert!(!cleaned.contains("DEPS"));
+	assert!(!cleaned.contains("install-deps"));
+	assert!(!std::path::Path::new("scripts/install-deps.sh").exists());
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_add_dependencies_wires_pkg_config_into_makefile() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_pkgconfig_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	fs::write(
+		"Makefile",
+		sticks::Language::C.generate_makefile_content("pkg_app"),
+	)
+	.unwrap();
+
+	add_dependencies(&["libcurl".to_string(), "pthread".to_string()]).unwrap();
+	let updated = fs::read_to_string("Makefile").unwrap();
+	assert!(updated.contains("DEPS = libcurl pthread"));
+	assert!(updated.contains("PKG_CONFIG_DEPS = libcurl\n"));
+	assert!(updated.contains("CFLAGS += $(shell pkg-config --cflags $(PKG_CONFIG_DEPS))"));
+	assert!(updated.contains("LDFLAGS += $(shell pkg-config --libs $(PKG_CONFIG_DEPS))"));
+	let ldflags = updated.find("LDFLAGS =").unwrap();
+	assert!(ldflags < updated.find("LDFLAGS +=").unwrap());
+
+	add_dependencies(&["libxml2".to_string()]).unwrap();
+	let updated = fs::read_to_string("Makefile").unwrap();
+	assert!(updated.contains("PKG_CONFIG_DEPS = libcurl libxml-2.0\n"));
+	assert_eq!(updated.matches("CFLAGS += $(shell pkg-config").count(), 1);
+
+	remove_dependencies(&["libcurl".to_string(), "libxml2".to_string()]).unwrap();
+	let updated = fs::read_to_string("Makefile").unwrap();
+	assert!(updated.contains("DEPS = pthread"));
+	assert!(!updated.contains("pkg-config"));
+
+	remove_dependencies(&["pthread".to_string()]).unwrap();
+	let cleaned = fs::read_to_string("Makefile").unwrap();
+	assert_eq!(
+		cleaned,
+		sticks::Language::C.generate_makefile_content("pkg_app")
+	);
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_add_dependencies_detects_unknown_pc_file() {
+	if std::process::Command::new("pkg-config")
+		.arg("--version")
+		.output()
+		.is_err()
+	{
+		return;
+	}
+
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_pcfile_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+	let original_pc_path = env::var_os("PKG_CONFIG_PATH");
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(temp_dir.join("pc")).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+	env::set_var("PKG_CONFIG_PATH", temp_dir.join("pc"));
+
+	fs::write(
+		"pc/sticksdemo.pc",
+		"Name: sticksdemo\nDescription: test\nVersion: 1.0\nCflags: -I/opt/demo\nLibs: -ldemo\n",
+	)
+	.unwrap();
+	fs::write("Makefile", "CC = gcc\nLDFLAGS =\n\nall: app\n").unwrap();
+
+	add_dependencies(&["sticksdemo".to_string(), "nopcfile".to_string()]).unwrap();
+	let updated = fs::read_to_string("Makefile").unwrap();
+	assert!(updated.contains("DEPS = nopcfile sticksdemo"));
+	assert!(updated.contains("PKG_CONFIG_DEPS = sticksdemo\n"));
+
+	match original_pc_path {
+		Some(path) => env::set_var("PKG_CONFIG_PATH", path),
+		None => env::remove_var("PKG_CONFIG_PATH"),
+	}
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_add_dependencies_wires_pkg_config_into_cmake() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_cmake_deps_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	let original = sticks::CMakeGenerator.generate_build_file(sticks::Language::C, "cmake_app");
+	fs::write("CMakeLists.txt", &original).unwrap();
+
+	add_dependencies(&["libuv".to_string(), "jansson".to_string()]).unwrap();
+	let updated = fs::read_to_string("CMakeLists.txt").unwrap();
+	assert!(
+		updated.contains("pkg_check_modules(STICKS_PKG REQUIRED IMPORTED_TARGET jansson libuv)")
+	);
+	assert!(
+		updated.contains("target_link_libraries(${PROJECT_NAME} PRIVATE PkgConfig::STICKS_PKG)")
+	);
+	assert!(
+		updated.find("add_executable").unwrap()
+			< updated.find("# sticks:begin dependencies").unwrap()
+	);
+
+	remove_dependencies(&["libuv".to_string(), "jansson".to_string()]).unwrap();
+	assert_eq!(fs::read_to_string("CMakeLists.txt").unwrap(), original);
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_cmake_dependencies_use_find_package() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_cmake_find_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	let original = sticks::CMakeGenerator.generate_build_file(sticks::Language::Cpp, "find_app");
+	fs::write("CMakeLists.txt", &original).unwrap();
+
+	add_dependencies(&["openssl".to_string(), "pthread".to_string()]).unwrap();
+	add_dependencies(&["libuv".to_string(), "openssl".to_string()]).unwrap();
+	let updated = fs::read_to_string("CMakeLists.txt").unwrap();
+	assert_eq!(updated.matches("# sticks:begin dependencies").count(), 1);
+	assert!(updated.contains("set(STICKS_DEPS libuv openssl pthread)"));
+	assert!(updated.contains("find_package(OpenSSL REQUIRED)"));
+	assert!(updated.contains("find_package(Threads REQUIRED)"));
+	assert!(updated.contains("pkg_check_modules(STICKS_PKG REQUIRED IMPORTED_TARGET libuv)"));
+	assert!(updated.contains(
+		"target_link_libraries(${PROJECT_NAME} PRIVATE OpenSSL::SSL OpenSSL::Crypto Threads::Threads PkgConfig::STICKS_PKG)"
+	));
+	assert!(updated.contains("add_custom_target(install-deps COMMAND sh ${CMAKE_SOURCE_DIR}/scripts/install-deps.sh ${STICKS_DEPS} VERBATIM)"));
+	assert!(Path::new("scripts/install-deps.sh").exists());
+
+	remove_dependencies(&["libuv".to_string()]).unwrap();
+	let updated = fs::read_to_string("CMakeLists.txt").unwrap();
+	assert!(!updated.contains("PkgConfig"));
+	assert!(updated.contains("PRIVATE OpenSSL::SSL OpenSSL::Crypto Threads::Threads)"));
+
+	remove_dependencies(&["openssl".to_string(), "pthread".to_string()]).unwrap();
+	assert_eq!(fs::read_to_string("CMakeLists.txt").unwrap(), original);
+	assert!(!Path::new("scripts").exists());
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_add_dependencies_to_conan_manifest() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_conan_deps_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	let makefile = sticks::Language::C.generate_makefile_content("conan_app");
+	fs::write("Makefile", &makefile).unwrap();
+	let manifest = sticks::package_managers::ConanGenerator.generate_manifest("conan_app");
+	fs::write("conanfile.txt", &manifest).unwrap();
+
+	add_dependencies(&["fmt/10.2.1".to_string()]).unwrap();
+	let updated = fs::read_to_string("conanfile.txt").unwrap();
+	assert!(updated.starts_with("[requires]\nfmt/10.2.1\n"));
+	assert_eq!(fs::read_to_string("Makefile").unwrap(), makefile);
+	assert!(!Path::new("scripts").exists());
+
+	assert!(add_dependencies(&["openssl".to_string()]).is_err());
+
+	remove_dependencies(&["fmt".to_string()]).unwrap();
+	assert_eq!(fs::read_to_string("conanfile.txt").unwrap(), manifest);
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
@@ -0,0 +1,165 @@
+use serial_test::serial;
+use std::env;
+use std::fs;
+use sticks::dependency_check::{
+	audit_dependencies, check_dependency, linked_libraries, run_check, scan_includes,
+};
+use sticks::{HostPackageManager, Language};
+
+#[test]
+fn test_check_libc_dependency_has_no_problems() {
+	let check = check_dependency("pthread", HostPackageManager::detect(), Language::C);
+	assert_eq!(check.package, None);
+	assert_eq!(check.pkg_config, None);
+	assert_eq!(check.header.as_deref(), Some("pthread.h"));
+	assert!(check.problems().is_empty(), "{:?}", check.problems());
+}
+
+#[test]
+fn test_check_unknown_dependency_reports_problems() {
+	let check = check_dependency(
+		"sticks-no-such-lib",
+		Some(HostPackageManager::Apt),
+		Language::C,
+	);
+	let problems = check.problems();
+	assert!(problems.contains(&"package sticks-no-such-lib is not installed".to_string()));
+	assert!(problems.contains(&"no pkg-config file for sticks-no-such-lib".to_string()));
+	assert_eq!(check.header, None);
+}
+
+#[test]
+#[serial]
+fn test_run_check_fails_for_missing_dependencies() {
+	let pm = match HostPackageManager::detect() {
+		Some(pm) => pm,
+		None => return,
+	};
+	if pm.installed_version("zlib1g-dev").is_none() {
+		return;
+	}
+
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_deps_check_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	fs::write("Makefile", "all: clean\n\tbuild\n").unwrap();
+	sticks::add_dependencies(&["zlib".to_string()]).unwrap();
+	let zlib = check_dependency("zlib", Some(pm), Language::C);
+	assert!(zlib.problems().is_empty(), "{:?}", zlib.problems());
+	assert!(run_check().is_ok());
+
+	sticks::add_dependencies(&["sticks-no-such-lib".to_string()]).unwrap();
+	let error = run_check().unwrap_err().to_string();
+	assert!(error.contains("1 of 2 dependencies"));
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_scan_includes_skips_project_headers() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_scan_includes_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(temp_dir.join("src")).unwrap();
+	fs::create_dir_all(temp_dir.join("include")).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	fs::write("include/utils.h", "#pragma once\n#include <stdint.h>\n").unwrap();
+	fs::write(
+		"src/main.c",
+		"#include <stdio.h>\n#include \"utils.h\"\n  #  include <zlib.h>\n#include <utils.h>\n",
+	)
+	.unwrap();
+	fs::write(
+		"Makefile",
+		"LDFLAGS = -lm -lncurses\n# sticks:begin vendor-x\nLDFLAGS += -lfoo\n# sticks:end vendor-x\n",
+	)
+	.unwrap();
+
+	let includes = scan_includes().unwrap();
+	let headers: Vec<&String> = includes.keys().collect();
+	assert_eq!(headers, vec!["stdint.h", "stdio.h", "zlib.h"]);
+	assert_eq!(includes["zlib.h"], vec!["src/main.c".to_string()]);
+	assert_eq!(linked_libraries().unwrap(), vec!["m", "ncurses"]);
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_audit_reports_unused_and_undeclared_dependencies() {
+	let pm = match HostPackageManager::detect() {
+		Some(pm) if pm == HostPackageManager::Apt => pm,
+		_ => return,
+	};
+	if ["zlib1g-dev", "libsqlite3-dev", "libssl-dev"]
+		.iter()
+		.any(|package| pm.installed_version(package).is_none())
+	{
+		return;
+	}
+
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_audit_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(temp_dir.join("src")).unwrap();
+	fs::create_dir_all(temp_dir.join("include")).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	fs::write(
+		"Makefile",
+		"LDFLAGS = -lm -lz -lreadline\n\nall: clean\n\tbuild\n",
+	)
+	.unwrap();
+	sticks::add_dependencies(&["zlib".to_string(), "sqlite3".to_string()]).unwrap();
+	fs::write(
+		"src/main.c",
+		"#include <stdio.h>\n#include <zlib.h>\n#include <openssl/ssl.h>\n#include <no_such_header.h>\n",
+	)
+	.unwrap();
+
+	let report = audit_dependencies().unwrap();
+	assert_eq!(report.unused, vec!["sqlite3".to_string()]);
+	let undeclared: Vec<(&str, &str)> = report
+		.undeclared
+		.iter()
+		.map(|u| (u.usage.as_str(), u.suggestion.as_str()))
+		.collect();
+	assert_eq!(
+		undeclared,
+		vec![("<openssl/ssl.h>", "openssl"), ("-lreadline", "readline")]
+	);
+	assert_eq!(report.unresolved, vec!["no_such_header.h".to_string()]);
+	assert!(!report.is_clean());
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
@@ -104,6 +104,31 @@ fn test_detect_package_manager_conan() {
 	fs::remove_dir_all(&temp_dir).ok();
 }
 
+#[test]
+#[serial]
+fn test_detect_package_manager_conan_recipe() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_pm_conan_py_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+	fs::write("conanfile.py", "from conan import ConanFile\n").unwrap();
+
+	let result = detect_package_manager().unwrap();
+	assert_eq!(result, Some(PackageManager::ConanRecipe));
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
 #[test]
 #[serial]
 fn test_detect_package_manager_vcpkg() {
@@ -0,0 +1,144 @@
+use serial_test::serial;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use sticks::fetch_content::{git_dependencies, resolve_git_ref};
+use sticks::{add_git_dependency, remove_dependencies, BuildSystemGenerator, Language};
+
+fn git(dir: &Path, args: &[&str]) -> String {
+	let output = Command::new("git")
+		.arg("-C")
+		.arg(dir)
+		.args([
+			"-c",
+			"user.name=sticks",
+			"-c",
+			"user.email=sticks@example.com",
+		])
+		.args(args)
+		.output()
+		.unwrap();
+	assert!(output.status.success(), "git {:?} failed", args);
+	String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+fn bare_remote(root: &Path) -> PathBuf {
+	let work = root.join("work");
+	fs::create_dir_all(work.join("include")).unwrap();
+	fs::write(work.join("include/lib.h"), "#pragma once\n").unwrap();
+	git(root, &["init", "-q", work.to_str().unwrap()]);
+	git(&work, &["add", "."]);
+	git(&work, &["commit", "-q", "-m", "initial"]);
+	git(&work, &["tag", "v1.2.3"]);
+	let remote = root.join("remote.git");
+	git(
+		root,
+		&[
+			"clone",
+			"-q",
+			"--bare",
+			work.to_str().unwrap(),
+			remote.to_str().unwrap(),
+		],
+	);
+	remote
+}
+
+#[test]
+#[serial]
+fn test_resolve_git_ref() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_git_ref_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	fs::create_dir_all(&temp_dir).unwrap();
+	let remote = bare_remote(&temp_dir);
+	let url = remote.to_str().unwrap();
+	let head = git(&remote, &["rev-parse", "HEAD"]);
+
+	assert_eq!(resolve_git_ref(url, Some("v1.2.3")).unwrap(), "v1.2.3");
+	assert_eq!(resolve_git_ref(url, None).unwrap(), head);
+	assert!(resolve_git_ref(url, Some("v9.9.9")).is_err());
+	assert!(resolve_git_ref(temp_dir.join("missing.git").to_str().unwrap(), None).is_err());
+
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_add_and_remove_git_dependency() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_fetch_content_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(temp_dir.join("project")).unwrap();
+	let remote = bare_remote(&temp_dir);
+	let url = remote.to_str().unwrap();
+	env::set_current_dir(temp_dir.join("project")).unwrap();
+
+	let listfile = sticks::CMakeGenerator.generate_build_file(Language::Cpp, "fetch_app");
+	fs::write("CMakeLists.txt", &listfile).unwrap();
+
+	add_git_dependency("mylib", url, Some("v1.2.3"), Some("mylib::mylib")).unwrap();
+	let updated = fs::read_to_string("CMakeLists.txt").unwrap();
+	assert!(updated.contains(&format!(
+		"FetchContent_Declare(mylib\n\tGIT_REPOSITORY {}\n\tGIT_TAG v1.2.3\n)\nFetchContent_MakeAvailable(mylib)\ntarget_link_libraries(${{PROJECT_NAME}} PRIVATE mylib::mylib)\n",
+		url
+	)));
+
+	let dependencies = git_dependencies(&updated);
+	assert_eq!(dependencies.len(), 1);
+	assert_eq!(dependencies[0].name, "mylib");
+	assert_eq!(dependencies[0].url, url);
+	assert_eq!(dependencies[0].git_ref, "v1.2.3");
+	assert_eq!(dependencies[0].link, "mylib::mylib");
+
+	assert!(add_git_dependency("broken", url, Some("nope"), None).is_err());
+	assert!(add_git_dependency("bad name", url, None, None).is_err());
+
+	remove_dependencies(&["mylib".to_string()]).unwrap();
+	assert_eq!(fs::read_to_string("CMakeLists.txt").unwrap(), listfile);
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_git_dependency_requires_cmake() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_fetch_make_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+	fs::write("Makefile", "all:\n").unwrap();
+
+	let result = add_git_dependency("lib", "https://example.invalid/lib.git", None, None);
+	assert!(result
+		.unwrap_err()
+		.to_string()
+		.contains("sticks vendor add"));
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
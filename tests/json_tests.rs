@@ -0,0 +1,67 @@
+use sticks::json::{detect_indent, JsonValue};
+
+#[test]
+fn test_parse_and_render_round_trip() {
+	let input = "{\n  \"name\": \"app\",\n  \"version\": \"0.1.0\",\n  \"dependencies\": [\n    \"fmt\",\n    {\n      \"name\": \"zlib\",\n      \"default-features\": false\n    }\n  ],\n  \"builtin-baseline\": null,\n  \"port-version\": 2\n}\n";
+	let value = JsonValue::parse(input).unwrap();
+	assert_eq!(value.get("name").and_then(JsonValue::as_str), Some("app"));
+	assert_eq!(
+		value.get("dependencies").unwrap().as_array().unwrap().len(),
+		2
+	);
+	assert_eq!(value.render(), input);
+}
+
+#[test]
+fn test_parse_escapes() {
+	let value = JsonValue::parse(r#"{"path": "a\\b\"cA\n"}"#).unwrap();
+	assert_eq!(
+		value.get("path").and_then(JsonValue::as_str),
+		Some("a\\b\"cA\n")
+	);
+	assert_eq!(value.render(), "{\n  \"path\": \"a\\\\b\\\"cA\\n\"\n}\n");
+}
+
+#[test]
+fn test_parse_surrogate_pairs() {
+	let value = JsonValue::parse(r#"{"face": "\ud83d\ude00 \u00e9"}"#).unwrap();
+	assert_eq!(
+		value.get("face").and_then(JsonValue::as_str),
+		Some("\u{1f600} \u{e9}")
+	);
+	assert_eq!(value.render(), "{\n  \"face\": \"\u{1f600} \u{e9}\"\n}\n");
+
+	let err = JsonValue::parse(r#""\ud83d""#).unwrap_err();
+	assert!(err.to_string().contains("Unpaired surrogate \\ud83d"));
+	assert!(JsonValue::parse(r#""\ud83d\u0041""#).is_err());
+	assert!(JsonValue::parse(r#""\ude00""#).is_err());
+}
+
+#[test]
+fn test_render_keeps_detected_indent() {
+	let input = "{\n\t\"name\": \"app\",\n\t\"dependencies\": [\n\t\t\"fmt\"\n\t]\n}\n";
+	let value = JsonValue::parse(input).unwrap();
+	assert_eq!(detect_indent(input), "\t");
+	assert_eq!(value.render_with_indent(detect_indent(input)), input);
+	assert_eq!(detect_indent("{\n\n    \"a\": 1\n}\n"), "    ");
+	assert_eq!(detect_indent("{}"), "  ");
+}
+
+#[test]
+fn test_set_and_remove() {
+	let mut value = JsonValue::parse("{}").unwrap();
+	value.set("a", JsonValue::string("1"));
+	value.set("b", JsonValue::Array(Vec::new()));
+	value.set("a", JsonValue::Bool(true));
+	assert_eq!(value.render(), "{\n  \"a\": true,\n  \"b\": []\n}\n");
+	assert_eq!(value.remove("a"), Some(JsonValue::Bool(true)));
+	assert!(value.get("a").is_none());
+}
+
+#[test]
+fn test_parse_invalid_json() {
+	assert!(JsonValue::parse("{\"a\": }").is_err());
+	assert!(JsonValue::parse("[1, 2").is_err());
+	assert!(JsonValue::parse("{\"a\": 1} trailing").is_err());
+	assert!(JsonValue::parse("\"unterminated").is_err());
+}
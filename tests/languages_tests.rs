@@ -1,4 +1,10 @@
-use sticks::{Language, LanguageConsts};
+use serial_test::serial;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use sticks::languages::SourceCounts;
+use sticks::{add_sources, init_project, Language, LanguageConsts};
 
 #[test]
 fn test_language_display() {
@@ -10,12 +16,17 @@ fn test_language_display() {
 fn test_language_cc() {
 	assert_eq!(Language::C.cc(), "gcc");
 	assert_eq!(Language::Cpp.cc(), "g++");
+	assert_eq!(Language::Fortran.cc(), "gfortran");
 }
 
 #[test]
 fn test_language_extension() {
 	assert_eq!(Language::C.extension(), "c");
 	assert_eq!(Language::Cpp.extension(), "cpp");
+	assert_eq!(Language::ObjC.extension(), "m");
+	assert_eq!(Language::ObjCpp.extension(), "mm");
+	assert_eq!(Language::Fortran.extension(), "f90");
+	assert_eq!(Language::CFortran.extension(), "c");
 }
 
 #[test]
@@ -27,6 +38,28 @@ fn test_language_helloworld() {
 	let cpp_hello = Language::Cpp.generate_helloworld_content();
 	assert!(cpp_hello.contains("#include <iostream>"));
 	assert!(cpp_hello.contains("std::cout"));
+
+	let objc_hello = Language::ObjC.generate_helloworld_content();
+	assert!(objc_hello.starts_with("#import <Foundation/Foundation.h>\n"));
+	assert!(objc_hello.contains("NSLog(@\"Hello, World!\");"));
+	assert!(objc_hello.contains("[pool drain];"));
+
+	let objcpp_hello = Language::ObjCpp.generate_helloworld_content();
+	assert!(objcpp_hello.contains("#include <iostream>\n"));
+	assert!(objcpp_hello.contains("[greeting UTF8String]"));
+
+	let fortran_hello = Language::Fortran.generate_helloworld_content();
+	assert!(fortran_hello.starts_with("program main\n    implicit none\n"));
+	assert!(!fortran_hello.contains('\t'));
+	assert!(Language::Fortran.companion_sources().is_empty());
+	let companions = Language::CFortran.companion_sources();
+	assert_eq!(companions[0].0, "greet.f90");
+	assert!(companions[0]
+		.1
+		.starts_with("subroutine greet() bind(c, name='greet')\n"));
+	assert!(Language::CFortran
+		.generate_helloworld_content()
+		.contains("greet();"));
 }
 
 #[test]
@@ -46,6 +79,216 @@ fn test_language_from_str() {
 	assert!(matches!("C".parse::<Language>(), Ok(Language::C)));
 	assert!(matches!("cpp".parse::<Language>(), Ok(Language::Cpp)));
 	assert!(matches!("CPP".parse::<Language>(), Ok(Language::Cpp)));
+	assert!(matches!("mixed".parse::<Language>(), Ok(Language::Mixed)));
+	assert!(matches!("objc".parse::<Language>(), Ok(Language::ObjC)));
+	assert!(matches!("ObjCpp".parse::<Language>(), Ok(Language::ObjCpp)));
+	assert!(matches!(
+		"fortran".parse::<Language>(),
+		Ok(Language::Fortran)
+	));
+	assert!(matches!(
+		"c-fortran".parse::<Language>(),
+		Ok(Language::CFortran)
+	));
 	assert!("rust".parse::<Language>().is_err());
 	assert!("java".parse::<Language>().is_err());
 }
+
+#[test]
+#[serial]
+fn test_detect_counts_sources_recursively() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_detect_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(temp_dir.join("src/engine/math")).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	assert_eq!(Language::detect().unwrap(), None);
+	for file in [
+		"src/aaa.h",
+		"src/main.c",
+		"src/util.c",
+		"src/engine/math/vec.cc",
+		"src/engine/Physics.C",
+		"src/engine/render.cxx",
+		"src/engine/render.hpp",
+	] {
+		fs::write(file, "").unwrap();
+	}
+
+	let counts = SourceCounts::scan(Path::new("src")).unwrap();
+	assert_eq!(
+		counts,
+		SourceCounts {
+			c: 2,
+			cpp: 3,
+			..Default::default()
+		}
+	);
+	assert_eq!(counts.primary(), Some(Language::Cpp));
+	assert_eq!(Language::detect().unwrap(), Some(Language::Cpp));
+	fs::write("src/startup.S", "").unwrap();
+	assert_eq!(Language::detect().unwrap(), Some(Language::Mixed));
+	assert_eq!(
+		SourceCounts {
+			c: 1,
+			fortran: 2,
+			..Default::default()
+		}
+		.primary(),
+		Some(Language::CFortran)
+	);
+	assert_eq!(Language::from_extension("c++"), Some(Language::Cpp));
+	assert_eq!(Language::from_extension("h"), None);
+	assert_eq!(Language::from_extension("mm"), Some(Language::ObjCpp));
+
+	fs::write(
+		"sticks.toml",
+		"[project]\nname = \"demo\"\nlanguage = \"c\"\n",
+	)
+	.unwrap();
+	assert_eq!(Language::detect().unwrap(), Some(Language::C));
+	assert!(matches!(
+		Language::from_project_structure_with_prompt(false),
+		Ok(Language::C)
+	));
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_mixed_project_builds_c_cpp_and_assembly() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_mixed_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	init_project(Language::Mixed).unwrap();
+	assert!(Path::new("src/main.c").exists());
+	let makefile = fs::read_to_string("Makefile").unwrap();
+	assert!(makefile.contains("CC = gcc\nCXX = g++\nAS = gcc\n"));
+	assert!(makefile.contains("$(BUILD_DIR)/%.cpp.o: $(SRC_DIR)/%.cpp\n"));
+	assert!(makefile.contains("\t$(AS) $(ASFLAGS) -c $< -o $@\n"));
+	assert!(fs::read_to_string("sticks.toml")
+		.unwrap()
+		.contains("language = \"mixed\""));
+
+	add_sources(&["answer.cpp", "boot/start.S", "boot/stub.s", "util"]).unwrap();
+	assert_eq!(
+		fs::read_to_string("src/boot/stub.s").unwrap(),
+		"\t.text\n\n\t.section .note.GNU-stack,\"\",%progbits\n"
+	);
+	assert!(Path::new("src/util.c").exists());
+	assert!(Path::new("include")
+		.join(temp_dir.file_name().unwrap())
+		.join("answer.h")
+		.exists());
+	assert!(!Path::new("include")
+		.join(temp_dir.file_name().unwrap())
+		.join("boot/start.h")
+		.exists());
+
+	let include = Path::new("include").join(temp_dir.file_name().unwrap());
+	assert!(!fs::read_to_string(include.join("answer.h"))
+		.unwrap()
+		.contains("extern \"C\""));
+	let header = fs::read_to_string(include.join("util.h")).unwrap();
+	assert!(header.contains("#ifdef __cplusplus\nextern \"C\" {\n#endif\n"));
+	fs::write(
+		include.join("util.h"),
+		header.replace(
+			"extern \"C\" {\n#endif\n",
+			"extern \"C\" {\n#endif\n\nint cpp_answer(void);\n",
+		),
+	)
+	.unwrap();
+	fs::write(
+		"src/answer.cpp",
+		format!(
+			"#include \"{}/util.h\"\n\nint cpp_answer(void) {{ return 42; }}\n",
+			temp_dir.file_name().unwrap().to_string_lossy()
+		),
+	)
+	.unwrap();
+	fs::write(
+		"src/boot/start.S",
+		"\t.section .rodata\n\t.globl startup_magic\nstartup_magic:\n\t.long 42\n\
+		\t.section .note.GNU-stack,\"\",%progbits\n",
+	)
+	.unwrap();
+	fs::write(
+		"src/main.c",
+		format!(
+			"#include \"{}/util.h\"\n\nextern const int startup_magic;\n\n\
+			int main(void) {{ return startup_magic == cpp_answer() ? 0 : 1; }}\n",
+			temp_dir.file_name().unwrap().to_string_lossy()
+		),
+	)
+	.unwrap();
+
+	if Command::new("make").arg("--version").output().is_ok() {
+		let output = Command::new("make").arg("run").output().unwrap();
+		assert!(output.status.success());
+		assert!(!String::from_utf8_lossy(&output.stderr).contains("executable stack"));
+		assert!(Path::new("build/boot/start.S.o").exists());
+		assert!(Path::new("build/boot/stub.s.o").exists());
+		assert!(Path::new("build/answer.cpp.o").exists());
+	}
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_cpp_makefile_builds_every_cpp_extension() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_cpp_exts_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(temp_dir.join("src")).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	fs::write("Makefile", Language::Cpp.generate_makefile_content("exts")).unwrap();
+	fs::write(
+		"src/main.cc",
+		"int one();\nint two();\n\nint main() { return one() + two() == 3 ? 0 : 1; }\n",
+	)
+	.unwrap();
+	fs::write("src/one.cxx", "int one() { return 1; }\n").unwrap();
+	fs::write("src/two.C", "int two() { return 2; }\n").unwrap();
+
+	if Command::new("make").arg("--version").output().is_ok() {
+		let status = Command::new("make").arg("run").status().unwrap();
+		assert!(status.success());
+		assert!(Path::new("build/main.o").exists());
+		assert!(Path::new("build/one.o").exists());
+		assert!(Path::new("build/two.o").exists());
+	}
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
@@ -0,0 +1,135 @@
+use serial_test::serial;
+use std::env;
+use std::fs;
+use sticks::lockfile::{install_dependencies, Lockfile};
+use sticks::HostPackageManager;
+
+#[test]
+fn test_lockfile_round_trip() {
+	let mut lock = Lockfile::default();
+	lock.set("host", "package_manager", "apt");
+	lock.set("system", "zlib", "1:1.2.13.dfsg-1");
+	lock.set("conan", "fmt", "10.2.1#a1b2c3");
+
+	let rendered = lock.render();
+	assert!(rendered.starts_with("# Generated by sticks"));
+	assert!(rendered.contains("[system]\nzlib = \"1:1.2.13.dfsg-1\"\n"));
+	assert!(rendered.contains("fmt = \"10.2.1#a1b2c3\""));
+
+	let parsed = Lockfile::parse(&rendered).unwrap();
+	assert_eq!(parsed, lock);
+	assert_eq!(parsed.get("conan", "fmt"), Some("10.2.1#a1b2c3"));
+}
+
+#[test]
+fn test_lockfile_diff_reports_divergences() {
+	let mut locked = Lockfile::default();
+	locked.set("system", "zlib", "1.2.13");
+	locked.set("system", "openssl", "3.0.1");
+	locked.set("conan", "fmt", "10.2.1");
+
+	let mut current = Lockfile::default();
+	current.set("system", "zlib", "1.3");
+	current.set("conan", "fmt", "10.2.1");
+	current.set("conan", "spdlog", "1.13.0");
+
+	let divergences = locked.diff(&current);
+	assert_eq!(divergences.len(), 3);
+	assert!(divergences.contains(&"[system] zlib: locked at 1.2.13 but found 1.3".to_string()));
+	assert!(divergences
+		.iter()
+		.any(|d| d.starts_with("[system] openssl")));
+	assert!(divergences
+		.iter()
+		.any(|d| d.contains("spdlog") && d.contains("not recorded")));
+	assert!(locked.diff(&locked).is_empty());
+}
+
+#[test]
+#[serial]
+fn test_lockfile_resolves_manifest_and_conan_lock() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_lock_conan_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	fs::write(
+		"conanfile.txt",
+		"[requires]\nfmt/10.2.1\nzlib/1.3.1\n\n[generators]\nPkgConfigDeps\n",
+	)
+	.unwrap();
+	let lock = Lockfile::resolve().unwrap();
+	assert_eq!(lock.get("conan", "fmt"), Some("10.2.1"));
+	assert_eq!(lock.get("conan", "zlib"), Some("1.3.1"));
+
+	fs::write(
+		"conan.lock",
+		"{\n  \"version\": \"0.5\",\n  \"requires\": [\n    \"fmt/10.2.1#f1e2d3%1700000000.0\"\n  ]\n}\n",
+	)
+	.unwrap();
+	let lock = Lockfile::resolve().unwrap();
+	assert_eq!(lock.get("conan", "fmt"), Some("10.2.1#f1e2d3"));
+	assert_eq!(lock.get("conan", "zlib"), None);
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_install_locked_fails_when_host_diverges() {
+	let pm = match HostPackageManager::detect() {
+		Some(pm) => pm,
+		None => return,
+	};
+	let installed = match pm.installed_version("zlib1g-dev") {
+		Some(version) if pm == HostPackageManager::Apt => version,
+		_ => return,
+	};
+
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_lock_install_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	fs::write("Makefile", "all: clean\n\tbuild\n").unwrap();
+	sticks::add_dependencies(&["zlib".to_string()]).unwrap();
+
+	let result = install_dependencies(true);
+	assert!(result
+		.unwrap_err()
+		.to_string()
+		.contains("No sticks.lock found"));
+
+	install_dependencies(false).unwrap();
+	let lock = fs::read_to_string("sticks.lock").unwrap();
+	assert!(lock.contains("package_manager = \"apt\""));
+	assert!(lock.contains(&format!("zlib = \"{}\"", installed)));
+	install_dependencies(true).unwrap();
+
+	fs::write("sticks.lock", lock.replace(&installed, "0.0.1")).unwrap();
+	let error = install_dependencies(true).unwrap_err().to_string();
+	assert!(error.contains("diverges from sticks.lock"));
+	assert!(error.contains(&format!("zlib: locked at 0.0.1 but found {}", installed)));
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
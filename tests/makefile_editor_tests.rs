@@ -0,0 +1,109 @@
+use sticks::makefile_editor::{Anchor, Item, Makefile, Rule};
+use sticks::{Language, LanguageConsts};
+
+const MAKEFILE: &str = "# Build settings\n\
+	CC := gcc\n\
+	CFLAGS = -Wall \\\n\
+	\t-Wextra\n\
+	LDFLAGS =\n\
+	OBJS = $(SRCS:.c=.o)\n\
+	\n\
+	ifeq ($(DEBUG),1)\n\
+	CFLAGS += -g\n\
+	endif\n\
+	\n\
+	define banner\n\
+	@echo \"all: clean\"\n\
+	endef\n\
+	\n\
+	all: $(TARGET)\n\
+	\n\
+	$(TARGET): $(OBJS)\n\
+	\t$(CC) -o $@ $^ $(LDFLAGS)\n\
+	\n\
+	# Remove build output\n\
+	clean:\n\
+	\trm -f $(OBJS) $(TARGET)\n\
+	\n\
+	.PHONY: all clean\n";
+
+#[test]
+fn test_round_trip_preserves_content() {
+	assert_eq!(Makefile::parse(MAKEFILE).render(), MAKEFILE);
+	for language in [
+		Language::C,
+		Language::Cpp,
+		Language::Mixed,
+		Language::Fortran,
+		Language::CFortran,
+	] {
+		let generated = language.generate_makefile_content("demo");
+		assert_eq!(Makefile::parse(&generated).render(), generated);
+	}
+	let no_newline = "all:\n\techo done";
+	assert_eq!(Makefile::parse(no_newline).render(), no_newline);
+}
+
+#[test]
+fn test_parse_recognises_variables_and_rules() {
+	let makefile = Makefile::parse(MAKEFILE);
+	assert_eq!(makefile.variable("CC").unwrap().op, ":=");
+	assert_eq!(makefile.variable("CFLAGS").unwrap().value, "-Wall -Wextra");
+	assert_eq!(makefile.variables("CFLAGS").count(), 2);
+
+	let target = makefile.rule("$(TARGET)").unwrap();
+	assert_eq!(target.prerequisites, vec!["$(OBJS)"]);
+	assert_eq!(target.recipe, vec!["\t$(CC) -o $@ $^ $(LDFLAGS)"]);
+	assert!(makefile.rule("clean").is_some());
+	assert!(makefile
+		.items()
+		.iter()
+		.any(|item| matches!(item, Item::Directive(lines) if lines.len() == 3)));
+}
+
+#[test]
+fn test_rule_and_phony_edits_leave_other_text_alone() {
+	let mut makefile = Makefile::parse(MAKEFILE);
+	makefile.set_rule(
+		Rule::new("install-deps", &[], &["@sh install.sh".to_string()]),
+		Some("# Install dependencies"),
+	);
+	makefile.add_phony("install-deps");
+	makefile.add_prerequisite("all", "install-deps");
+	let edited = makefile.render();
+	assert!(edited.contains("all: $(TARGET) install-deps\n"));
+	assert!(edited.contains(".PHONY: all clean install-deps\n"));
+	assert!(edited.ends_with("\n\n# Install dependencies\ninstall-deps:\n\t@sh install.sh\n"));
+	assert!(edited.contains("@echo \"all: clean\"\n"));
+
+	let mut makefile = Makefile::parse(&edited);
+	makefile.remove_rule("install-deps");
+	makefile.remove_phony("install-deps");
+	makefile.remove_prerequisite("all", "install-deps");
+	assert_eq!(makefile.render(), MAKEFILE);
+}
+
+#[test]
+fn test_variable_and_region_edits() {
+	let mut makefile = Makefile::parse(MAKEFILE);
+	assert!(makefile.set_variable("LDFLAGS", "-lm"));
+	assert!(!makefile.set_variable("MISSING", "value"));
+	makefile.set_region(
+		"vendor_lib",
+		"LIB_SRCS = $(wildcard lib/*.c)\nOBJS += $(LIB_SRCS:.c=.o)",
+		Anchor::AfterVariable("OBJS"),
+	);
+	let edited = makefile.render();
+	assert!(edited.contains("LDFLAGS = -lm\n"));
+	assert!(edited.contains(
+		"OBJS = $(SRCS:.c=.o)\n\n# sticks:begin vendor_lib\nLIB_SRCS = $(wildcard lib/*.c)\n"
+	));
+	assert!(edited.contains("CFLAGS = -Wall \\\n\t-Wextra\n"));
+
+	let mut makefile = Makefile::parse(&edited);
+	assert_eq!(makefile.region("vendor_lib").unwrap().body.len(), 2);
+	assert!(makefile.variable("LIB_SRCS").is_none());
+	assert!(makefile.remove_region("vendor_lib"));
+	assert!(makefile.set_variable("LDFLAGS", ""));
+	assert_eq!(makefile.render(), MAKEFILE);
+}
@@ -1,4 +1,6 @@
-use sticks::{get_package_manager_generator, PackageManager};
+use sticks::package_managers::{ConanGenerator, ConanRecipeGenerator, PackageSpec, VcpkgGenerator};
+use sticks::BuildSystem;
+use sticks::{get_package_manager_generator, PackageManager, PackageManagerGenerator};
 
 #[test]
 fn test_package_manager_display() {
@@ -99,3 +101,167 @@ fn test_vcpkg_manifest_structure() {
 	assert!(manifest.contains("\"name\""));
 	assert!(manifest.contains("test_app"));
 }
+
+#[test]
+fn test_package_spec_from_str() {
+	let spec: PackageSpec = "fmt/10.2.1".parse().unwrap();
+	assert_eq!(spec.name, "fmt");
+	assert_eq!(spec.version.as_deref(), Some("10.2.1"));
+	assert_eq!(spec.to_string(), "fmt/10.2.1");
+
+	let spec: PackageSpec = "zlib".parse().unwrap();
+	assert_eq!(spec.version, None);
+
+	assert!("/1.0".parse::<PackageSpec>().is_err());
+	assert!("fmt/".parse::<PackageSpec>().is_err());
+}
+
+#[test]
+fn test_conan_add_and_remove_dependencies() {
+	let generator = ConanGenerator;
+	let manifest = generator.generate_manifest("app");
+	let specs = vec!["fmt/10.2.1".parse().unwrap(), "zlib/1.3.1".parse().unwrap()];
+
+	let updated = generator.add_dependencies(&manifest, &specs).unwrap();
+	assert!(updated.starts_with("[requires]\nfmt/10.2.1\nzlib/1.3.1\n\n[generators]"));
+	assert_eq!(generator.dependencies(&updated).unwrap(), specs);
+
+	let bumped = generator
+		.add_dependencies(&updated, &["fmt/11.0.2".parse().unwrap()])
+		.unwrap();
+	assert!(bumped.contains("fmt/11.0.2\nzlib/1.3.1\n"));
+	assert!(!bumped.contains("fmt/10.2.1"));
+
+	let removed = generator
+		.remove_dependencies(&bumped, &["fmt".to_string(), "zlib".to_string()])
+		.unwrap();
+	assert_eq!(removed, manifest);
+
+	assert!(generator
+		.add_dependencies(&manifest, &["fmt".parse().unwrap()])
+		.is_err());
+}
+
+#[test]
+fn test_vcpkg_add_and_remove_dependencies() {
+	let generator = VcpkgGenerator;
+	let manifest = generator.generate_manifest("app");
+
+	let updated = generator
+		.add_dependencies(
+			&manifest,
+			&["fmt/10.2.1".parse().unwrap(), "zlib".parse().unwrap()],
+		)
+		.unwrap();
+	assert!(updated.contains("{\n      \"name\": \"fmt\",\n      \"version>=\": \"10.2.1\"\n    }"));
+	assert!(updated.contains("\"zlib\""));
+	assert_eq!(
+		generator.dependencies(&updated).unwrap(),
+		vec![
+			"fmt/10.2.1".parse::<PackageSpec>().unwrap(),
+			"zlib".parse().unwrap()
+		]
+	);
+
+	let removed = generator
+		.remove_dependencies(&updated, &["fmt".to_string()])
+		.unwrap();
+	assert!(!removed.contains("fmt"));
+	assert!(removed.contains("\"name\": \"app\""));
+	assert!(removed.contains("\"zlib\""));
+}
+
+#[test]
+fn test_conan_recipe_from_str() {
+	assert_eq!(
+		"conan-py".parse::<PackageManager>().unwrap(),
+		PackageManager::ConanRecipe
+	);
+	assert_eq!(
+		"conanfile.py".parse::<PackageManager>().unwrap(),
+		PackageManager::ConanRecipe
+	);
+	let generator = get_package_manager_generator(PackageManager::ConanRecipe);
+	assert_eq!(generator.extension(), "conanfile.py");
+}
+
+#[test]
+fn test_conan_recipe_generation() {
+	let cmake = ConanRecipeGenerator.generate_recipe("my-app", BuildSystem::CMake, &[]);
+	assert!(cmake.starts_with("from conan import ConanFile\n"));
+	assert!(cmake.contains("class MyAppConan(ConanFile):\n"));
+	assert!(ConanRecipeGenerator
+		.generate_recipe("2048-game", BuildSystem::CMake, &[])
+		.contains("class Pkg2048GameConan(ConanFile):\n"));
+	assert!(ConanRecipeGenerator
+		.generate_recipe("___", BuildSystem::CMake, &[])
+		.contains("class PkgConan(ConanFile):\n"));
+	assert!(cmake.contains("    name = \"my-app\"\n"));
+	for method in ["requirements", "layout", "generate", "build", "package"] {
+		assert!(cmake.contains(&format!("    def {}(self):\n", method)));
+	}
+	assert!(cmake.contains("        cmake_layout(self)\n"));
+	assert!(cmake.contains(
+		"        # sticks:begin requires\n        pass\n        # sticks:end requires\n"
+	));
+
+	let makefile = ConanRecipeGenerator.generate_recipe(
+		"tool",
+		BuildSystem::Makefile,
+		&["zlib/1.3.1".parse().unwrap()],
+	);
+	assert!(makefile.starts_with("import os\n"));
+	assert!(makefile.contains("        PkgConfigDeps(self).generate()\n"));
+	assert!(makefile.contains("        self.requires(\"zlib/1.3.1\")\n"));
+	assert!(!makefile.contains("        pass\n"));
+	assert!(!makefile.contains('\t'));
+}
+
+#[test]
+fn test_conan_recipe_round_trips_requirements() {
+	let generator = ConanRecipeGenerator;
+	let recipe = generator.generate_manifest("app");
+
+	let updated = generator
+		.add_dependencies(
+			&recipe,
+			&["fmt/10.2.1".parse().unwrap(), "zlib/1.3.1".parse().unwrap()],
+		)
+		.unwrap();
+	assert_eq!(
+		generator.dependencies(&updated).unwrap(),
+		vec![
+			"fmt/10.2.1".parse::<PackageSpec>().unwrap(),
+			"zlib/1.3.1".parse().unwrap()
+		]
+	);
+
+	let bumped = generator
+		.add_dependencies(&updated, &["fmt/11.0.2".parse().unwrap()])
+		.unwrap();
+	assert!(bumped.contains("self.requires(\"fmt/11.0.2\")\n        self.requires(\"zlib/1.3.1\")"));
+
+	let removed = generator
+		.remove_dependencies(&bumped, &["fmt".to_string(), "zlib".to_string()])
+		.unwrap();
+	assert_eq!(removed, recipe);
+
+	assert!(generator
+		.add_dependencies(&recipe, &["fmt".parse().unwrap()])
+		.is_err());
+	assert!(generator
+		.add_dependencies("class X:\n\tpass\n", &["fmt/1.0".parse().unwrap()])
+		.is_err());
+}
+
+#[test]
+fn test_conan_recipe_keeps_existing_indentation() {
+	let legacy = "class App(ConanFile):\n\tdef requirements(self):\n\t\t# sticks:begin requires\n\t\tpass\n\t\t# sticks:end requires\n";
+	let updated = ConanRecipeGenerator
+		.add_dependencies(legacy, &["fmt/10.2.1".parse().unwrap()])
+		.unwrap();
+	assert_eq!(
+		updated,
+		"class App(ConanFile):\n\tdef requirements(self):\n\t\t# sticks:begin requires\n\t\tself.requires(\"fmt/10.2.1\")\n\t\t# sticks:end requires\n"
+	);
+}
@@ -0,0 +1,341 @@
+use serial_test::serial;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::Command;
+use sticks::project_templates::{
+	current_year, is_git_url, new_project_from_template, render_placeholders, TemplateManifest,
+	TemplateOptions, TemplatePrompt,
+};
+use sticks::{Language, ProjectConfig};
+
+fn write_template(dir: &Path) {
+	fs::create_dir_all(dir.join("src")).unwrap();
+	fs::create_dir_all(dir.join("scripts")).unwrap();
+	fs::write(
+		dir.join("template.toml"),
+		"[template]\ndescription = \"Command line tool\"\nlanguage = \"c\"\n\n[prompts]\nauthor = \"Author name\"\nlicense = [\"License\", \"MIT\"]\n\n[hooks]\npost_create = [\"echo {{project_name}} > hook.txt\"]\n",
+	)
+	.unwrap();
+	fs::write(
+		dir.join("src").join("{{project_name}}.c"),
+		"/* {{project_name}} (c) {{year}} {{author}}, {{license}} */\nint main(void) { return 0; }\n",
+	)
+	.unwrap();
+	fs::write(
+		dir.join("README.md"),
+		"# {{ project_name }}\n\nWritten in {{language}}. Keep {{unknown}} as is.\n",
+	)
+	.unwrap();
+	fs::write(
+		dir.join("scripts").join("run.sh"),
+		"#!/bin/sh\n./{{project_name}}\n",
+	)
+	.unwrap();
+	fs::set_permissions(
+		dir.join("scripts").join("run.sh"),
+		fs::Permissions::from_mode(0o755),
+	)
+	.unwrap();
+	fs::write(dir.join("logo.bin"), [0xff, 0xfe, 0x7b, 0x7b, 0x00]).unwrap();
+}
+
+#[test]
+fn test_render_placeholders() {
+	let mut values = HashMap::new();
+	values.insert("project_name".to_string(), "demo".to_string());
+	values.insert("year".to_string(), "2024".to_string());
+
+	assert_eq!(
+		render_placeholders("{{project_name}} {{ year }} {{other}} {{", &values),
+		"demo 2024 {{other}} {{"
+	);
+	assert_eq!(
+		render_placeholders("no placeholders", &values),
+		"no placeholders"
+	);
+}
+
+#[test]
+fn test_current_year_is_plausible() {
+	assert!(current_year() >= 2024);
+}
+
+#[test]
+fn test_is_git_url() {
+	assert!(is_git_url("https://github.com/user/template"));
+	assert!(is_git_url("git@github.com:user/template.git"));
+	assert!(is_git_url("file:///tmp/template"));
+	assert!(!is_git_url("./templates/cli"));
+	assert!(!is_git_url("cli"));
+}
+
+#[test]
+fn test_parse_template_manifest() {
+	let manifest = TemplateManifest::parse(
+		"[template]\nlanguage = \"cpp\"\n\n[prompts]\nauthor = \"Author name\"\nlicense = [\"License\", \"MIT\"]\n\n[hooks]\npost_create = \"git init\"\n",
+	)
+	.unwrap();
+
+	assert_eq!(manifest.language, Some(Language::Cpp));
+	assert_eq!(
+		manifest.prompts,
+		vec![
+			TemplatePrompt {
+				key: "author".to_string(),
+				question: "Author name".to_string(),
+				default: None,
+			},
+			TemplatePrompt {
+				key: "license".to_string(),
+				question: "License".to_string(),
+				default: Some("MIT".to_string()),
+			},
+		]
+	);
+	assert_eq!(manifest.post_create, vec!["git init".to_string()]);
+}
+
+#[test]
+#[serial]
+fn test_new_project_from_local_template() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_template_local_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+	let template = temp_dir.join("cli-template");
+
+	fs::remove_dir_all(&temp_dir).ok();
+	write_template(&template);
+	env::set_current_dir(&temp_dir).unwrap();
+
+	let options = TemplateOptions {
+		template: "./cli-template".to_string(),
+		language: None,
+		values: vec![
+			("author".to_string(), "Ada".to_string()),
+			("license".to_string(), "BSD".to_string()),
+		],
+		run_hooks: None,
+	};
+	let result = new_project_from_template("demo", &options);
+	env::set_current_dir(&original_dir).unwrap();
+	result.unwrap();
+
+	let project = temp_dir.join("demo");
+	let source = fs::read_to_string(project.join("src").join("demo.c")).unwrap();
+	assert!(source.contains(&format!("/* demo (c) {} Ada, BSD */", current_year())));
+	let readme = fs::read_to_string(project.join("README.md")).unwrap();
+	assert!(readme.contains("# demo"));
+	assert!(readme.contains("Written in c."));
+	assert!(readme.contains("{{unknown}}"));
+	assert_eq!(
+		fs::read(project.join("logo.bin")).unwrap(),
+		vec![0xff, 0xfe, 0x7b, 0x7b, 0x00]
+	);
+	let mode = fs::metadata(project.join("scripts").join("run.sh"))
+		.unwrap()
+		.permissions()
+		.mode();
+	assert_ne!(mode & 0o111, 0);
+	assert!(!project.join("template.toml").exists());
+	assert_eq!(
+		fs::read_to_string(project.join("hook.txt")).unwrap().trim(),
+		"demo"
+	);
+
+	env::set_current_dir(&project).unwrap();
+	let config = ProjectConfig::load().unwrap().unwrap();
+	env::set_current_dir(&original_dir).unwrap();
+	assert_eq!(config.name, "demo");
+	assert_eq!(config.language, Some(Language::C));
+
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_new_project_from_named_template_without_hooks() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_template_named_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+	let original_config = env::var_os("XDG_CONFIG_HOME");
+	let config_home = temp_dir.join("config");
+
+	fs::remove_dir_all(&temp_dir).ok();
+	write_template(&config_home.join("sticks").join("templates").join("cli"));
+	env::set_var("XDG_CONFIG_HOME", &config_home);
+	env::set_current_dir(&temp_dir).unwrap();
+
+	let options = TemplateOptions {
+		template: "cli".to_string(),
+		language: Some(Language::Cpp),
+		values: vec![
+			("author".to_string(), "Ada".to_string()),
+			("license".to_string(), "MIT".to_string()),
+		],
+		run_hooks: Some(false),
+	};
+	let result = new_project_from_template("tool", &options);
+	env::set_current_dir(&temp_dir).unwrap();
+	let missing = new_project_from_template(
+		"other",
+		&TemplateOptions {
+			template: "missing".to_string(),
+			language: None,
+			values: Vec::new(),
+			run_hooks: Some(false),
+		},
+	);
+	env::set_current_dir(&original_dir).unwrap();
+	match original_config {
+		Some(value) => env::set_var("XDG_CONFIG_HOME", value),
+		None => env::remove_var("XDG_CONFIG_HOME"),
+	}
+	result.unwrap();
+
+	let project = temp_dir.join("tool");
+	assert!(project.join("src").join("tool.c").exists());
+	assert!(!project.join("hook.txt").exists());
+	let readme = fs::read_to_string(project.join("README.md")).unwrap();
+	assert!(readme.contains("Written in cpp."));
+
+	let err = missing.unwrap_err().to_string();
+	assert!(err.contains("Template 'missing' not found"));
+	assert!(err.contains("cli"));
+	assert!(!temp_dir.join("other").exists());
+
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_new_project_from_git_template() {
+	if Command::new("git").arg("--version").output().is_err() {
+		return;
+	}
+
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_template_git_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+	let template = temp_dir.join("repo");
+
+	fs::remove_dir_all(&temp_dir).ok();
+	write_template(&template);
+	let git = |args: &[&str]| {
+		Command::new("git")
+			.args([
+				"-c",
+				"user.name=sticks",
+				"-c",
+				"user.email=sticks@example.com",
+			])
+			.args(args)
+			.current_dir(&template)
+			.output()
+			.unwrap()
+	};
+	git(&["init", "--quiet"]);
+	git(&["add", "-A"]);
+	assert!(git(&["commit", "--quiet", "-m", "template"])
+		.status
+		.success());
+	env::set_current_dir(&temp_dir).unwrap();
+
+	let options = TemplateOptions {
+		template: format!("file://{}", template.display()),
+		language: None,
+		values: vec![
+			("author".to_string(), "Ada".to_string()),
+			("license".to_string(), "MIT".to_string()),
+		],
+		run_hooks: Some(true),
+	};
+	let result = new_project_from_template("cloned", &options);
+	env::set_current_dir(&original_dir).unwrap();
+	result.unwrap();
+
+	let project = temp_dir.join("cloned");
+	assert!(project.join("src").join("cloned.c").exists());
+	assert!(!project.join(".git").exists());
+	assert!(project.join("hook.txt").exists());
+
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_template_symlinks_are_not_followed() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_template_links_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+	let template = temp_dir.join("linked");
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(template.join("docs")).unwrap();
+	fs::write(
+		template.join("docs").join("guide.md"),
+		"# {{project_name}}\n",
+	)
+	.unwrap();
+	std::os::unix::fs::symlink("docs/guide.md", template.join("GUIDE.md")).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+
+	let options = TemplateOptions {
+		template: "./linked".to_string(),
+		language: None,
+		values: Vec::new(),
+		run_hooks: Some(false),
+	};
+	let result = new_project_from_template("linked-app", &options);
+	env::set_current_dir(&original_dir).unwrap();
+	result.unwrap();
+
+	let link = temp_dir.join("linked-app").join("GUIDE.md");
+	assert!(fs::symlink_metadata(&link)
+		.unwrap()
+		.file_type()
+		.is_symlink());
+	assert_eq!(
+		fs::read_link(&link).unwrap(),
+		Path::new("docs").join("guide.md")
+	);
+
+	std::os::unix::fs::symlink("/etc/passwd", template.join("leak.txt")).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+	let result = new_project_from_template("leaky", &options);
+	env::set_current_dir(&original_dir).unwrap();
+
+	assert!(result
+		.unwrap_err()
+		.to_string()
+		.contains("points outside the template"));
+	assert!(!temp_dir.join("leaky").exists());
+
+	fs::remove_dir_all(&temp_dir).ok();
+}
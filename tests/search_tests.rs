@@ -0,0 +1,112 @@
+use serial_test::serial;
+use std::env;
+use std::fs;
+use sticks::search::{parse_host_search, search, search_known, search_vcpkg, SearchSource};
+use sticks::HostPackageManager;
+
+#[test]
+fn test_search_known_packages_by_alias() {
+	let results = search_known("ssl");
+	let openssl = results
+		.iter()
+		.find(|r| r.add_name == "openssl")
+		.expect("openssl should match its 'ssl' alias");
+	assert_eq!(openssl.source, SearchSource::Known);
+	assert!(search_known("no-such-library").is_empty());
+}
+
+#[test]
+fn test_parse_host_search_outputs() {
+	let apt = parse_host_search(
+		HostPackageManager::Apt,
+		"zlib1g-dev - compression library - development\n",
+	);
+	assert_eq!(
+		apt,
+		vec![(
+			"zlib1g-dev".to_string(),
+			"compression library - development".to_string()
+		)]
+	);
+
+	let pacman = parse_host_search(
+		HostPackageManager::Pacman,
+		"core/zlib 1:1.3.1-2 [installed]\n    Compression library\nextra/zlib-ng 2.2.2-1\n    zlib replacement\n",
+	);
+	assert_eq!(
+		pacman[0],
+		("zlib".to_string(), "Compression library".to_string())
+	);
+	assert_eq!(pacman[1].0, "zlib-ng");
+
+	let dnf = parse_host_search(
+		HostPackageManager::Dnf,
+		"======== Name Matched: zlib ========\nzlib-devel.x86_64 : Header files for zlib\n",
+	);
+	assert_eq!(
+		dnf,
+		vec![(
+			"zlib-devel".to_string(),
+			"Header files for zlib".to_string()
+		)]
+	);
+
+	let apk = parse_host_search(
+		HostPackageManager::Apk,
+		"zlib-dev-1.3.1-r1 - A compression/decompression Library (development files)\n",
+	);
+	assert_eq!(apk[0].0, "zlib-dev");
+}
+
+#[test]
+#[serial]
+fn test_search_vcpkg_ports() {
+	let root = env::temp_dir().join(format!(
+		"sticks_test_search_vcpkg_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	fs::create_dir_all(root.join("ports/fmt")).unwrap();
+	fs::create_dir_all(root.join("ports/zlib")).unwrap();
+	fs::write(
+		root.join("ports/fmt/vcpkg.json"),
+		"{\"name\": \"fmt\", \"version\": \"10.2.1\", \"description\": \"Formatting library\"}",
+	)
+	.unwrap();
+
+	let previous = env::var("VCPKG_ROOT").ok();
+	env::set_var("VCPKG_ROOT", &root);
+	let results = search_vcpkg("fm").unwrap();
+	match previous {
+		Some(value) => env::set_var("VCPKG_ROOT", value),
+		None => env::remove_var("VCPKG_ROOT"),
+	}
+
+	assert_eq!(results.len(), 1);
+	assert_eq!(results[0].add_name, "fmt");
+	assert_eq!(results[0].package, "fmt 10.2.1");
+	assert_eq!(results[0].description, "Formatting library");
+
+	fs::remove_dir_all(&root).ok();
+}
+
+#[test]
+#[serial]
+fn test_search_lists_pkg_config_modules() {
+	let installed = std::process::Command::new("pkg-config")
+		.args(["--exists", "zlib"])
+		.status()
+		.is_ok_and(|s| s.success());
+	if !installed {
+		return;
+	}
+
+	let results = search("zlib").unwrap();
+	assert!(results
+		.iter()
+		.any(|r| r.source == SearchSource::PkgConfig && r.add_name == "zlib"));
+	assert!(search("  ").is_err());
+}
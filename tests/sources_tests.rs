@@ -2,7 +2,12 @@ use serial_test::serial;
 use std::env;
 use std::fs;
 use std::path::Path;
-use sticks::add_sources;
+use std::process::Command;
+use sticks::sources::{ensure_mixed_sources, move_source, remove_sources};
+use sticks::{
+	add_sources, add_sources_with_visibility, BuildSystemGenerator, CMakeGenerator, Language,
+	LanguageConsts,
+};
 
 #[test]
 #[serial]
@@ -51,6 +56,13 @@ fn test_add_sources_success() {
 	fs::create_dir("src").unwrap();
 	fs::write("src/main.c", "int main() {}").unwrap();
 
+	fs::write(
+		"sticks.toml",
+		"[project]\nname = \"demo\"\nlanguage = \"c\"\n",
+	)
+	.unwrap();
+	fs::write("Makefile", "CFLAGS = -Wall\nLDFLAGS =\n").unwrap();
+
 	let result = add_sources(&["utils", "network"]);
 	assert!(result.is_ok());
 
@@ -59,22 +71,360 @@ fn test_add_sources_success() {
 		"src/utils.c should exist"
 	);
 	assert!(
-		Path::new("src/utils.h").exists(),
-		"src/utils.h should exist"
+		Path::new("include/demo/utils.h").exists(),
+		"include/demo/utils.h should exist"
 	);
 	assert!(
 		Path::new("src/network.c").exists(),
 		"src/network.c should exist"
 	);
 	assert!(
-		Path::new("src/network.h").exists(),
-		"src/network.h should exist"
+		Path::new("include/demo/network.h").exists(),
+		"include/demo/network.h should exist"
 	);
+	assert!(!Path::new("src/utils.h").exists());
 
-	let header_content = fs::read_to_string("src/utils.h").unwrap();
-	assert!(header_content.contains("#ifndef UTILS_H"));
-	assert!(header_content.contains("#define UTILS_H"));
+	let header_content = fs::read_to_string("include/demo/utils.h").unwrap();
+	assert!(header_content.contains("#ifndef DEMO_UTILS_H"));
+	assert!(header_content.contains("#define DEMO_UTILS_H"));
 	assert!(header_content.contains("#endif"));
+	assert_eq!(
+		fs::read_to_string("src/utils.c").unwrap(),
+		"#include \"demo/utils.h\"\n"
+	);
+	assert_eq!(
+		fs::read_to_string("Makefile").unwrap(),
+		"CFLAGS = -Wall -Iinclude\nLDFLAGS =\n"
+	);
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_add_private_sources_and_hpp_headers() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_private_src_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+	fs::create_dir("src").unwrap();
+	fs::write(
+		"sticks.toml",
+		"[project]\nname = \"engine\"\nlanguage = \"cpp\"\nheader_extension = \"hpp\"\n",
+	)
+	.unwrap();
+
+	add_sources_with_visibility(&["helpers"], true).unwrap();
+	assert_eq!(
+		fs::read_to_string("src/helpers.cpp").unwrap(),
+		"#include \"helpers.hpp\"\n"
+	);
+	let header = fs::read_to_string("src/helpers.hpp").unwrap();
+	assert!(header.starts_with("#ifndef ENGINE_HELPERS_HPP\n#define ENGINE_HELPERS_HPP\n"));
+	assert!(!Path::new("include").exists());
+
+	add_sources(&["renderer"]).unwrap();
+	assert!(Path::new("include/engine/renderer.hpp").exists());
+	assert_eq!(
+		fs::read_to_string("src/renderer.cpp").unwrap(),
+		"#include \"engine/renderer.hpp\"\n"
+	);
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_add_nested_sources() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_nested_src_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+	fs::create_dir("src").unwrap();
+	fs::write(
+		"sticks.toml",
+		"[project]\nname = \"my-app\"\nlanguage = \"c\"\n",
+	)
+	.unwrap();
+	let legacy = Language::C
+		.generate_makefile_content("my-app")
+		.replace(
+			"$(shell find $(SRC_DIR) -name '*.c')",
+			"$(wildcard $(SRC_DIR)/*.c)",
+		)
+		.replace("@mkdir -p $(dir $@)", "@mkdir -p $(BUILD_DIR)");
+	fs::write("Makefile", &legacy).unwrap();
+	fs::write(
+		"src/main.c",
+		"#include \"my-app/net/socket.h\"\n#include \"my-app/util/strings.h\"\n\nint main(void) { return 0; }\n",
+	)
+	.unwrap();
+
+	add_sources(&["net/socket", "src/util/strings.c"]).unwrap();
+	assert!(Path::new("src/net/socket.c").exists());
+	assert!(Path::new("src/util/strings.c").exists());
+	let header = fs::read_to_string("include/my-app/net/socket.h").unwrap();
+	assert!(header.starts_with("#ifndef MY_APP_NET_SOCKET_H\n#define MY_APP_NET_SOCKET_H\n"));
+
+	add_sources_with_visibility(&["util/buffer"], true).unwrap();
+	assert_eq!(
+		fs::read_to_string("src/util/buffer.c").unwrap(),
+		"#include \"buffer.h\"\n"
+	);
+	assert!(fs::read_to_string("src/util/buffer.h")
+		.unwrap()
+		.contains("#define MY_APP_UTIL_BUFFER_H\n"));
+
+	for invalid in ["../escape", "/abs/path", "net//socket"] {
+		assert!(
+			add_sources(&[invalid]).is_err(),
+			"{} should be rejected",
+			invalid
+		);
+	}
+
+	let makefile = fs::read_to_string("Makefile").unwrap();
+	assert_eq!(makefile, Language::C.generate_makefile_content("my-app"));
+
+	if Command::new("make").arg("--version").output().is_ok() {
+		let status = Command::new("make").status().unwrap();
+		assert!(status.success());
+		assert!(Path::new("build/net/socket.o").exists());
+		assert!(Path::new("build/util/buffer.o").exists());
+	}
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_move_and_remove_sources() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_mv_src_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+	fs::create_dir("src").unwrap();
+	fs::write(
+		"sticks.toml",
+		"[project]\nname = \"demo\"\nlanguage = \"c\"\n",
+	)
+	.unwrap();
+	add_sources(&["utils", "parser"]).unwrap();
+	add_sources_with_visibility(&["lexer"], true).unwrap();
+	fs::write(
+		"src/main.c",
+		"#include \"demo/utils.h\"\n#include \"lexer.h\"\n\n#ifdef DEMO_UTILS_H\nint main(void) { return 0; }\n#endif\n",
+	)
+	.unwrap();
+	fs::write(
+		"CMakeLists.txt",
+		"add_executable(demo\n\tsrc/main.c\n\tsrc/utils.c\n\t\"${CMAKE_CURRENT_SOURCE_DIR}/src/lexer.c\"\n\tsrc/parser.c)\n# src/utils.c stays in this comment\nmessage(\"src/parser.c\")\nset(NOTES [[src/parser.c]])\n",
+	)
+	.unwrap();
+	fs::write(
+		"Makefile",
+		"SRCS = src/main.c src/utils.c $(SRC_DIR)/parser.c\n\nlint:\n\tcheck src/parser.c\n",
+	)
+	.unwrap();
+
+	move_source("utils", "net/strings").unwrap();
+	move_source("src/lexer.c", "front/lexer").unwrap();
+	assert!(!Path::new("src/utils.c").exists());
+	assert!(!Path::new("include/demo/utils.h").exists());
+	assert_eq!(
+		fs::read_to_string("src/net/strings.c").unwrap(),
+		"#include \"demo/net/strings.h\"\n"
+	);
+	assert!(fs::read_to_string("include/demo/net/strings.h")
+		.unwrap()
+		.starts_with("#ifndef DEMO_NET_STRINGS_H\n#define DEMO_NET_STRINGS_H\n"));
+	assert_eq!(
+		fs::read_to_string("src/front/lexer.c").unwrap(),
+		"#include \"lexer.h\"\n"
+	);
+	assert!(fs::read_to_string("src/front/lexer.h")
+		.unwrap()
+		.contains("DEMO_FRONT_LEXER_H"));
+	assert_eq!(
+		fs::read_to_string("src/main.c").unwrap(),
+		"#include \"demo/net/strings.h\"\n#include \"front/lexer.h\"\n\n#ifdef DEMO_NET_STRINGS_H\nint main(void) { return 0; }\n#endif\n"
+	);
+
+	remove_sources(&["parser"]).unwrap();
+	assert!(!Path::new("src/parser.c").exists());
+	assert!(!Path::new("include/demo/parser.h").exists());
+	assert_eq!(
+		fs::read_to_string("CMakeLists.txt").unwrap(),
+		"add_executable(demo\n\tsrc/main.c\n\tsrc/net/strings.c\n\t\"${CMAKE_CURRENT_SOURCE_DIR}/src/front/lexer.c\"\n\t)\n# src/utils.c stays in this comment\nmessage(\"src/parser.c\")\nset(NOTES [[src/parser.c]])\n"
+	);
+	assert_eq!(
+		fs::read_to_string("Makefile").unwrap(),
+		"SRCS = src/main.c src/net/strings.c\n\nlint:\n\tcheck src/parser.c\n"
+	);
+
+	assert!(move_source("missing", "other").is_err());
+	assert!(move_source("net/strings", "front/lexer").is_err());
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_add_c_sources_to_cpp_project() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_mixed_src_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(temp_dir.join("src")).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+	let config = "[project]\nname = \"mixed\"\nlanguage = \"cpp\"\n";
+	fs::write("sticks.toml", config).unwrap();
+	fs::write("Makefile", Language::Cpp.generate_makefile_content("mixed")).unwrap();
+	fs::write(
+		"CMakeLists.txt",
+		CMakeGenerator.generate_build_file(Language::Cpp, "mixed"),
+	)
+	.unwrap();
+	fs::write(
+		"src/main.cpp",
+		"#include \"mixed/checksum.h\"\n\nint main() { return checksum() == 42 ? 0 : 1; }\n",
+	)
+	.unwrap();
+
+	add_sources(&["checksum.c", "parser"]).unwrap();
+	assert!(Path::new("src/checksum.c").exists());
+	assert!(Path::new("src/parser.cpp").exists());
+	let header = fs::read_to_string("include/mixed/checksum.h").unwrap();
+	assert!(header.contains("#ifdef __cplusplus\nextern \"C\" {\n#endif\n"));
+	assert!(!fs::read_to_string("include/mixed/parser.h")
+		.unwrap()
+		.contains("extern \"C\""));
+	fs::write(
+		"include/mixed/checksum.h",
+		header.replace(
+			"extern \"C\" {\n#endif\n",
+			"extern \"C\" {\n#endif\n\nint checksum(void);\n",
+		),
+	)
+	.unwrap();
+	let mut source = fs::read_to_string("src/checksum.c").unwrap();
+	source.push_str("\nint checksum(void) { return 42; }\n");
+	fs::write("src/checksum.c", source).unwrap();
+
+	let makefile = fs::read_to_string("Makefile").unwrap();
+	assert!(makefile.contains("CC = g++\n"));
+	assert!(makefile.contains("# sticks:begin c-sources\nC_COMPILER = gcc\n"));
+	assert!(makefile.contains("$(BUILD_DIR)/%.o: $(SRC_DIR)/%.c\n"));
+	let cmake = fs::read_to_string("CMakeLists.txt").unwrap();
+	assert!(cmake.contains("project(mixed C CXX)\n"));
+	assert!(cmake.contains(
+		"CONFIGURE_DEPENDS \"src/*.cpp\" \"src/*.cc\" \"src/*.cxx\" \"src/*.C\" \"src/*.c\")\n"
+	));
+
+	ensure_mixed_sources().unwrap();
+	assert_eq!(fs::read_to_string("Makefile").unwrap(), makefile);
+	assert_eq!(fs::read_to_string("CMakeLists.txt").unwrap(), cmake);
+
+	fs::write(
+		"sticks.toml",
+		"[project]\nname = \"mixed\"\nlanguage = \"c\"\n",
+	)
+	.unwrap();
+	assert!(add_sources(&["widget.cpp"])
+		.unwrap_err()
+		.to_string()
+		.contains("C project"));
+	fs::write("sticks.toml", config).unwrap();
+
+	if Command::new("make").arg("--version").output().is_ok() {
+		let status = Command::new("make").arg("run").status().unwrap();
+		assert!(status.success());
+		assert!(Path::new("build/checksum.o").exists());
+		assert!(Path::new("build/parser.o").exists());
+	}
+
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_add_sources_to_c_fortran_project() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_fortran_src_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(temp_dir.join("src")).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+	fs::write(
+		"sticks.toml",
+		"[project]\nname = \"solver\"\nlanguage = \"c-fortran\"\n",
+	)
+	.unwrap();
+
+	add_sources(&["num/2d-grid.f90", "bridge"]).unwrap();
+	assert_eq!(
+		fs::read_to_string("src/num/2d-grid.f90").unwrap(),
+		"module m_2d_grid\n    implicit none\n    private\ncontains\nend module m_2d_grid\n"
+	);
+	assert!(!Path::new("include/solver/num/2d-grid.h").exists());
+	assert!(Path::new("src/bridge.c").exists());
+	assert!(Path::new("include/solver/bridge.h").exists());
+
+	fs::write(
+		"sticks.toml",
+		"[project]\nname = \"solver\"\nlanguage = \"c\"\n",
+	)
+	.unwrap();
+	assert!(add_sources(&["kernel.f90"])
+		.unwrap_err()
+		.to_string()
+		.contains("c-fortran"));
 
 	env::set_current_dir(&original_dir).unwrap();
 	fs::remove_dir_all(&temp_dir).ok();
@@ -0,0 +1,74 @@
+use sticks::system_packages::{
+	cmake_link_block, find_known_package, generate_install_script, translate_package,
+	HostPackageManager,
+};
+
+#[test]
+fn test_host_package_manager_from_str() {
+	assert!(matches!(
+		"apt".parse::<HostPackageManager>(),
+		Ok(HostPackageManager::Apt)
+	));
+	assert!(matches!(
+		"DNF".parse::<HostPackageManager>(),
+		Ok(HostPackageManager::Dnf)
+	));
+	assert!(matches!(
+		"pacman".parse::<HostPackageManager>(),
+		Ok(HostPackageManager::Pacman)
+	));
+	assert!("portage".parse::<HostPackageManager>().is_err());
+}
+
+#[test]
+fn test_translate_known_packages() {
+	assert_eq!(
+		translate_package("libcurl", HostPackageManager::Apt).as_deref(),
+		Some("libcurl4-openssl-dev")
+	);
+	assert_eq!(
+		translate_package("libcurl", HostPackageManager::Dnf).as_deref(),
+		Some("libcurl-devel")
+	);
+	assert_eq!(
+		translate_package("libcurl", HostPackageManager::Pacman).as_deref(),
+		Some("curl")
+	);
+	assert_eq!(
+		translate_package("curl", HostPackageManager::Apk).as_deref(),
+		Some("curl-dev")
+	);
+	assert_eq!(translate_package("pthread", HostPackageManager::Apt), None);
+}
+
+#[test]
+fn test_translate_unknown_package_passes_through() {
+	assert!(find_known_package("libfoo-dev").is_none());
+	assert_eq!(
+		translate_package("libfoo-dev", HostPackageManager::Zypper).as_deref(),
+		Some("libfoo-dev")
+	);
+}
+
+#[test]
+fn test_generate_install_script() {
+	let script = generate_install_script(&["openssl".to_string(), "custom".to_string()]);
+	assert!(script.starts_with("#!/bin/sh"));
+	assert!(script.contains("command -v apt-get"));
+	assert!(script.contains("command -v brew"));
+	assert!(script.contains("apt:openssl) echo \"libssl-dev\""));
+	assert!(script.contains("brew:openssl) echo \"openssl@3\""));
+	assert!(!script.contains(":custom)"));
+	assert!(script.contains("pacman) $SUDO pacman -S --needed --noconfirm $packages"));
+	assert!(script.contains("brew) brew install $packages"));
+}
+
+#[test]
+fn test_cmake_link_block_skips_unknown_packages() {
+	let block = cmake_link_block(&["fmt".to_string(), "tinyxml2".to_string()]).unwrap();
+	assert_eq!(
+		block,
+		"find_package(fmt REQUIRED)\ntarget_link_libraries(${PROJECT_NAME} PRIVATE fmt::fmt)\n"
+	);
+	assert!(cmake_link_block(&["tinyxml2".to_string()]).is_none());
+}
@@ -54,6 +54,17 @@ fn test_generate_clang_format_cpp() {
 	assert!(config.contains("Cpp11BracedListStyle: true"));
 }
 
+#[test]
+fn test_generate_clang_format_objc() {
+	for language in [Language::ObjC, Language::ObjCpp] {
+		let config = generate_clang_format_config(language);
+		assert!(config.contains("Language: ObjC\n"));
+		assert!(config.contains("ObjCBlockIndentWidth: 4"));
+		assert!(!config.contains("Standard:"));
+	}
+	assert!(generate_vscode_settings(Language::ObjC).contains("\"[objective-c]\""));
+}
+
 #[test]
 fn test_generate_vscode_settings_c() {
 	let settings = generate_vscode_settings(Language::C);
@@ -0,0 +1,171 @@
+use serial_test::serial;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use sticks::vcpkg::{add_presets, configuration, integrate, remove_integration, remove_presets};
+use sticks::{add_dependencies, BuildSystemGenerator, Language, PackageManagerGenerator};
+
+#[test]
+fn test_presets_merge_with_existing_file() {
+	let existing = "{\n  \"version\": 6,\n  \"configurePresets\": [\n    {\n      \"name\": \"debug\"\n    }\n  ]\n}\n";
+	let merged = add_presets(existing).unwrap();
+	assert!(merged.contains("\"version\": 6"));
+	assert!(merged.contains("\"name\": \"debug\""));
+	assert!(merged.contains("$env{VCPKG_ROOT}/scripts/buildsystems/vcpkg.cmake"));
+	assert_eq!(add_presets(&merged).unwrap(), merged);
+
+	assert_eq!(remove_presets(&merged).unwrap().as_deref(), Some(existing));
+	assert_eq!(remove_presets(&add_presets("{}").unwrap()).unwrap(), None);
+
+	let four_spaces = "{\n    \"version\": 6,\n    \"configurePresets\": []\n}\n";
+	let merged = add_presets(four_spaces).unwrap();
+	assert!(merged
+		.contains("\n    \"configurePresets\": [\n        {\n            \"name\": \"vcpkg\""));
+	assert!(!merged.contains("\n  \"version\""));
+
+	let tests_only = "{\n  \"version\": 6,\n  \"testPresets\": [\n    {\n      \"name\": \"unit\"\n    }\n  ]\n}\n";
+	assert_eq!(
+		remove_presets(&add_presets(tests_only).unwrap())
+			.unwrap()
+			.as_deref(),
+		Some(tests_only)
+	);
+}
+
+#[test]
+fn test_configuration_pins_baseline() {
+	let config = configuration("0123456789abcdef0123456789abcdef01234567");
+	assert!(config.contains("\"kind\": \"git\""));
+	assert!(config.contains("\"repository\": \"https://github.com/microsoft/vcpkg\""));
+	assert!(config.contains("\"baseline\": \"0123456789abcdef0123456789abcdef01234567\""));
+}
+
+#[test]
+#[serial]
+fn test_integrate_cmake_project() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_vcpkg_cmake_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+	let original_root = env::var_os("VCPKG_ROOT");
+
+	Command::new("git")
+		.args(["init", "-q", "vcpkg-root"])
+		.status()
+		.unwrap();
+	Command::new("git")
+		.args([
+			"-C",
+			"vcpkg-root",
+			"-c",
+			"user.name=sticks",
+			"-c",
+			"user.email=sticks@example.com",
+			"commit",
+			"-q",
+			"--allow-empty",
+			"-m",
+			"baseline",
+		])
+		.status()
+		.unwrap();
+	let head = Command::new("git")
+		.args(["-C", "vcpkg-root", "rev-parse", "HEAD"])
+		.output()
+		.unwrap();
+	let head = String::from_utf8_lossy(&head.stdout).trim().to_string();
+	env::set_var("VCPKG_ROOT", temp_dir.join("vcpkg-root"));
+
+	let listfile = sticks::CMakeGenerator.generate_build_file(Language::Cpp, "vcpkg_app");
+	fs::write("CMakeLists.txt", &listfile).unwrap();
+	fs::write(
+		"vcpkg.json",
+		sticks::package_managers::VcpkgGenerator.generate_manifest("vcpkg_app"),
+	)
+	.unwrap();
+
+	integrate().unwrap();
+	let updated = fs::read_to_string("CMakeLists.txt").unwrap();
+	assert!(updated.find("vcpkg.cmake").unwrap() < updated.find("project(").unwrap());
+	assert!(Path::new("CMakePresets.json").exists());
+	assert!(fs::read_to_string("vcpkg-configuration.json")
+		.unwrap()
+		.contains(&head));
+
+	add_dependencies(&["fmt".to_string(), "curl/8.6.0".to_string()]).unwrap();
+	let updated = fs::read_to_string("CMakeLists.txt").unwrap();
+	assert!(updated.contains("find_package(fmt REQUIRED)\nfind_package(CURL REQUIRED)\n"));
+	assert!(updated.contains("PRIVATE fmt::fmt CURL::libcurl)"));
+
+	remove_integration().unwrap();
+	assert_eq!(fs::read_to_string("CMakeLists.txt").unwrap(), listfile);
+	assert!(!Path::new("CMakePresets.json").exists());
+	assert!(!Path::new("vcpkg-configuration.json").exists());
+
+	match original_root {
+		Some(root) => env::set_var("VCPKG_ROOT", root),
+		None => env::remove_var("VCPKG_ROOT"),
+	}
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
+
+#[test]
+#[serial]
+fn test_integrate_without_vcpkg_root_skips_baseline() {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_vcpkg_noroot_{}_{}",
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+
+	fs::remove_dir_all(&temp_dir).ok();
+	fs::create_dir_all(&temp_dir).unwrap();
+	env::set_current_dir(&temp_dir).unwrap();
+	let original_root = env::var_os("VCPKG_ROOT");
+	env::remove_var("VCPKG_ROOT");
+
+	fs::write(
+		"CMakeLists.txt",
+		sticks::CMakeGenerator.generate_build_file(Language::C, "noroot"),
+	)
+	.unwrap();
+	fs::write(
+		"vcpkg.json",
+		sticks::package_managers::VcpkgGenerator.generate_manifest("noroot"),
+	)
+	.unwrap();
+
+	integrate().unwrap();
+	assert!(Path::new("CMakePresets.json").exists());
+	assert!(!Path::new("vcpkg-configuration.json").exists());
+
+	let user_configuration = "{\n  \"overlay-ports\": [\"ports\"]\n}\n";
+	fs::write("vcpkg-configuration.json", user_configuration).unwrap();
+	remove_integration().unwrap();
+	assert!(!Path::new("CMakePresets.json").exists());
+	assert_eq!(
+		fs::read_to_string("vcpkg-configuration.json").unwrap(),
+		user_configuration
+	);
+
+	if let Some(root) = original_root {
+		env::set_var("VCPKG_ROOT", root);
+	}
+	env::set_current_dir(&original_dir).unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+}
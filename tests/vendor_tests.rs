@@ -0,0 +1,224 @@
+use serial_test::serial;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use sticks::vendor::{
+	add_vendor, cmake_block, makefile_block, remove_vendor, update_vendor, vendored_dependencies,
+	VendoredDependency,
+};
+use sticks::{BuildSystemGenerator, Language, LanguageConsts};
+
+fn git(dir: &Path, args: &[&str]) -> String {
+	let output = Command::new("git")
+		.arg("-C")
+		.arg(dir)
+		.args([
+			"-c",
+			"user.name=sticks",
+			"-c",
+			"user.email=sticks@example.com",
+		])
+		.args(args)
+		.output()
+		.unwrap();
+	assert!(
+		output.status.success(),
+		"git {:?} failed: {}",
+		args,
+		String::from_utf8_lossy(&output.stderr)
+	);
+	String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+fn setup(label: &str, with_cmake: bool) -> (PathBuf, PathBuf, PathBuf) {
+	let temp_dir = env::temp_dir().join(format!(
+		"sticks_test_vendor_{}_{}_{}",
+		label,
+		std::process::id(),
+		std::time::SystemTime::now()
+			.duration_since(std::time::UNIX_EPOCH)
+			.unwrap()
+			.as_nanos()
+	));
+	let original_dir = env::current_dir().unwrap();
+	fs::remove_dir_all(&temp_dir).ok();
+
+	let work = temp_dir.join("mathlib");
+	fs::create_dir_all(work.join("src")).unwrap();
+	fs::create_dir_all(work.join("include")).unwrap();
+	fs::write(
+		work.join("src/mathlib.c"),
+		"#include \"mathlib.h\"\nint mathlib_answer(void) { int unused = 0; return 42; }\n",
+	)
+	.unwrap();
+	fs::write(
+		work.join("include/mathlib.h"),
+		"#pragma once\nint mathlib_answer(void);\n",
+	)
+	.unwrap();
+	if with_cmake {
+		fs::write(
+			work.join("CMakeLists.txt"),
+			"add_library(mathlib src/mathlib.c)\n",
+		)
+		.unwrap();
+	}
+	git(&temp_dir, &["init", "-q", "mathlib"]);
+	git(&work, &["add", "."]);
+	git(&work, &["commit", "-q", "-m", "initial"]);
+	let remote = temp_dir.join("mathlib.git");
+	git(
+		&temp_dir,
+		&[
+			"clone",
+			"-q",
+			"--bare",
+			work.to_str().unwrap(),
+			remote.to_str().unwrap(),
+		],
+	);
+
+	let project = temp_dir.join("app");
+	fs::create_dir_all(project.join("src")).unwrap();
+	git(&temp_dir, &["init", "-q", "app"]);
+	env::set_current_dir(&project).unwrap();
+	env::set_var("GIT_CONFIG_COUNT", "1");
+	env::set_var("GIT_CONFIG_KEY_0", "protocol.file.allow");
+	env::set_var("GIT_CONFIG_VALUE_0", "always");
+
+	(temp_dir, original_dir, remote)
+}
+
+fn teardown(temp_dir: &Path, original_dir: &Path) {
+	env::remove_var("GIT_CONFIG_COUNT");
+	env::remove_var("GIT_CONFIG_KEY_0");
+	env::remove_var("GIT_CONFIG_VALUE_0");
+	env::set_current_dir(original_dir).unwrap();
+	fs::remove_dir_all(temp_dir).ok();
+}
+
+#[test]
+fn test_vendor_blocks() {
+	let dependency = VendoredDependency {
+		name: "json-c".to_string(),
+		path: "third_party/json-c".to_string(),
+	};
+	let block = makefile_block(&dependency);
+	assert!(block.starts_with("VENDOR_JSON_C_FLAGS = -O2 -g -Ithird_party/json-c\n"));
+	assert!(block.contains(
+		"VENDOR_JSON_C_SRCS = $(wildcard third_party/json-c/*.c third_party/json-c/*.cpp"
+	));
+	assert!(block.contains(
+		"$(BUILD_DIR)/third_party/json-c/%.c.o: third_party/json-c/%.c\n\t@mkdir -p $(dir $@)\n\t$(CC) $(VENDOR_JSON_C_FLAGS) -c $< -o $@\n"
+	));
+	assert!(block.contains(
+		"$(BUILD_DIR)/third_party/json-c/%.cc.o: third_party/json-c/%.cc\n\t@mkdir -p $(dir $@)\n\t$(CXX) $(VENDOR_JSON_C_FLAGS)"
+	));
+	assert!(!block.contains("-Werror"));
+
+	assert_eq!(
+		cmake_block(&dependency, true, Some("json-c::json-c")),
+		"add_subdirectory(third_party/json-c EXCLUDE_FROM_ALL)\ntarget_link_libraries(${PROJECT_NAME} PRIVATE json-c::json-c)\n"
+	);
+	let block = cmake_block(&dependency, true, None);
+	assert!(block.starts_with("add_subdirectory(third_party/json-c EXCLUDE_FROM_ALL)\n"));
+	assert!(!block.contains("\ntarget_link_libraries"));
+	assert!(cmake_block(&dependency, false, None)
+		.contains("target_sources(${PROJECT_NAME} PRIVATE ${VENDOR_JSON_C_SOURCES})"));
+}
+
+#[test]
+#[serial]
+fn test_vendor_makefile_project() {
+	let (temp_dir, original_dir, remote) = setup("make", false);
+
+	let makefile = Language::C.generate_makefile_content("app");
+	fs::write("Makefile", &makefile).unwrap();
+	fs::write(
+		"src/main.c",
+		"#include \"mathlib.h\"\nint main(void) { return mathlib_answer() == 42 ? 0 : 1; }\n",
+	)
+	.unwrap();
+
+	add_vendor(remote.to_str().unwrap(), None, None).unwrap();
+	assert!(Path::new("third_party/mathlib/src/mathlib.c").exists());
+	assert_eq!(
+		vendored_dependencies().unwrap(),
+		vec![VendoredDependency {
+			name: "mathlib".to_string(),
+			path: "third_party/mathlib".to_string(),
+		}]
+	);
+	let updated = fs::read_to_string("Makefile").unwrap();
+	assert!(updated.contains("# sticks:begin vendor-mathlib"));
+	assert!(updated.find("OBJS =").unwrap() < updated.find("OBJS +=").unwrap());
+	assert!(add_vendor(remote.to_str().unwrap(), None, None).is_err());
+
+	let status = Command::new("make").arg("-s").status().unwrap();
+	assert!(status.success());
+	assert!(Command::new("bin/app").status().unwrap().success());
+
+	let work = temp_dir.join("mathlib");
+	fs::write(work.join("NEWS"), "v2\n").unwrap();
+	git(&work, &["add", "NEWS"]);
+	git(&work, &["commit", "-q", "-m", "v2"]);
+	git(&work, &["push", "-q", remote.to_str().unwrap(), "HEAD"]);
+	update_vendor(Some("mathlib")).unwrap();
+	assert!(Path::new("third_party/mathlib/NEWS").exists());
+
+	remove_vendor("mathlib").unwrap();
+	assert_eq!(fs::read_to_string("Makefile").unwrap(), makefile);
+	assert!(!Path::new("third_party").exists());
+	assert!(!Path::new(".gitmodules").exists());
+	assert!(vendored_dependencies().unwrap().is_empty());
+
+	teardown(&temp_dir, &original_dir);
+}
+
+#[test]
+#[serial]
+fn test_vendor_c_library_into_cpp_makefile_project() {
+	let (temp_dir, original_dir, remote) = setup("make_cpp", false);
+
+	fs::write("Makefile", Language::Cpp.generate_makefile_content("app")).unwrap();
+	fs::write(
+		"src/main.cpp",
+		"#include \"mathlib.h\"\nint main() { return mathlib_answer() == 42 ? 0 : 1; }\n",
+	)
+	.unwrap();
+
+	add_vendor(remote.to_str().unwrap(), None, None).unwrap();
+	let status = Command::new("make").arg("-s").status().unwrap();
+	assert!(status.success());
+	assert!(Path::new("build/third_party/mathlib/src/mathlib.c.o").exists());
+	assert!(Command::new("bin/app").status().unwrap().success());
+
+	teardown(&temp_dir, &original_dir);
+}
+
+#[test]
+#[serial]
+fn test_vendor_cmake_project_with_custom_path() {
+	let (temp_dir, original_dir, remote) = setup("cmake", true);
+
+	let listfile = sticks::CMakeGenerator.generate_build_file(Language::C, "app");
+	fs::write("CMakeLists.txt", &listfile).unwrap();
+
+	add_vendor(
+		remote.to_str().unwrap(),
+		Some("libs/mathlib/"),
+		Some("mathlib"),
+	)
+	.unwrap();
+	let updated = fs::read_to_string("CMakeLists.txt").unwrap();
+	assert!(updated.contains(
+		"add_subdirectory(libs/mathlib EXCLUDE_FROM_ALL)\ntarget_link_libraries(${PROJECT_NAME} PRIVATE mathlib)\n"
+	));
+
+	remove_vendor("libs/mathlib").unwrap();
+	assert_eq!(fs::read_to_string("CMakeLists.txt").unwrap(), listfile);
+	assert!(remove_vendor("mathlib").is_err());
+
+	teardown(&temp_dir, &original_dir);
+}